@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::stores::{version_key, StoreError, Versions};
+use crate::core::versioning;
+
+/// A collection of STIX objects shared together, as defined in [Section 8 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+/// Bundles are not STIX objects themselves, so they are (de)serialized on their own rather than as a `Box<dyn STIXObject>`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename = "bundle")]
+pub struct Bundle {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<Box<dyn STIXObject>>,
+}
+
+/// How bundles are merged.
+#[derive(Debug, Clone, Default)]
+pub struct MergeOptions {
+    /// Keeps every version of the objects, rather than only the latest one.
+    pub all_versions: bool,
+}
+
+impl Bundle {
+    /// Wraps the objects into a bundle with a new identifier.
+    pub fn new(objects: Vec<Box<dyn STIXObject>>) -> Bundle {
+        Bundle {
+            id: format!("bundle--{}", uuid::Uuid::new_v4()),
+            objects,
+        }
+    }
+
+    /// Merges bundles into a new one, with the objects in the order they first appear.
+    ///
+    /// The copies of a version (the same identifier and `modified` timestamp) are collapsed into the first one, which also collapses the SCOs sharing a deterministic identifier.
+    /// Unless every version is kept, only the latest version of each object remains. When the earlier versions of a grouping by the same creator refer to
+    /// objects its latest version leaves out, a new version referring to them all is made instead (see `versioning::new_version`), so as not to
+    /// give a different content to a version already shared.
+    pub fn merge(bundles: Vec<Bundle>, options: &MergeOptions) -> Result<Bundle, StoreError> {
+        let mut order = Vec::new();
+        let mut objects: HashMap<String, Versions> = HashMap::new();
+        for object in bundles.into_iter().flat_map(|bundle| bundle.objects) {
+            let (id, modified) = version_key(object.as_ref())?;
+            let versions = objects.entry(id.clone()).or_insert_with(|| {
+                order.push(id);
+                Versions::new()
+            });
+            versions.entry(modified).or_insert(object);
+        }
+        let mut merged = Vec::new();
+        for id in order {
+            let versions = objects.remove(&id).expect("every identifier seen has versions");
+            if options.all_versions {
+                merged.extend(versions.into_values());
+            } else {
+                merged.push(latest_version(versions));
+            }
+        }
+        Ok(Bundle::new(merged))
+    }
+}
+
+/// Returns the latest version of an object, or for a grouping a newer version adding the `object_refs` of its earlier versions by the same creator.
+fn latest_version(mut versions: Versions) -> Box<dyn STIXObject> {
+    let (_, latest) = versions.pop_last().expect("every identifier seen has versions");
+    let value = latest.to_value();
+    if versions.is_empty() || value.get("type").and_then(Value::as_str) != Some("grouping") {
+        return latest;
+    }
+    let creator = value.get("created_by_ref").cloned();
+    let latest_refs: Vec<Value> = value.get("object_refs").and_then(Value::as_array).cloned().unwrap_or_default();
+    let mut object_refs = latest_refs.clone();
+    for version in versions.values().rev().map(|version| version.to_value()) {
+        if version.get("created_by_ref").cloned() != creator {
+            continue;
+        }
+        for object_ref in version.get("object_refs").and_then(Value::as_array).into_iter().flatten() {
+            if !object_refs.contains(object_ref) {
+                object_refs.push(object_ref.clone());
+            }
+        }
+    }
+    if object_refs == latest_refs {
+        return latest;
+    }
+    versioning::new_version(latest.as_ref(), serde_json::json!({"object_refs": object_refs})).unwrap_or(latest)
+}
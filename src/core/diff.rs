@@ -0,0 +1,156 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use serde_json::Value;
+use crate::core::STIXObject;
+
+#[derive(Debug, PartialEq)]
+pub enum DiffError {
+    /// The objects compared have these different identifiers, so they are not versions of the same object.
+    DifferentIds(String, String),
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffError::DifferentIds(first, second) => write!(f, "{} and {} are not versions of the same object", first, second),
+        }
+    }
+}
+
+impl Error for DiffError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// The property or list item only appears in the new version, with this value.
+    Added(Value),
+    /// The property or list item only appears in the old version, with this value.
+    Removed(Value),
+    /// The property has a different value in each version.
+    Changed { old: Value, new: Value },
+}
+
+/// A change found at a path of the object: a property name, followed by `.name` for the properties of nested objects and `[index]` for list items.
+///
+/// The index of an added or changed item is its position in the new version, and that of a removed item its position in the old one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ChangeKind::Added(value) => write!(f, "+ {}: {}", self.path, value),
+            ChangeKind::Removed(value) => write!(f, "- {}: {}", self.path, value),
+            ChangeKind::Changed { old, new } => write!(f, "~ {}: {} -> {}", self.path, old, new),
+        }
+    }
+}
+
+/// The changes between two versions of an object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectDiff {
+    pub id: String,
+    pub changes: Vec<Change>,
+}
+
+impl ObjectDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes made to a property, including those made to its nested properties and list items.
+    pub fn changes_of<'a>(&'a self, property: &'a str) -> impl Iterator<Item = &'a Change> + 'a {
+        self.changes.iter().filter(move |change| {
+            change.path.strip_prefix(property).is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
+        })
+    }
+}
+
+/// Renders the changes one per line under the identifier, marking additions with `+`, removals with `-` and changes with `~`.
+impl fmt::Display for ObjectDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if self.changes.is_empty() {
+            return write!(f, " (no changes)");
+        }
+        for change in self.changes.iter() {
+            write!(f, "\n  {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two versions of an object, property by property.
+///
+/// Lists are compared item by item regardless of their order, so that the items added to or removed from `object_refs` show as such.
+/// The external references with the same `source_name`, and the granular markings with the same `marking_ref` or `lang`, are taken as the same item and their own changes are given.
+pub fn diff(old: &dyn STIXObject, new: &dyn STIXObject) -> Result<ObjectDiff, DiffError> {
+    let (old, new) = (old.to_value(), new.to_value());
+    let id = |value: &Value| value.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+    if id(&old) != id(&new) {
+        return Err(DiffError::DifferentIds(id(&old), id(&new)));
+    }
+    let mut changes = Vec::new();
+    diff_values("", &old, &new, &mut changes);
+    Ok(ObjectDiff { id: id(&old), changes })
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        _ if old == new => {}
+        (Value::Object(old), Value::Object(new)) => {
+            let properties: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for property in properties {
+                let path = if path.is_empty() { property.to_string() } else { format!("{}.{}", path, property) };
+                match (old.get(property), new.get(property)) {
+                    (Some(old), Some(new)) => diff_values(&path, old, new, changes),
+                    (Some(old), None) => changes.push(Change { path, kind: ChangeKind::Removed(old.clone()) }),
+                    (None, Some(new)) => changes.push(Change { path, kind: ChangeKind::Added(new.clone()) }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => diff_lists(path, old, new, changes),
+        _ => changes.push(Change { path: path.to_string(), kind: ChangeKind::Changed { old: old.clone(), new: new.clone() } }),
+    }
+}
+
+fn diff_lists(path: &str, old: &[Value], new: &[Value], changes: &mut Vec<Change>) {
+    let mut remaining: Vec<(usize, &Value)> = old.iter().enumerate().collect();
+    let mut unmatched = Vec::new();
+    for (index, item) in new.iter().enumerate() {
+        match remaining.iter().position(|(_, old)| *old == item) {
+            Some(position) => {
+                remaining.remove(position);
+            }
+            None => unmatched.push((index, item)),
+        }
+    }
+    for (index, item) in unmatched {
+        let key = item_key(path, item);
+        let same = key.as_ref().and_then(|key| remaining.iter().position(|(_, old)| item_key(path, old).as_ref() == Some(key)));
+        let item_path = format!("{}[{}]", path, index);
+        match same {
+            Some(position) => {
+                let (_, old) = remaining.remove(position);
+                diff_values(&item_path, old, item, changes);
+            }
+            None => changes.push(Change { path: item_path, kind: ChangeKind::Added(item.clone()) }),
+        }
+    }
+    for (index, item) in remaining {
+        changes.push(Change { path: format!("{}[{}]", path, index), kind: ChangeKind::Removed(item.clone()) });
+    }
+}
+
+/// The value identifying an item of the lists whose items can change from one version to another.
+fn item_key<'a>(path: &str, item: &'a Value) -> Option<&'a Value> {
+    match path {
+        "external_references" => item.get("source_name"),
+        "granular_markings" => item.get("marking_ref").or_else(|| item.get("lang")),
+        _ => None,
+    }
+}
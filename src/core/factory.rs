@@ -0,0 +1,73 @@
+use crate::core::sdos::builder::{BuildError, SdoBuilder};
+use crate::core::types::ExternalReference;
+
+/// Builds objects sharing the same defaults, such as the identity of their creator and their markings, as does the `ObjectFactory` of python-stix2.
+///
+/// The properties given to a builder prevail over the defaults, except for the lists of marking definitions and external references to which
+/// the defaults missing from them are appended, unless `replace_lists` is set. An external reference is taken as already given when one with
+/// the same `source_name` and `external_id` is.
+///
+/// ```
+/// use stix4rust::core::factory::ObjectFactory;
+/// use stix4rust::core::sdos::malware::Malware;
+///
+/// let factory = ObjectFactory {
+///     created_by_ref: Some("identity--f431f809-377b-45e0-aa1c-6a4751cae5ff".to_string()),
+///     object_marking_refs: vec!["marking-definition--f88d31f6-486f-44da-b317-01333bde0b82".to_string()],
+///     ..ObjectFactory::default()
+/// };
+/// let malware = factory.create(Malware::builder().malware_types(vec!["ransomware".to_string()]).is_family(true)).unwrap();
+/// assert_eq!(malware.created_by_ref, factory.created_by_ref);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ObjectFactory {
+    pub created_by_ref: Option<String>,
+    pub object_marking_refs: Vec<String>,
+    pub lang: Option<String>,
+    pub external_references: Vec<ExternalReference>,
+    /// Keeps the lists given to a builder as they are instead of appending the defaults to them.
+    pub replace_lists: bool,
+}
+
+impl ObjectFactory {
+    /// Applies the defaults to the builder of an SDO or an SRO, then builds the object.
+    pub fn create<B: SdoBuilder>(&self, mut builder: B) -> Result<B::Object, BuildError> {
+        let common = builder.common_properties();
+        if common.created_by_ref.is_none() {
+            common.created_by_ref = self.created_by_ref.clone();
+        }
+        if common.lang.is_none() {
+            common.lang = self.lang.clone();
+        }
+        if !self.object_marking_refs.is_empty() {
+            match &mut common.object_marking_refs {
+                Some(_) if self.replace_lists => {}
+                Some(object_marking_refs) => {
+                    let missing: Vec<String> = self.object_marking_refs.iter().filter(|marking| !object_marking_refs.contains(marking)).cloned().collect();
+                    object_marking_refs.extend(missing);
+                }
+                None => common.object_marking_refs = Some(self.object_marking_refs.clone()),
+            }
+        }
+        if !self.external_references.is_empty() {
+            match &mut common.external_references {
+                Some(_) if self.replace_lists => {}
+                Some(external_references) => {
+                    let missing: Vec<ExternalReference> = self
+                        .external_references
+                        .iter()
+                        .filter(|reference| !external_references.iter().any(|given| same_reference(given, reference)))
+                        .cloned()
+                        .collect();
+                    external_references.extend(missing);
+                }
+                None => common.external_references = Some(self.external_references.clone()),
+            }
+        }
+        builder.build()
+    }
+}
+
+fn same_reference(first: &ExternalReference, second: &ExternalReference) -> bool {
+    first.source_name == second.source_name && first.external_id == second.external_id
+}
@@ -0,0 +1,49 @@
+use std::collections::{HashSet, VecDeque};
+use serde_json::Value;
+use crate::core::bundle::Bundle;
+use crate::core::graph::navigation;
+use crate::core::stores::{Store, StoreError};
+
+/// Which of the objects referred to are gathered along with the seed.
+#[derive(Clone, Debug, Default)]
+pub struct ClosureOptions {
+    /// The types of the objects gathered, all of them if not given. The seed is always gathered.
+    pub types: Option<Vec<String>>,
+    /// How many references away from the seed the objects can be, without limit if not given.
+    pub max_depth: Option<usize>,
+}
+
+/// Gathers into a bundle the latest version of an object and of everything it refers to, directly or through the objects gathered:
+/// the objects of its `object_refs`, its creator, its marking definitions, the ends of the relationships, and so on.
+///
+/// The objects of the types left out are not gathered, nor are the objects they refer to through them, and the objects missing from the store
+/// (e. g. the TLP marking definitions) or that it cannot look up are skipped. The objects come breadth first from the seed, and nothing is returned
+/// if the seed is not stored.
+pub fn closure(store: &dyn Store, seed: &str, options: &ClosureOptions) -> Result<Option<Bundle>, StoreError> {
+    let seed_object = match store.get(seed)? {
+        Some(object) => object,
+        None => return Ok(None),
+    };
+    let mut objects = Vec::new();
+    let mut seen: HashSet<String> = vec![seed.to_string()].into_iter().collect();
+    let mut queue = VecDeque::from(vec![(seed_object, 0)]);
+    while let Some((object, depth)) = queue.pop_front() {
+        if options.max_depth.is_none_or(|max_depth| depth < max_depth) {
+            for (_, target) in navigation::references(&object.to_value()) {
+                if !seen.insert(target.clone()) {
+                    continue;
+                }
+                let referred = match navigation::find(store, &target)? {
+                    Some(referred) => referred,
+                    None => continue,
+                };
+                let object_type = referred.to_value().get("type").and_then(Value::as_str).unwrap_or_default().to_string();
+                if options.types.as_ref().is_none_or(|types| types.contains(&object_type)) {
+                    queue.push_back((referred, depth + 1));
+                }
+            }
+        }
+        objects.push(object);
+    }
+    Ok(Some(Bundle::new(objects)))
+}
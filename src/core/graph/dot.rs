@@ -0,0 +1,25 @@
+use crate::core::graph::export::Subgraph;
+use crate::core::graph::navigation::EdgeKind;
+
+/// Renders a subgraph in the DOT language of Graphviz. Relationships are drawn as solid edges labelled by their type,
+/// and embedded references as dashed edges labelled by their property.
+pub fn render(subgraph: &Subgraph) -> String {
+    let mut dot = String::from("digraph stix {\n    node [shape=box];\n");
+    for node in &subgraph.nodes {
+        dot.push_str(&format!("    \"{}\" [label=\"{}\", tooltip=\"{}\"];\n", escape(&node.id), escape(&node.label), escape(&node.object_type)));
+    }
+    for edge in &subgraph.edges {
+        let (label, style) = match &edge.kind {
+            EdgeKind::Relationship { relationship_type, .. } => (relationship_type, "solid"),
+            EdgeKind::Reference(property) => (property, "dashed"),
+        };
+        dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\", style={}];\n", escape(&edge.source), escape(&edge.target), escape(label), style));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Escapes the text of a quoted DOT identifier.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::graph::navigation::{self, Direction, Edge};
+use crate::core::stores::{Store, StoreError};
+
+/// Which part of a collection of objects is exported.
+#[derive(Clone, Debug, Default)]
+pub struct ExportOptions {
+    /// The types of the objects kept as nodes, all of them if not given.
+    pub types: Option<Vec<String>>,
+    /// The object from which the graph is explored, the whole collection being exported if not given.
+    pub seed: Option<String>,
+    /// How many edges away from the seed the nodes can be, without limit if not given.
+    pub max_depth: Option<usize>,
+}
+
+/// An object drawn as a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub id: String,
+    pub object_type: String,
+    /// The `name` of the object or, lacking it, its `value` (as for many SCOs) or its identifier.
+    pub label: String,
+}
+
+/// The nodes and edges to draw, sorted so that the renderings do not change from one export to another.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Subgraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl Subgraph {
+    /// Selects the subgraph of a collection of objects, e. g. those of a bundle.
+    ///
+    /// Every object is a node except the relationships and sightings, which become edges along with the embedded references.
+    /// The edges whose ends are not both nodes are left out. Once the types are filtered, the nodes are those reached from the seed,
+    /// following the edges in both directions.
+    pub fn from_objects(objects: &[Box<dyn STIXObject>], options: &ExportOptions) -> Subgraph {
+        Subgraph::from_values(objects.iter().map(|object| object.to_value()).collect(), options)
+    }
+
+    /// Selects the subgraph of the objects of a store (see `from_objects`). With a seed, only the objects the seed leads to are loaded.
+    pub fn from_store(store: &dyn Store, options: &ExportOptions) -> Result<Subgraph, StoreError> {
+        let mut values = Vec::new();
+        match &options.seed {
+            Some(seed) => {
+                for visit in navigation::traverse(store, seed, options.max_depth) {
+                    let object = visit?.object.to_value();
+                    let id = object["id"].as_str().unwrap_or_default().to_string();
+                    values.push(object);
+                    for relationship in navigation::relationships(store, &id, None, Direction::Both)? {
+                        values.push(relationship.to_value());
+                    }
+                }
+            }
+            None => {
+                for id in store.ids()? {
+                    values.extend(store.get(&id)?.map(|object| object.to_value()));
+                }
+            }
+        }
+        Ok(Subgraph::from_values(values, options))
+    }
+
+    fn from_values(values: Vec<Value>, options: &ExportOptions) -> Subgraph {
+        let mut nodes = BTreeMap::new();
+        let mut edges = Vec::new();
+        for value in &values {
+            let object_type = value.get("type").and_then(Value::as_str).unwrap_or_default();
+            if object_type == "relationship" || object_type == "sighting" {
+                let relationship_edges = navigation::relationship_edges(value);
+                // A sighting not telling where the object was sighted stands at the source of its edge, so it is drawn as a node too.
+                let is_source = relationship_edges.iter().any(|edge| Some(edge.source.as_str()) == value.get("id").and_then(Value::as_str));
+                edges.extend(relationship_edges);
+                if !is_source {
+                    continue;
+                }
+            }
+            if options.types.as_ref().is_some_and(|types| !types.iter().any(|kept| kept == object_type)) {
+                continue;
+            }
+            let id = value.get("id").and_then(Value::as_str).unwrap_or_default();
+            let label = ["name", "value"].iter().find_map(|property| value.get(*property).and_then(Value::as_str)).unwrap_or(id);
+            nodes.insert(id.to_string(), Node { id: id.to_string(), object_type: object_type.to_string(), label: label.to_string() });
+            edges.extend(navigation::reference_edges(value));
+        }
+        edges.retain(|edge| nodes.contains_key(&edge.source) && nodes.contains_key(&edge.target));
+        if let Some(seed) = &options.seed {
+            let reached = reach(seed, &edges, options.max_depth);
+            nodes.retain(|id, _| reached.contains(id.as_str()));
+            edges.retain(|edge| reached.contains(edge.source.as_str()) && reached.contains(edge.target.as_str()));
+        }
+        edges.sort_by(|edge, other| (&edge.source, &edge.target, format!("{:?}", edge.kind)).cmp(&(&other.source, &other.target, format!("{:?}", other.kind))));
+        edges.dedup();
+        Subgraph { nodes: nodes.into_values().collect(), edges }
+    }
+}
+
+/// The identifiers of the nodes reached from the seed within the given depth, following the edges in both directions.
+fn reach(seed: &str, edges: &[Edge], max_depth: Option<usize>) -> HashSet<String> {
+    let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges {
+        neighbours.entry(&edge.source).or_default().push(&edge.target);
+        neighbours.entry(&edge.target).or_default().push(&edge.source);
+    }
+    let mut reached: HashSet<&str> = vec![seed].into_iter().collect();
+    let mut queue = VecDeque::from(vec![(seed, 0)]);
+    while let Some((id, depth)) = queue.pop_front() {
+        if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            continue;
+        }
+        for next in neighbours.get(id).into_iter().flatten() {
+            if reached.insert(next) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+    reached.into_iter().map(str::to_string).collect()
+}
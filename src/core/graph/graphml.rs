@@ -0,0 +1,44 @@
+use crate::core::graph::export::Subgraph;
+use crate::core::graph::navigation::EdgeKind;
+
+/// Renders a subgraph as a GraphML document. Nodes carry their `label` and `type`, and edges their `label`
+/// (the relationship type or the property of the reference) and `kind` (`relationship` or `reference`).
+pub fn render(subgraph: &Subgraph) -> String {
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n",
+        "  <key id=\"edge_label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        "  <graph id=\"stix\" edgedefault=\"directed\">\n",
+    ));
+    for node in &subgraph.nodes {
+        xml.push_str(&format!(
+            "    <node id=\"{}\">\n      <data key=\"label\">{}</data>\n      <data key=\"type\">{}</data>\n    </node>\n",
+            escape(&node.id),
+            escape(&node.label),
+            escape(&node.object_type)
+        ));
+    }
+    for edge in &subgraph.edges {
+        let (label, kind) = match &edge.kind {
+            EdgeKind::Relationship { relationship_type, .. } => (relationship_type, "relationship"),
+            EdgeKind::Reference(property) => (property, "reference"),
+        };
+        xml.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\">\n      <data key=\"edge_label\">{}</data>\n      <data key=\"kind\">{}</data>\n    </edge>\n",
+            escape(&edge.source),
+            escape(&edge.target),
+            escape(label),
+            kind
+        ));
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+/// Escapes the text of an XML attribute or element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
@@ -0,0 +1,21 @@
+/// This module handles the graph formed by the objects of a store, that analysts navigate pivoting from one object to the others (see `navigation`).
+/// The edges of the graph are the relationships (`Relationship` objects and, as relationships of type `sighted`, `Sighting` objects)
+/// and the references embedded in the objects, i. e. their `*_ref` and `*_refs` properties.
+/// The graph of a bundle or a store, or the part of it around an object, can be drawn with Graphviz (see `dot`) or the tools reading GraphML (see `graphml`),
+/// once selected through the `export` module. An object can also be shared along with everything it refers to, directly or not, as a bundle (see `closure`).
+///
+/// ```
+/// use stix4rust::core::graph::navigation::{self, Direction};
+/// use stix4rust::core::stores::memory::MemoryStore;
+///
+/// let mut store = MemoryStore::new();
+/// store.add(serde_json::from_str(r#"{"type": "relationship", "spec_version": "2.1", "id": "relationship--1", "created": "2020-01-01T00:00:00Z",
+///     "modified": "2020-01-01T00:00:00Z", "relationship_type": "uses", "source_ref": "malware--1", "target_ref": "infrastructure--1"}"#).unwrap()).unwrap();
+/// let relationships = navigation::relationships(&store, "malware--1", Some("uses"), Direction::Source).unwrap();
+/// assert_eq!(relationships.len(), 1);
+/// ```
+pub mod closure;
+pub mod dot;
+pub mod export;
+pub mod graphml;
+pub mod navigation;
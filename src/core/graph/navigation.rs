@@ -0,0 +1,266 @@
+use std::collections::{HashSet, VecDeque};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::stores::{Store, StoreError};
+use crate::core::stores::filter::{Filter, Operator};
+
+/// The relationship type given to the sightings, going from the identities or locations of their `where_sighted_refs` to the object sighted,
+/// or from the sighting itself when it does not tell where the object was sighted.
+pub const SIGHTED: &str = "sighted";
+
+/// Which end of the relationships an object stands at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Both,
+    /// The object is the source of the relationships.
+    Source,
+    /// The object is the target of the relationships.
+    Target,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// A reference embedded in the source, with the dotted path of its property (e. g. `object_refs` or `granular_markings.marking_ref`).
+    Reference(String),
+    /// A `Relationship`, or a `Sighting` of type `sighted`, with its identifier.
+    Relationship { relationship_type: String, id: String },
+}
+
+/// A directed edge between two objects, given by their identifiers.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub source: String,
+    pub target: String,
+    pub kind: EdgeKind,
+}
+
+impl Edge {
+    /// The end of the edge opposite to the given object.
+    pub fn other_end(&self, id: &str) -> &str {
+        if self.source == id { &self.target } else { &self.source }
+    }
+}
+
+/// Returns the targets of the `*_ref` and `*_refs` properties of an object, nested ones included, with the dotted path of their property.
+///
+/// The deprecated `objects` of the observed data are left out, as their references are keys of that dictionary and not identifiers.
+pub fn references(object: &Value) -> Vec<(String, String)> {
+    let mut references = Vec::new();
+    match object {
+        Value::Object(properties) if object.get("type").and_then(Value::as_str) == Some("observed-data") => {
+            let mut properties = properties.clone();
+            properties.remove("objects");
+            collect_references(&Value::Object(properties), "", &mut references);
+        }
+        _ => collect_references(object, "", &mut references),
+    }
+    references
+}
+
+fn collect_references(value: &Value, path: &str, references: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(properties) => {
+            for (name, value) in properties {
+                let property = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                match value {
+                    Value::String(target) if name.ends_with("_ref") => references.push((property, target.clone())),
+                    Value::Array(targets) if name.ends_with("_refs") => {
+                        references.extend(targets.iter().filter_map(Value::as_str).map(|target| (property.clone(), target.to_string())))
+                    }
+                    _ => collect_references(value, &property, references),
+                }
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_references(value, path, references)),
+        _ => {}
+    }
+}
+
+/// Returns the edges going out of an object through its embedded references.
+pub fn reference_edges(object: &Value) -> Vec<Edge> {
+    let source = object.get("id").and_then(Value::as_str).unwrap_or_default();
+    references(object)
+        .into_iter()
+        .map(|(property, target)| Edge { source: source.to_string(), target, kind: EdgeKind::Reference(property) })
+        .collect()
+}
+
+/// Returns the edges stood for by a relationship or a sighting, or nothing for the other objects.
+pub fn relationship_edges(object: &Value) -> Vec<Edge> {
+    let text = |property: &str| object.get(property).and_then(Value::as_str).map(str::to_string);
+    let id = text("id").unwrap_or_default();
+    match object.get("type").and_then(Value::as_str) {
+        Some("relationship") => match (text("source_ref"), text("target_ref")) {
+            (Some(source), Some(target)) => {
+                let relationship_type = text("relationship_type").unwrap_or_default();
+                vec![Edge { source, target, kind: EdgeKind::Relationship { relationship_type, id } }]
+            }
+            _ => Vec::new(),
+        },
+        Some("sighting") => {
+            let target = text("sighting_of_ref").unwrap_or_default();
+            let mut sources: Vec<String> = object.get("where_sighted_refs").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).map(str::to_string).collect();
+            if sources.is_empty() {
+                sources.push(id.clone());
+            }
+            let kind = EdgeKind::Relationship { relationship_type: SIGHTED.to_string(), id };
+            sources.into_iter().map(|source| Edge { source, target: target.clone(), kind: kind.clone() }).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the relationships and sightings involving an object, optionally of a single type and with the object at one end only.
+pub fn relationships(store: &dyn Store, id: &str, relationship_type: Option<&str>, direction: Direction) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+    let mut found = Vec::new();
+    let mut seen = HashSet::new();
+    for edge_filters in edge_queries(id, relationship_type, direction) {
+        for object in store.query(&edge_filters)? {
+            let value = object.to_value();
+            let involved = relationship_edges(&value).iter().any(|edge| match direction {
+                Direction::Both => edge.source == id || edge.target == id,
+                Direction::Source => edge.source == id,
+                Direction::Target => edge.target == id,
+            });
+            if involved && seen.insert(value["id"].as_str().unwrap_or_default().to_string()) {
+                found.push(object);
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// The queries finding the relationships and sightings of an object.
+fn edge_queries(id: &str, relationship_type: Option<&str>, direction: Direction) -> Vec<Vec<Filter>> {
+    let mut queries = Vec::new();
+    if relationship_type.is_none_or(|relationship_type| relationship_type != SIGHTED) {
+        let mut base = vec![Filter::new("type", Operator::Equal, "relationship")];
+        base.extend(relationship_type.map(|relationship_type| Filter::new("relationship_type", Operator::Equal, relationship_type)));
+        if direction != Direction::Target {
+            queries.push([base.clone(), vec![Filter::new("source_ref", Operator::Equal, id)]].concat());
+        }
+        if direction != Direction::Source {
+            queries.push([base, vec![Filter::new("target_ref", Operator::Equal, id)]].concat());
+        }
+    }
+    if relationship_type.is_none_or(|relationship_type| relationship_type == SIGHTED) {
+        let base = vec![Filter::new("type", Operator::Equal, "sighting")];
+        if direction != Direction::Target {
+            queries.push([base.clone(), vec![Filter::new("where_sighted_refs", Operator::Contains, id)]].concat());
+            queries.push([base.clone(), vec![Filter::new("id", Operator::Equal, id)]].concat());
+        }
+        if direction != Direction::Source {
+            queries.push([base, vec![Filter::new("sighting_of_ref", Operator::Equal, id)]].concat());
+        }
+    }
+    queries
+}
+
+/// Returns the objects at the other end of the relationships and sightings involving an object (see `relationships`).
+/// The objects referred to but missing from the store are left out.
+pub fn related_to(store: &dyn Store, id: &str, relationship_type: Option<&str>, direction: Direction) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+    let mut related = Vec::new();
+    let mut seen = HashSet::new();
+    for relationship in relationships(store, id, relationship_type, direction)? {
+        for edge in relationship_edges(&relationship.to_value()) {
+            let other = match direction {
+                Direction::Source if edge.source == id => &edge.target,
+                Direction::Target if edge.target == id => &edge.source,
+                Direction::Both if edge.source == id || edge.target == id => edge.other_end(id),
+                _ => continue,
+            };
+            if seen.insert(other.to_string()) {
+                related.extend(find(store, other)?);
+            }
+        }
+    }
+    Ok(related)
+}
+
+/// Looks an object up, taking the identifiers a store cannot look up (e. g. malformed ones) as those of missing objects.
+pub(crate) fn find(store: &dyn Store, id: &str) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+    match store.get(id) {
+        Err(StoreError::InvalidId(_)) => Ok(None),
+        result => result,
+    }
+}
+
+/// Returns the identity that created an object, if it is known and stored.
+pub fn creator_of(store: &dyn Store, object: &dyn STIXObject) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+    match object.to_value().get("created_by_ref").and_then(Value::as_str) {
+        Some(creator) => find(store, creator),
+        None => Ok(None),
+    }
+}
+
+/// An object reached by a traversal, with its distance to the starting object and the edge it was reached through.
+#[derive(Debug)]
+pub struct Visit {
+    pub object: Box<dyn STIXObject>,
+    pub depth: usize,
+    pub edge: Option<Edge>,
+}
+
+/// Walks the graph breadth first from an object, following the relationships and sightings in both directions as well as
+/// the references embedded in the objects reached. Each object is visited once, and the objects missing from the store are skipped.
+pub struct Traversal<'a> {
+    store: &'a dyn Store,
+    max_depth: Option<usize>,
+    queue: VecDeque<(String, usize, Option<Edge>)>,
+    visited: HashSet<String>,
+}
+
+/// Starts a traversal from an object, going no further than `max_depth` edges away from it if given.
+pub fn traverse<'a>(store: &'a dyn Store, id: &str, max_depth: Option<usize>) -> Traversal<'a> {
+    Traversal {
+        store,
+        max_depth,
+        queue: VecDeque::from(vec![(id.to_string(), 0, None)]),
+        visited: vec![id.to_string()].into_iter().collect(),
+    }
+}
+
+impl Traversal<'_> {
+    /// The edges leading from an object to its neighbours.
+    fn edges(&self, id: &str, object: &Value) -> Result<Vec<Edge>, StoreError> {
+        let mut edges = reference_edges(object);
+        for relationship in relationships(self.store, id, None, Direction::Both)? {
+            edges.extend(relationship_edges(&relationship.to_value()).into_iter().filter(|edge| edge.source == id || edge.target == id));
+        }
+        Ok(edges)
+    }
+}
+
+impl Iterator for Traversal<'_> {
+    type Item = Result<Visit, StoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((id, depth, edge)) = self.queue.pop_front() {
+            let object = match find(self.store, &id) {
+                Ok(Some(object)) => object,
+                Ok(None) => continue,
+                Err(error) => {
+                    self.queue.clear();
+                    return Some(Err(error));
+                }
+            };
+            if self.max_depth.is_none_or(|max_depth| depth < max_depth) {
+                let edges = match self.edges(&id, &object.to_value()) {
+                    Ok(edges) => edges,
+                    Err(error) => {
+                        self.queue.clear();
+                        return Some(Err(error));
+                    }
+                };
+                for next in edges {
+                    let other = next.other_end(&id).to_string();
+                    if self.visited.insert(other.clone()) {
+                        self.queue.push_back((other, depth + 1, Some(next)));
+                    }
+                }
+            }
+            return Some(Ok(Visit { object, depth, edge }));
+        }
+        None
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+use std::fmt;
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::graph::navigation;
+use crate::core::stores::{Store, StoreError};
+
+/// The identifiers of the TLP marking definitions, which are defined by [Section 7.2.1.4 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html) and so always resolve.
+pub const TLP_MARKING_DEFINITIONS: [&str; 4] = [
+    "marking-definition--613f2e26-407d-48c7-9eca-b8e91df99dc9",
+    "marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da",
+    "marking-definition--f88d31f6-486f-44da-b317-01333bde0b82",
+    "marking-definition--5e57c739-391a-4eb3-b6be-7d15ca92d5ed",
+];
+
+/// The types a reference may point to.
+#[derive(Clone, Copy, Debug)]
+enum Allowed {
+    OneOf(&'static [&'static str]),
+    NoneOf(&'static [&'static str]),
+}
+
+const NOT_SDO_OR_SCO: &[&str] = &["relationship", "sighting", "marking-definition", "language-content", "bundle"];
+const ADDRESSES: &[&str] = &["ipv4-addr", "ipv6-addr", "mac-addr", "domain-name"];
+
+/// The types allowed for the references, by object type (`*` for any) and dotted property path.
+const TYPE_RULES: &[(&str, &str, Allowed)] = &[
+    ("*", "created_by_ref", Allowed::OneOf(&["identity"])),
+    ("*", "object_marking_refs", Allowed::OneOf(&["marking-definition"])),
+    ("*", "granular_markings.marking_ref", Allowed::OneOf(&["marking-definition"])),
+    ("malware", "sample_refs", Allowed::OneOf(&["file", "artifact"])),
+    ("relationship", "source_ref", Allowed::NoneOf(NOT_SDO_OR_SCO)),
+    ("relationship", "target_ref", Allowed::NoneOf(NOT_SDO_OR_SCO)),
+    ("sighting", "sighting_of_ref", Allowed::NoneOf(NOT_SDO_OR_SCO)),
+    ("sighting", "observed_data_refs", Allowed::OneOf(&["observed-data"])),
+    ("sighting", "where_sighted_refs", Allowed::OneOf(&["identity", "location"])),
+    ("directory", "contains_refs", Allowed::OneOf(&["file", "directory"])),
+    ("domain-name", "resolves_to_refs", Allowed::OneOf(&["ipv4-addr", "ipv6-addr", "domain-name"])),
+    ("email-addr", "belongs_to_ref", Allowed::OneOf(&["user-account"])),
+    ("email-message", "from_ref", Allowed::OneOf(&["email-addr"])),
+    ("email-message", "sender_ref", Allowed::OneOf(&["email-addr"])),
+    ("email-message", "to_refs", Allowed::OneOf(&["email-addr"])),
+    ("email-message", "cc_refs", Allowed::OneOf(&["email-addr"])),
+    ("email-message", "bcc_refs", Allowed::OneOf(&["email-addr"])),
+    ("email-message", "raw_email_ref", Allowed::OneOf(&["artifact"])),
+    ("email-message", "body_multipart.body_raw_ref", Allowed::OneOf(&["artifact", "file"])),
+    ("file", "parent_directory_ref", Allowed::OneOf(&["directory"])),
+    ("file", "content_ref", Allowed::OneOf(&["artifact"])),
+    ("ipv4-addr", "resolves_to_refs", Allowed::OneOf(&["mac-addr"])),
+    ("ipv4-addr", "belongs_to_refs", Allowed::OneOf(&["autonomous-system"])),
+    ("ipv6-addr", "resolves_to_refs", Allowed::OneOf(&["mac-addr"])),
+    ("ipv6-addr", "belongs_to_refs", Allowed::OneOf(&["autonomous-system"])),
+    ("network-traffic", "src_ref", Allowed::OneOf(ADDRESSES)),
+    ("network-traffic", "dst_ref", Allowed::OneOf(ADDRESSES)),
+    ("network-traffic", "src_payload_ref", Allowed::OneOf(&["artifact"])),
+    ("network-traffic", "dst_payload_ref", Allowed::OneOf(&["artifact"])),
+    ("network-traffic", "encapsulates_refs", Allowed::OneOf(&["network-traffic"])),
+    ("network-traffic", "encapsulated_by_ref", Allowed::OneOf(&["network-traffic"])),
+    ("process", "opened_connection_refs", Allowed::OneOf(&["network-traffic"])),
+    ("process", "creator_user_ref", Allowed::OneOf(&["user-account"])),
+    ("process", "image_ref", Allowed::OneOf(&["file"])),
+    ("process", "parent_ref", Allowed::OneOf(&["process"])),
+    ("process", "child_refs", Allowed::OneOf(&["process"])),
+    ("windows-registry-key", "creator_user_ref", Allowed::OneOf(&["user-account"])),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// No object with the identifier referred to is found.
+    Unresolved,
+    /// The identifier referred to is of a type the property does not allow.
+    DisallowedType,
+}
+
+/// A reference of an object breaking the referential integrity, with the dotted path of its property (e. g. `object_refs` or `granular_markings.marking_ref`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub source: String,
+    pub property: String,
+    pub target: String,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let problem = match self.kind {
+            ViolationKind::Unresolved => "which is not found",
+            ViolationKind::DisallowedType => "whose type is not allowed",
+        };
+        write!(f, "the {} of {} refers to {}, {}", self.property, self.source, self.target, problem)
+    }
+}
+
+/// Checks the references of the objects (e. g. those of a bundle) against the objects themselves.
+///
+/// Every version is checked, and the references to the TLP marking definitions are always resolved.
+pub fn check_references(objects: &[Box<dyn STIXObject>]) -> Vec<Violation> {
+    let values: Vec<Value> = objects.iter().map(|object| object.to_value()).collect();
+    let ids: HashSet<&str> = values.iter().filter_map(|value| value.get("id").and_then(Value::as_str)).collect();
+    values.iter().flat_map(|value| check_object(value, |id| ids.contains(id))).collect()
+}
+
+/// Checks the references of the latest version of every object of a store against the objects stored.
+pub fn check_store_references(store: &dyn Store) -> Result<Vec<Violation>, StoreError> {
+    let ids: HashSet<String> = store.ids()?.into_iter().collect();
+    let mut values: Vec<Value> = store.query(&[])?.iter().map(|object| object.to_value()).collect();
+    values.sort_by(|first, second| first.get("id").and_then(Value::as_str).cmp(&second.get("id").and_then(Value::as_str)));
+    Ok(values.iter().flat_map(|value| check_object(value, |id| ids.contains(id))).collect())
+}
+
+fn check_object(value: &Value, resolves: impl Fn(&str) -> bool) -> Vec<Violation> {
+    let source = value.get("id").and_then(Value::as_str).unwrap_or_default();
+    let object_type = value.get("type").and_then(Value::as_str).unwrap_or_default();
+    let mut violations = Vec::new();
+    for (property, target) in navigation::references(value) {
+        let kind = if !allows(object_type, &property, &target) {
+            ViolationKind::DisallowedType
+        } else if !resolves(&target) && !TLP_MARKING_DEFINITIONS.contains(&target.as_str()) {
+            ViolationKind::Unresolved
+        } else {
+            continue;
+        };
+        violations.push(Violation { source: source.to_string(), property, target, kind });
+    }
+    violations
+}
+
+fn allows(object_type: &str, property: &str, target: &str) -> bool {
+    let target_type = target.split("--").next().unwrap_or_default();
+    let rule = TYPE_RULES.iter().find(|(rule_type, rule_property, _)| (*rule_type == "*" || *rule_type == object_type) && *rule_property == property);
+    match rule {
+        Some((_, _, Allowed::OneOf(types))) => types.contains(&target_type),
+        Some((_, _, Allowed::NoneOf(types))) => !types.contains(&target_type),
+        None => true,
+    }
+}
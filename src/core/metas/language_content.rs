@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError};
+use crate::core::STIXObject;
+use crate::core::types::{ExternalReference, GranularMarking};
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct LanguageContent {
+    // Required common properties
+    pub id: String,
+    pub spec_version: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_references: Option<Vec<ExternalReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    // Specific properties
+    pub object_ref: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_modified: Option<DateTime<Utc>>,
+    #[validate(custom = "validate_language_tags")]
+    pub contents: HashMap<String, HashMap<String, Value>>
+}
+
+#[typetag::serde(name = "language-content")]
+impl STIXObject for LanguageContent {}
+
+impl LanguageContent {
+    /// Whether this content provides translations for the given version of an object.
+    /// The object is identified by its `id` and, if `object_modified` is set, its `modified` timestamp.
+    pub fn applies_to(&self, object: &Value) -> bool {
+        if object.get("id").and_then(Value::as_str) != Some(self.object_ref.as_str()) {
+            return false;
+        }
+        match &self.object_modified {
+            Some(object_modified) => object
+                .get("modified")
+                .and_then(Value::as_str)
+                .and_then(|modified| DateTime::parse_from_rfc3339(modified).ok())
+                .is_some_and(|modified| modified == *object_modified),
+            None => true,
+        }
+    }
+
+    /// Returns the translated properties for the best match of `language`, if any.
+    /// Exact tags are preferred and, otherwise, a more specific tag is accepted (e. g. `es-ES` for `es`).
+    pub fn contents_for(&self, language: &str) -> Option<(&String, &HashMap<String, Value>)> {
+        let language = language.to_lowercase();
+        self.contents
+            .iter()
+            .find(|(tag, _)| tag.to_lowercase() == language)
+            .or_else(|| {
+                let prefix = format!("{}-", language);
+                let mut candidates: Vec<_> = self.contents
+                    .iter()
+                    .filter(|(tag, _)| tag.to_lowercase().starts_with(&prefix))
+                    .collect();
+                candidates.sort_by(|a, b| a.0.cmp(b.0));
+                candidates.into_iter().next()
+            })
+    }
+}
+
+/// Checks that every key of the `contents` property is a valid RFC 5646 language tag.
+fn validate_language_tags(contents: &HashMap<String, HashMap<String, Value>>) -> Result<(), ValidationError> {
+    if contents.is_empty() {
+        return Err(ValidationError::new("empty_contents"));
+    }
+    for tag in contents.keys() {
+        if langtag::LanguageTag::parse(tag.as_str()).is_err() {
+            return Err(ValidationError::new("invalid_language_tag"));
+        }
+    }
+    Ok(())
+}
+
+/// Returns a copy of `object` with its translatable properties replaced by the translations found in `language_contents`.
+///
+/// The `preferred` languages are tried in order for each property, so a property missing in the first language falls back to the next one.
+/// Only the language contents targeting the object `id` (and its `modified` timestamp when `object_modified` is set) are considered, the most recent ones first.
+/// The `lang` property of the result is set to the first preferred language that provided at least one translation.
+pub fn translate(
+    object: &dyn STIXObject,
+    language_contents: &[LanguageContent],
+    preferred: &[&str],
+) -> serde_json::Result<Box<dyn STIXObject>> {
+    let mut value = object.to_value();
+    let mut applicable: Vec<&LanguageContent> = language_contents
+        .iter()
+        .filter(|content| content.applies_to(&value))
+        .collect();
+    applicable.sort_by_key(|content| std::cmp::Reverse(content.modified));
+
+    let mut translated: Vec<String> = Vec::new();
+    let mut chosen_language: Option<String> = None;
+    for language in preferred {
+        for content in &applicable {
+            let (tag, properties) = match content.contents_for(language) {
+                Some(found) => found,
+                None => continue,
+            };
+            for (property, translation) in properties {
+                if translated.contains(property) || property == "id" || property == "type" {
+                    continue;
+                }
+                if let Some(original) = value.get_mut(property) {
+                    merge_translation(original, translation);
+                    translated.push(property.clone());
+                    if chosen_language.is_none() {
+                        chosen_language = Some(tag.clone());
+                    }
+                }
+            }
+        }
+    }
+    if let (Some(language), Some(map)) = (chosen_language, value.as_object_mut()) {
+        map.insert("lang".to_string(), Value::String(language));
+    }
+    <dyn STIXObject>::from_value(value)
+}
+
+/// Overwrites `original` with `translation` following the rules for nested contents:
+/// lists are translated item by item, leaving untouched the items translated as an empty string, and objects key by key.
+fn merge_translation(original: &mut Value, translation: &Value) {
+    match (original, translation) {
+        (Value::Array(items), Value::Array(translated_items)) => {
+            for (item, translated_item) in items.iter_mut().zip(translated_items) {
+                if translated_item.as_str() != Some("") {
+                    merge_translation(item, translated_item);
+                }
+            }
+        }
+        (Value::Object(map), Value::Object(translated_map)) => {
+            for (key, translated_value) in translated_map {
+                if let Some(item) = map.get_mut(key) {
+                    merge_translation(item, translated_value);
+                }
+            }
+        }
+        (original, translation) => *original = translation.clone(),
+    }
+}
@@ -0,0 +1,7 @@
+/// This module defines de Rust structures that represent the STIX Meta Objects, as defined in [Section 7 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+/// The set of STIX Meta Objects of this crate includes the following structures, being the name of the struct itself specified between brackets:
+///
+/// - Language Content (`LanguageContent`)
+///
+/// Meta objects do not describe CTI concepts on their own but enrich other objects, e. g. providing translations of their text properties.
+pub mod language_content;
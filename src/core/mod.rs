@@ -1,9 +1,19 @@
 use std::fmt::Debug;
+use serde_json::Value;
+pub mod bundle;
+pub mod diff;
+pub mod factory;
+pub mod graph;
+pub mod integrity;
+pub mod metas;
 pub mod patterns;
 pub mod scos;
 pub mod sdos;
+pub mod similarity;
 pub mod sros;
+pub mod stores;
 pub mod types;
+pub mod versioning;
 
 /// This trait implements the basic functionalities for STIX objects.
 
@@ -26,4 +36,34 @@ pub trait STIXObject : Debug {
     // fn matches_stix_type(&self, new_type: String) -> bool {
     //     self.type == new_type
     // }
-}
\ No newline at end of file
+}
+
+/// Generic access to any STIX object through its JSON representation.
+///
+/// These helpers let the features that work across object types (translations, stores, filters…) inspect and rebuild objects without knowing their concrete struct.
+impl dyn STIXObject + '_ {
+    /// Returns the JSON representation of the object, including its `type` property.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).expect("STIX objects are always serializable")
+    }
+
+    /// Rebuilds a STIX object from its JSON representation, dispatching on its `type` property.
+    pub fn from_value(value: Value) -> serde_json::Result<Box<dyn STIXObject>> {
+        serde_json::from_value(value)
+    }
+
+    /// Compares the object, as an older version, with a newer version of it (see `diff::diff`).
+    pub fn diff(&self, new: &dyn STIXObject) -> Result<diff::ObjectDiff, diff::DiffError> {
+        diff::diff(self, new)
+    }
+
+    /// Makes a new version of the object with the given changes (see `versioning::new_version`).
+    pub fn new_version(&self, changes: Value) -> Result<Box<dyn STIXObject>, versioning::VersioningError> {
+        versioning::new_version(self, changes)
+    }
+
+    /// Makes the last version of the object, marking it as revoked (see `versioning::revoke`).
+    pub fn revoke(&self) -> Result<Box<dyn STIXObject>, versioning::VersioningError> {
+        versioning::revoke(self)
+    }
+}
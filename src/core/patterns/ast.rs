@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+
+/// A complete STIX pattern, i. e. the value of the `pattern` property of an `Indicator` whose `pattern_type` is `stix`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    pub expression: ObservationExpression,
+}
+
+/// An expression over observations: either a single observation between brackets or a combination of them.
+///
+/// Chains of the same operator (e. g. `[a] AND [b] AND [c]`) are kept in a single node, while parentheses written in the original pattern are kept as `Group` nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObservationExpression {
+    Observation(ComparisonExpression),
+    And(Vec<ObservationExpression>),
+    Or(Vec<ObservationExpression>),
+    FollowedBy(Vec<ObservationExpression>),
+    Qualified(Box<ObservationExpression>, Qualifier),
+    Group(Box<ObservationExpression>),
+}
+
+/// The qualifiers that can be applied to an observation expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Qualifier {
+    Within(f64),
+    Repeats(u64),
+    StartStop(DateTime<Utc>, DateTime<Utc>),
+}
+
+/// An expression over the properties of the objects of a single observation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComparisonExpression {
+    Comparison(Comparison),
+    Exists(ObjectPath),
+    And(Vec<ComparisonExpression>),
+    Or(Vec<ComparisonExpression>),
+    Group(Box<ComparisonExpression>),
+}
+
+/// The test of an object path against a literal, e. g. `file:name NOT LIKE 'foo%'`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub path: ObjectPath,
+    pub negated: bool,
+    pub operator: ComparisonOperator,
+    pub value: Literal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    GreaterOrEqual,
+    LessOrEqual,
+    In,
+    Like,
+    Matches,
+    IsSubset,
+    IsSuperset,
+}
+
+/// The path to a property of an object, e. g. `file:extensions.'windows-pebinary-ext'.sections[*].entropy`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ObjectPath {
+    pub object_type: String,
+    pub components: Vec<PathComponent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathComponent {
+    Property(String),
+    Index(ListIndex),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ListIndex {
+    Position(i64),
+    Any,
+}
+
+/// The constants that can be compared against an object path.
+///
+/// Binary literals keep their base64 text and hex literals their (lowercased) hexadecimal digits.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Binary(String),
+    Hex(String),
+    Timestamp(DateTime<Utc>),
+    Set(Vec<Literal>),
+}
+
+impl ObservationExpression {
+    /// Returns every comparison expression found inside the observations of this expression.
+    pub fn observations(&self) -> Vec<&ComparisonExpression> {
+        match self {
+            ObservationExpression::Observation(comparison) => vec![comparison],
+            ObservationExpression::And(items)
+            | ObservationExpression::Or(items)
+            | ObservationExpression::FollowedBy(items) => items.iter().flat_map(|item| item.observations()).collect(),
+            ObservationExpression::Qualified(inner, _) | ObservationExpression::Group(inner) => inner.observations(),
+        }
+    }
+}
+
+impl ComparisonExpression {
+    /// Returns every object path referenced by this expression.
+    pub fn object_paths(&self) -> Vec<&ObjectPath> {
+        match self {
+            ComparisonExpression::Comparison(comparison) => vec![&comparison.path],
+            ComparisonExpression::Exists(path) => vec![path],
+            ComparisonExpression::And(items) | ComparisonExpression::Or(items) => {
+                items.iter().flat_map(|item| item.object_paths()).collect()
+            }
+            ComparisonExpression::Group(inner) => inner.object_paths(),
+        }
+    }
+}
+
+impl Pattern {
+    /// Returns every object path referenced by the pattern.
+    pub fn object_paths(&self) -> Vec<&ObjectPath> {
+        self.expression
+            .observations()
+            .into_iter()
+            .flat_map(|comparison| comparison.object_paths())
+            .collect()
+    }
+}
@@ -0,0 +1,409 @@
+use std::cmp::Ordering;
+use std::net::IpAddr;
+use chrono::SecondsFormat;
+use serde_json::Value;
+use crate::core::patterns::ast::{
+    Comparison,
+    ComparisonExpression,
+    ComparisonOperator,
+    ListIndex,
+    Literal,
+    ObjectPath,
+    ObservationExpression,
+    PathComponent,
+    Pattern,
+    Qualifier,
+};
+use crate::core::patterns::matcher::{compare, network_contains, order, parse_network};
+use crate::core::patterns::normalizer::normalize;
+
+/// Hash algorithm names of the `hashing-algorithm-ov` vocabulary, used to fix the spelling of the hash keys (e. g. `sha256` into `SHA-256`).
+const HASH_ALGORITHMS: [&str; 8] = ["MD5", "SHA-1", "SHA-256", "SHA-512", "SHA3-256", "SHA3-512", "SSDEEP", "TLSH"];
+
+/// The most terms the disjunctive normal form of a pattern is allowed to have. Rewriting a pattern in this form can make it
+/// exponentially larger (e. g. a conjunction of many disjunctions), so the patterns going beyond are not compared at all.
+pub const MAX_TERMS: usize = 256;
+
+/// Whether two patterns match exactly the same observations, i. e. each of them subsumes the other.
+/// The patterns whose disjunctive normal form would have more than `MAX_TERMS` terms are never found equivalent.
+pub fn equivalent(first: &Pattern, second: &Pattern) -> bool {
+    let (first, second) = match (canonical(first), canonical(second)) {
+        (Some(first), Some(second)) => (first, second),
+        _ => return false,
+    };
+    first == second || (subsumes_expression(&first.expression, &second.expression) && subsumes_expression(&second.expression, &first.expression))
+}
+
+/// Whether the `general` pattern matches every set of observations matched by the `specific` one,
+/// e. g. `[ipv4-addr:value ISSUBSET '10.0.0.0/8']` subsumes `[ipv4-addr:value = '10.1.2.3']`.
+///
+/// The check is sound but not complete: when it returns `true` the `specific` pattern is redundant, but some
+/// subsumptions that would require reasoning about the values (e. g. overlapping regular expressions) are not found,
+/// nor are those between patterns whose disjunctive normal form would have more than `MAX_TERMS` terms.
+pub fn subsumes(general: &Pattern, specific: &Pattern) -> bool {
+    match (canonical(general), canonical(specific)) {
+        (Some(general), Some(specific)) => subsumes_expression(&general.expression, &specific.expression),
+        _ => false,
+    }
+}
+
+/// Returns the indices of the patterns that are subsumed by another pattern of the list, and therefore redundant.
+/// Of a group of equivalent patterns, only the first one is kept. The patterns too large to be compared (see `MAX_TERMS`) are kept.
+pub fn redundant(patterns: &[Pattern]) -> Vec<usize> {
+    let canonical: Vec<Option<Pattern>> = patterns.iter().map(canonical).collect();
+    let subsumes = |general: usize, specific: usize| match (&canonical[general], &canonical[specific]) {
+        (Some(general), Some(specific)) => subsumes_expression(&general.expression, &specific.expression),
+        _ => false,
+    };
+    (0..canonical.len())
+        .filter(|i| (0..canonical.len()).any(|j| j != *i && subsumes(j, *i) && (j < *i || !subsumes(*i, j))))
+        .collect()
+}
+
+/// Returns the canonical form of a pattern, following the pattern equivalence section of the STIX best practices.
+///
+/// On top of the normalization (see `normalize`), the canonicalization:
+///
+/// - rewrites the comparison and observation expressions in disjunctive normal form (an `OR` of terms without `OR`),
+///   except under a `REPEATS` qualifier since the repetitions may match different operands,
+/// - removes the terms subsumed by another one (e. g. `[A] OR [A AND B]` into `[A]`),
+/// - turns `IN` with a single value into `=`, and
+/// - writes the values the same way: CIDR blocks without host bits nor `/32` (`/128`), hash names of the vocabulary
+///   with hash values in lowercase, and Windows registry keys in lowercase. The values compared with `LIKE` or `MATCHES`
+///   are left as they are, since they are case sensitive.
+///
+/// The patterns whose disjunctive normal form would have more than `MAX_TERMS` terms are only normalized.
+pub fn canonicalize(pattern: &Pattern) -> Pattern {
+    canonical(pattern).unwrap_or_else(|| normalize(pattern))
+}
+
+/// The canonical form of a pattern, unless its disjunctive normal form would have more than `MAX_TERMS` terms.
+fn canonical(pattern: &Pattern) -> Option<Pattern> {
+    let terms = absorb(observation_terms(&pattern.expression)?);
+    Some(normalize(&Pattern { expression: disjunction(terms) }))
+}
+
+fn disjunction(mut terms: Vec<ObservationExpression>) -> ObservationExpression {
+    if terms.len() == 1 {
+        terms.remove(0)
+    } else {
+        ObservationExpression::Or(terms)
+    }
+}
+
+/// Removes the duplicated terms and those subsumed by another term.
+fn absorb(terms: Vec<ObservationExpression>) -> Vec<ObservationExpression> {
+    let terms: Vec<ObservationExpression> = terms.into_iter().map(|term| normalize(&Pattern { expression: term }).expression).collect();
+    let mut kept: Vec<ObservationExpression> = Vec::new();
+    for (i, term) in terms.iter().enumerate() {
+        let absorbed = terms.iter().enumerate().any(|(j, other)| {
+            j != i && subsumes_term(other, term) && (j < i || !subsumes_term(term, other))
+        });
+        if !absorbed {
+            kept.push(term.clone());
+        }
+    }
+    kept
+}
+
+/// Splits an observation expression into terms, the expression matching whenever one of the terms does.
+/// Nothing is returned if there would be more than `MAX_TERMS` terms.
+fn observation_terms(expression: &ObservationExpression) -> Option<Vec<ObservationExpression>> {
+    let terms = match expression {
+        ObservationExpression::Observation(comparison) => comparison_terms(comparison)?
+            .into_iter()
+            .map(|atoms| ObservationExpression::Observation(conjunction(atoms)))
+            .collect(),
+        ObservationExpression::Group(inner) => observation_terms(inner)?,
+        ObservationExpression::Or(items) => items.iter().map(observation_terms).collect::<Option<Vec<_>>>()?.concat(),
+        ObservationExpression::And(items) => product(items.iter().map(observation_terms).collect::<Option<_>>()?)?
+            .into_iter()
+            .map(ObservationExpression::And)
+            .collect(),
+        ObservationExpression::FollowedBy(items) => product(items.iter().map(observation_terms).collect::<Option<_>>()?)?
+            .into_iter()
+            .map(ObservationExpression::FollowedBy)
+            .collect(),
+        ObservationExpression::Qualified(inner, Qualifier::Repeats(times)) => {
+            let inner = disjunction(absorb(observation_terms(inner)?));
+            vec![ObservationExpression::Qualified(Box::new(inner), Qualifier::Repeats(*times))]
+        }
+        ObservationExpression::Qualified(inner, qualifier) => observation_terms(inner)?
+            .into_iter()
+            .map(|term| ObservationExpression::Qualified(Box::new(term), qualifier.clone()))
+            .collect(),
+    };
+    within_limit(terms)
+}
+
+/// Splits a comparison expression into conjunctions of comparisons (or `EXISTS`), already canonicalized.
+/// Nothing is returned if there would be more than `MAX_TERMS` conjunctions.
+fn comparison_terms(expression: &ComparisonExpression) -> Option<Vec<Vec<ComparisonExpression>>> {
+    let terms = match expression {
+        ComparisonExpression::Comparison(comparison) => vec![vec![ComparisonExpression::Comparison(canonical_comparison(comparison))]],
+        ComparisonExpression::Exists(path) => vec![vec![ComparisonExpression::Exists(path.clone())]],
+        ComparisonExpression::Group(inner) => comparison_terms(inner)?,
+        ComparisonExpression::Or(items) => items.iter().map(comparison_terms).collect::<Option<Vec<_>>>()?.concat(),
+        ComparisonExpression::And(items) => product(items.iter().map(comparison_terms).collect::<Option<_>>()?)?
+            .into_iter()
+            .map(|parts| parts.into_iter().flatten().collect())
+            .collect(),
+    };
+    within_limit(terms)
+}
+
+fn within_limit<T>(terms: Vec<T>) -> Option<Vec<T>> {
+    if terms.len() > MAX_TERMS {
+        None
+    } else {
+        Some(terms)
+    }
+}
+
+fn conjunction(mut atoms: Vec<ComparisonExpression>) -> ComparisonExpression {
+    if atoms.len() == 1 {
+        atoms.remove(0)
+    } else {
+        ComparisonExpression::And(atoms)
+    }
+}
+
+/// Every combination made of one option of each list, keeping the order of the lists.
+/// Nothing is returned if there would be more than `MAX_TERMS` combinations, before any of them is made.
+pub(crate) fn product<T: Clone>(lists: Vec<Vec<T>>) -> Option<Vec<Vec<T>>> {
+    let mut count: usize = 1;
+    for options in lists.iter() {
+        count = count.saturating_mul(options.len());
+        if count > MAX_TERMS {
+            return None;
+        }
+    }
+    let mut combinations = vec![Vec::new()];
+    for options in lists {
+        combinations = combinations
+            .iter()
+            .flat_map(|combination| {
+                options.iter().map(move |option| {
+                    let mut combination = combination.clone();
+                    combination.push(option.clone());
+                    combination
+                })
+            })
+            .collect();
+    }
+    Some(combinations)
+}
+
+/// Writes the values of a comparison the canonical way (see `canonicalize`).
+pub(crate) fn canonical_comparison(comparison: &Comparison) -> Comparison {
+    let mut comparison = comparison.clone();
+    if comparison.operator == ComparisonOperator::In {
+        if let Literal::Set(items) = &comparison.value {
+            if items.len() == 1 {
+                comparison.value = items[0].clone();
+                comparison.operator = ComparisonOperator::Equal;
+            }
+        }
+    }
+    let case_insensitive_path = is_case_insensitive(&comparison.path);
+    let object_type = comparison.path.object_type.as_str();
+    let components = &mut comparison.path.components;
+    if let Some(position) = components.iter().position(|component| *component == PathComponent::Property("hashes".to_string())) {
+        if let Some(PathComponent::Property(name)) = components.get_mut(position + 1) {
+            let simplified = name.replace('-', "").to_uppercase();
+            if let Some(algorithm) = HASH_ALGORITHMS.iter().find(|algorithm| algorithm.replace('-', "") == simplified) {
+                *name = algorithm.to_string();
+            }
+        }
+    }
+    let is_address = matches!(object_type, "ipv4-addr" | "ipv6-addr") && *components == [PathComponent::Property("value".to_string())];
+    // Only the comparisons of whole values are case insensitive, the regular expressions and LIKE patterns are not.
+    let case_insensitive = matches!(comparison.operator, ComparisonOperator::Equal | ComparisonOperator::NotEqual | ComparisonOperator::In);
+    let canonical_value = |literal: &Literal| match literal {
+        Literal::String(text) if is_address => Literal::String(canonical_network(text).unwrap_or_else(|| text.clone())),
+        Literal::String(text) if case_insensitive && case_insensitive_path => Literal::String(text.to_lowercase()),
+        _ => literal.clone(),
+    };
+    comparison.value = match &comparison.value {
+        Literal::Set(items) => Literal::Set(items.iter().map(canonical_value).collect()),
+        literal => canonical_value(literal),
+    };
+    comparison
+}
+
+/// Whether the values of a property are compared regardless of their case, as are the hashes and the Windows registry keys.
+fn is_case_insensitive(path: &ObjectPath) -> bool {
+    let is_hash = path.components.iter().any(|component| *component == PathComponent::Property("hashes".to_string()));
+    let is_registry_key = path.object_type == "windows-registry-key" && path.components == [PathComponent::Property("key".to_string())];
+    is_hash || is_registry_key
+}
+
+/// Whether a path goes through every item of a list, and so may lead to several values.
+fn has_any_index(path: &ObjectPath) -> bool {
+    path.components.contains(&PathComponent::Index(ListIndex::Any))
+}
+
+/// Writes an IP address or a CIDR block without host bits, leaving out the prefix of single addresses.
+fn canonical_network(text: &str) -> Option<String> {
+    let (address, prefix) = parse_network(text)?;
+    let masked = match address {
+        IpAddr::V4(address) => {
+            let mask = if prefix == 0 { 0 } else { !0u32 << (32 - prefix) };
+            IpAddr::from((u32::from(address) & mask).to_be_bytes())
+        }
+        IpAddr::V6(address) => {
+            let mask = if prefix == 0 { 0 } else { !0u128 << (128 - prefix) };
+            IpAddr::from((u128::from(address) & mask).to_be_bytes())
+        }
+    };
+    if prefix == if masked.is_ipv4() { 32 } else { 128 } {
+        Some(masked.to_string())
+    } else {
+        Some(format!("{}/{}", masked, prefix))
+    }
+}
+
+/// Whether every term of `specific` is subsumed by a term of `general`.
+fn subsumes_expression(general: &ObservationExpression, specific: &ObservationExpression) -> bool {
+    terms(specific).iter().all(|specific| terms(general).iter().any(|general| subsumes_term(general, specific)))
+}
+
+fn terms(expression: &ObservationExpression) -> Vec<&ObservationExpression> {
+    match expression {
+        ObservationExpression::Or(items) => items.iter().collect(),
+        ObservationExpression::Group(inner) => terms(inner),
+        _ => vec![expression],
+    }
+}
+
+fn subsumes_term(general: &ObservationExpression, specific: &ObservationExpression) -> bool {
+    use ObservationExpression::*;
+    match (general, specific) {
+        (Group(general), _) => subsumes_term(general, specific),
+        (_, Group(specific)) => subsumes_term(general, specific),
+        (Or(_), _) | (_, Or(_)) => subsumes_expression(general, specific),
+        (Observation(general), Observation(specific)) => implies(&atoms(specific), &atoms(general)),
+        (Qualified(general_inner, general_qualifier), Qualified(specific_inner, specific_qualifier))
+            if qualifier_subsumes(general_qualifier, specific_qualifier) && subsumes_expression(general_inner, specific_inner) =>
+        {
+            true
+        }
+        // A qualifier only removes matches, so whatever covers the unqualified expression covers the qualified one.
+        (_, Qualified(specific_inner, _)) => subsumes_expression(general, specific_inner),
+        (Qualified(_, _), _) => false,
+        (And(general), And(specific) | FollowedBy(specific)) => assign(general, specific, false),
+        (FollowedBy(general), FollowedBy(specific)) => assign(general, specific, true),
+        (Observation(_), And(specific) | FollowedBy(specific)) => specific.iter().any(|specific| subsumes_term(general, specific)),
+        _ => false,
+    }
+}
+
+/// Whether each operand of `general` subsumes a different operand of `specific`, in the same order if `ordered` is set.
+fn assign(general: &[ObservationExpression], specific: &[ObservationExpression], ordered: bool) -> bool {
+    fn search(general: &[ObservationExpression], specific: &[ObservationExpression], used: &mut Vec<bool>, from: usize, ordered: bool) -> bool {
+        let (first, rest) = match general.split_first() {
+            Some(split) => split,
+            None => return true,
+        };
+        for i in from..specific.len() {
+            if !used[i] && subsumes_term(first, &specific[i]) {
+                used[i] = true;
+                if search(rest, specific, used, if ordered { i + 1 } else { 0 }, ordered) {
+                    return true;
+                }
+                used[i] = false;
+            }
+        }
+        false
+    }
+    search(general, specific, &mut vec![false; specific.len()], 0, ordered)
+}
+
+fn qualifier_subsumes(general: &Qualifier, specific: &Qualifier) -> bool {
+    match (general, specific) {
+        (Qualifier::Within(general), Qualifier::Within(specific)) => specific <= general,
+        (Qualifier::Repeats(general), Qualifier::Repeats(specific)) => specific >= general,
+        (Qualifier::StartStop(general_start, general_stop), Qualifier::StartStop(specific_start, specific_stop)) => {
+            general_start <= specific_start && specific_stop <= general_stop
+        }
+        _ => false,
+    }
+}
+
+/// The conjunctions of a canonical comparison expression, as returned by `comparison_terms`.
+fn atoms(expression: &ComparisonExpression) -> Vec<&ComparisonExpression> {
+    match expression {
+        ComparisonExpression::And(items) => items.iter().collect(),
+        _ => vec![expression],
+    }
+}
+
+/// Whether the conjunction `specific` implies the conjunction `general`.
+fn implies(specific: &[&ComparisonExpression], general: &[&ComparisonExpression]) -> bool {
+    general.iter().all(|general| specific.iter().any(|specific| implies_atom(specific, general)))
+}
+
+fn implies_atom(specific: &ComparisonExpression, general: &ComparisonExpression) -> bool {
+    if specific == general {
+        return true;
+    }
+    match (specific, general) {
+        // Any comparison that is not negated requires the property to exist.
+        (ComparisonExpression::Comparison(Comparison { path, negated: false, .. }) | ComparisonExpression::Exists(path), ComparisonExpression::Exists(general)) => {
+            path.object_type == general.object_type && path.components.starts_with(&general.components)
+        }
+        (ComparisonExpression::Comparison(specific), ComparisonExpression::Comparison(general)) => {
+            !specific.negated && specific.path == general.path && implies_comparison(specific, general)
+        }
+        _ => false,
+    }
+}
+
+fn implies_comparison(specific: &Comparison, general: &Comparison) -> bool {
+    use ComparisonOperator::*;
+    let satisfies = |literal: &Literal| match literal_value(literal) {
+        Some(value) => compare(general.operator, &general.value, &value).map(|result| result != general.negated),
+        None => None,
+    };
+    match (specific.operator, general.operator) {
+        // The values lowercased stand for every spelling of them, which a case sensitive comparison does not accept alike.
+        (Equal | In, Like | Matches) if is_case_insensitive(&specific.path) => false,
+        // A negated comparison on a list path holds when no item compares, which one item comparing otherwise does not tell.
+        _ if general.negated && has_any_index(&general.path) => false,
+        (Equal, _) => satisfies(&specific.value) == Some(true),
+        (In, _) => match &specific.value {
+            Literal::Set(items) => items.iter().all(|item| satisfies(item) == Some(true)),
+            _ => false,
+        },
+        (IsSubset, IsSubset) if !general.negated => match (&specific.value, &general.value) {
+            (Literal::String(specific), Literal::String(general)) => match (parse_network(specific), parse_network(general)) {
+                (Some(specific), Some(general)) => network_contains(&general, &specific),
+                _ => false,
+            },
+            _ => false,
+        },
+        (GreaterThan | GreaterOrEqual, GreaterThan | GreaterOrEqual) | (LessThan | LessOrEqual, LessThan | LessOrEqual) if !general.negated => {
+            // Position of the specific bound with regard to the general one. Equal bounds only do if the specific one excludes it or the general one includes it.
+            let bound = match literal_value(&specific.value).and_then(|value| order(&general.value, &value)) {
+                Some(bound) => bound,
+                None => return false,
+            };
+            let equal_allowed = matches!(specific.operator, GreaterThan | LessThan) || matches!(general.operator, GreaterOrEqual | LessOrEqual);
+            let tighter = if matches!(general.operator, GreaterThan | GreaterOrEqual) { Ordering::Greater } else { Ordering::Less };
+            bound == tighter || (bound == Ordering::Equal && equal_allowed)
+        }
+        _ => false,
+    }
+}
+
+/// The value an object would hold to be equal to the literal.
+fn literal_value(literal: &Literal) -> Option<Value> {
+    Some(match literal {
+        Literal::Integer(value) => Value::from(*value),
+        Literal::Float(value) => Value::from(*value),
+        Literal::Boolean(value) => Value::Bool(*value),
+        Literal::String(text) | Literal::Binary(text) | Literal::Hex(text) => Value::String(text.clone()),
+        Literal::Timestamp(timestamp) => Value::String(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        Literal::Set(_) => return None,
+    })
+}
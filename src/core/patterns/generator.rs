@@ -0,0 +1,242 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::patterns::ast::{
+    Comparison,
+    ComparisonExpression,
+    ComparisonOperator,
+    ListIndex,
+    Literal,
+    ObjectPath,
+    ObservationExpression,
+    PathComponent,
+    Pattern,
+};
+use crate::core::patterns::parser::is_base64;
+use crate::core::scos::schema::{self, Property, PropertyType};
+use crate::core::sdos::indicator::Indicator;
+
+/// How many references are followed from an object, e. g. `network-traffic:dst_ref.resolves_to_refs[*].value` follows two.
+const MAX_REFERENCE_DEPTH: usize = 2;
+
+/// Properties that tell nothing about what was observed.
+const IGNORED_PROPERTIES: [&str; 6] = ["type", "id", "spec_version", "object_marking_refs", "granular_markings", "defanged"];
+
+/// How the observations generated for several objects are joined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Join {
+    /// The pattern matches when any of the objects is observed.
+    Any,
+    /// The pattern matches when all of the objects are observed.
+    All,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenerateError {
+    NoObjects,
+    /// The object with this identifier is not an SCO.
+    NotObservable(String),
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerateError::NoObjects => write!(f, "no object with properties to compare was given"),
+            GenerateError::NotObservable(id) => write!(f, "{} is not a cyber-observable object", id),
+        }
+    }
+}
+
+impl Error for GenerateError {}
+
+/// Builds a pattern matching the given SCOs, with an observation for each of them comparing every property observed.
+///
+/// References to other objects of the list are followed (e. g. the `dst_ref` of a `network-traffic` is compared through
+/// `network-traffic:dst_ref.value`), and the objects referenced this way do not get an observation of their own.
+/// References to objects that are not in the list are left out, as their identifiers are meaningless to other producers.
+/// The literals are typed after the SCO definitions (timestamps, binaries and hexadecimal strings) and the strings escaped when printed.
+pub fn generate(objects: &[Box<dyn STIXObject>], join: Join) -> Result<Pattern, GenerateError> {
+    let values: Vec<Value> = objects.iter().map(|object| object.to_value()).collect();
+    let mut by_id: HashMap<&str, &Value> = HashMap::new();
+    for value in &values {
+        let object_type = value.get("type").and_then(Value::as_str).unwrap_or_default();
+        let id = value.get("id").and_then(Value::as_str).unwrap_or_default();
+        if !schema::OBJECT_TYPES.contains(&object_type) && !schema::is_custom_name(object_type) {
+            return Err(GenerateError::NotObservable(id.to_string()));
+        }
+        by_id.insert(id, value);
+    }
+    let referenced: HashSet<&str> = values.iter().flat_map(|value| references(value)).collect();
+    let mut roots: Vec<&Value> = values
+        .iter()
+        .filter(|value| !referenced.contains(value.get("id").and_then(Value::as_str).unwrap_or_default()))
+        .collect();
+    if roots.is_empty() {
+        // The objects reference each other in a cycle, so every one of them is described.
+        roots = values.iter().collect();
+    }
+    let mut observations = Vec::new();
+    for root in roots {
+        let object_type = root["type"].as_str().unwrap_or_default().to_string();
+        let mut generator = Generator { by_id: &by_id, visited: Vec::new(), comparisons: Vec::new() };
+        let path = ObjectPath { object_type: object_type.clone(), components: Vec::new() };
+        generator.object(root, &path, &object_type);
+        match generator.comparisons.len() {
+            0 => {}
+            1 => observations.push(ObservationExpression::Observation(generator.comparisons.remove(0))),
+            _ => observations.push(ObservationExpression::Observation(ComparisonExpression::And(generator.comparisons))),
+        }
+    }
+    let expression = match (observations.len(), join) {
+        (0, _) => return Err(GenerateError::NoObjects),
+        (1, _) => observations.remove(0),
+        (_, Join::Any) => ObservationExpression::Or(observations),
+        (_, Join::All) => ObservationExpression::And(observations),
+    };
+    Ok(Pattern { expression })
+}
+
+/// Builds the pattern of the given SCOs (see `generate`) and wraps it into a new indicator.
+pub fn indicator(objects: &[Box<dyn STIXObject>], join: Join) -> Result<Indicator, GenerateError> {
+    Ok(Indicator::from_pattern(&generate(objects, join)?))
+}
+
+/// The identifiers referenced by the `_ref` and `_refs` properties of an object, at any depth.
+fn references(value: &Value) -> Vec<&str> {
+    let mut found = Vec::new();
+    if let Value::Object(properties) = value {
+        for (name, property) in properties {
+            match property {
+                Value::String(id) if name.ends_with("_ref") => found.push(id.as_str()),
+                Value::Array(ids) if name.ends_with("_refs") => found.extend(ids.iter().filter_map(Value::as_str)),
+                Value::Object(_) => found.extend(references(property)),
+                Value::Array(items) => found.extend(items.iter().flat_map(references)),
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+/// What is known about the value found at some point of an object path.
+#[derive(Clone, Copy)]
+enum Hint {
+    Sco(&'static str),
+    /// The `extensions` dictionary of an SCO.
+    Extensions(&'static str),
+    Properties(&'static [Property]),
+    Type(PropertyType),
+    Unknown,
+}
+
+struct Generator<'a> {
+    by_id: &'a HashMap<&'a str, &'a Value>,
+    /// The objects being described, to avoid following a reference back to one of them.
+    visited: Vec<String>,
+    comparisons: Vec<ComparisonExpression>,
+}
+
+impl<'a> Generator<'a> {
+    /// Describes an SCO found at the given path, either the root object or one reached through a reference.
+    fn object(&mut self, value: &Value, path: &ObjectPath, object_type: &str) {
+        let properties = match value {
+            Value::Object(properties) => properties,
+            _ => return,
+        };
+        self.visited.push(value.get("id").and_then(Value::as_str).unwrap_or_default().to_string());
+        let hint = schema::OBJECT_TYPES.iter().find(|name| **name == object_type).map_or(Hint::Unknown, |name| Hint::Sco(name));
+        for (name, property) in properties {
+            if IGNORED_PROPERTIES.contains(&name.as_str()) {
+                continue;
+            }
+            self.value(property, &child(path, PathComponent::Property(name.clone())), child_hint(hint, name), name.ends_with("_ref") || name.ends_with("_refs"));
+        }
+        self.visited.pop();
+    }
+
+    fn value(&mut self, value: &Value, path: &ObjectPath, hint: Hint, is_reference: bool) {
+        match value {
+            Value::Object(properties) => {
+                for (name, property) in properties {
+                    let is_reference = name.ends_with("_ref") || name.ends_with("_refs");
+                    self.value(property, &child(path, PathComponent::Property(name.clone())), child_hint(hint, name), is_reference);
+                }
+            }
+            Value::Array(items) => {
+                let item_hint = match hint {
+                    Hint::Type(PropertyType::List(items)) => Hint::Type(*items),
+                    _ => Hint::Unknown,
+                };
+                for item in items {
+                    self.value(item, &child(path, PathComponent::Index(ListIndex::Any)), item_hint, is_reference);
+                }
+            }
+            Value::String(id) if is_reference || matches!(hint, Hint::Type(PropertyType::Reference(_))) => self.reference(id, path),
+            Value::Null => {}
+            _ => {
+                if let Some(literal) = literal(value, hint) {
+                    self.comparisons.push(ComparisonExpression::Comparison(Comparison {
+                        path: path.clone(),
+                        negated: false,
+                        operator: ComparisonOperator::Equal,
+                        value: literal,
+                    }));
+                }
+            }
+        }
+    }
+
+    fn reference(&mut self, id: &str, path: &ObjectPath) {
+        let depth = path.components.iter().filter(|component| matches!(component, PathComponent::Property(name) if name.ends_with("_ref") || name.ends_with("_refs"))).count();
+        if depth > MAX_REFERENCE_DEPTH || self.visited.iter().any(|visited| visited == id) {
+            return;
+        }
+        if let Some(target) = self.by_id.get(id) {
+            let target_type = target.get("type").and_then(Value::as_str).unwrap_or_default().to_string();
+            self.object(target, path, &target_type);
+        }
+    }
+}
+
+fn child(path: &ObjectPath, component: PathComponent) -> ObjectPath {
+    let mut child = path.clone();
+    child.components.push(component);
+    child
+}
+
+fn child_hint(hint: Hint, name: &str) -> Hint {
+    let find = |properties: &'static [Property]| properties.iter().find(|(property, _)| *property == name).map(|(_, property_type)| *property_type);
+    let found = match hint {
+        Hint::Sco(object_type) if name == "extensions" => return Hint::Extensions(object_type),
+        Hint::Sco(object_type) => schema::property_type(object_type, name),
+        Hint::Extensions(object_type) => return schema::extension_properties(object_type, name).map_or(Hint::Unknown, Hint::Properties),
+        Hint::Properties(properties) | Hint::Type(PropertyType::Object(properties)) => find(properties),
+        Hint::Type(PropertyType::Dictionary(values)) => Some(*values),
+        _ => None,
+    };
+    found.map_or(Hint::Unknown, Hint::Type)
+}
+
+/// The literal to compare a value with, typed after the definition of the property when it is known.
+fn literal(value: &Value, hint: Hint) -> Option<Literal> {
+    Some(match (value, hint) {
+        (Value::Bool(value), _) => Literal::Boolean(*value),
+        (Value::Number(number), _) => match number.as_i64() {
+            Some(integer) => Literal::Integer(integer),
+            None => Literal::Float(number.as_f64()?),
+        },
+        (Value::String(text), Hint::Type(PropertyType::Timestamp)) => match DateTime::parse_from_rfc3339(text) {
+            Ok(timestamp) => Literal::Timestamp(timestamp.with_timezone(&Utc)),
+            Err(_) => Literal::String(text.clone()),
+        },
+        (Value::String(text), Hint::Type(PropertyType::Hex)) if text.len().is_multiple_of(2) && text.chars().all(|c| c.is_ascii_hexdigit()) => {
+            Literal::Hex(text.to_lowercase())
+        }
+        (Value::String(text), Hint::Type(PropertyType::Binary)) if is_base64(text) => Literal::Binary(text.clone()),
+        (Value::String(text), _) => Literal::String(text.clone()),
+        _ => return None,
+    })
+}
@@ -0,0 +1,259 @@
+use std::fmt;
+use crate::core::patterns::parser::{PatternError, PatternErrorKind};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    LeftBracket,
+    RightBracket,
+    LeftParenthesis,
+    RightParenthesis,
+    Colon,
+    Dot,
+    Comma,
+    Asterisk,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+    Keyword(Keyword),
+    Identifier(String),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Binary(String),
+    Hex(String),
+    Timestamp(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keyword {
+    And,
+    Or,
+    Not,
+    FollowedBy,
+    Like,
+    Matches,
+    IsSubset,
+    IsSuperset,
+    Exists,
+    In,
+    Start,
+    Stop,
+    Seconds,
+    Within,
+    Repeats,
+    Times,
+    True,
+    False,
+}
+
+impl Keyword {
+    fn from_identifier(identifier: &str) -> Option<Keyword> {
+        let keyword = match identifier {
+            "AND" => Keyword::And,
+            "OR" => Keyword::Or,
+            "NOT" => Keyword::Not,
+            "FOLLOWEDBY" => Keyword::FollowedBy,
+            "LIKE" => Keyword::Like,
+            "MATCHES" => Keyword::Matches,
+            "ISSUBSET" => Keyword::IsSubset,
+            "ISSUPERSET" => Keyword::IsSuperset,
+            "EXISTS" => Keyword::Exists,
+            "IN" => Keyword::In,
+            "START" => Keyword::Start,
+            "STOP" => Keyword::Stop,
+            "SECONDS" => Keyword::Seconds,
+            "WITHIN" => Keyword::Within,
+            "REPEATS" => Keyword::Repeats,
+            "TIMES" => Keyword::Times,
+            "true" | "TRUE" => Keyword::True,
+            "false" | "FALSE" => Keyword::False,
+            _ => return None,
+        };
+        Some(keyword)
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Keyword::And => "AND",
+            Keyword::Or => "OR",
+            Keyword::Not => "NOT",
+            Keyword::FollowedBy => "FOLLOWEDBY",
+            Keyword::Like => "LIKE",
+            Keyword::Matches => "MATCHES",
+            Keyword::IsSubset => "ISSUBSET",
+            Keyword::IsSuperset => "ISSUPERSET",
+            Keyword::Exists => "EXISTS",
+            Keyword::In => "IN",
+            Keyword::Start => "START",
+            Keyword::Stop => "STOP",
+            Keyword::Seconds => "SECONDS",
+            Keyword::Within => "WITHIN",
+            Keyword::Repeats => "REPEATS",
+            Keyword::Times => "TIMES",
+            Keyword::True => "true",
+            Keyword::False => "false",
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::LeftBracket => write!(f, "'['"),
+            Token::RightBracket => write!(f, "']'"),
+            Token::LeftParenthesis => write!(f, "'('"),
+            Token::RightParenthesis => write!(f, "')'"),
+            Token::Colon => write!(f, "':'"),
+            Token::Dot => write!(f, "'.'"),
+            Token::Comma => write!(f, "','"),
+            Token::Asterisk => write!(f, "'*'"),
+            Token::Equal => write!(f, "'='"),
+            Token::NotEqual => write!(f, "'!='"),
+            Token::LessThan => write!(f, "'<'"),
+            Token::LessOrEqual => write!(f, "'<='"),
+            Token::GreaterThan => write!(f, "'>'"),
+            Token::GreaterOrEqual => write!(f, "'>='"),
+            Token::Keyword(keyword) => write!(f, "keyword {}", keyword.as_str()),
+            Token::Identifier(identifier) => write!(f, "identifier '{}'", identifier),
+            Token::Integer(value) => write!(f, "integer {}", value),
+            Token::Float(value) => write!(f, "float {}", value),
+            Token::String(_) => write!(f, "string literal"),
+            Token::Binary(_) => write!(f, "binary literal"),
+            Token::Hex(_) => write!(f, "hex literal"),
+            Token::Timestamp(_) => write!(f, "timestamp literal"),
+        }
+    }
+}
+
+/// A token along with the position (in characters) where it starts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spanned {
+    pub token: Token,
+    pub position: usize,
+}
+
+/// Splits a pattern into tokens.
+pub fn tokenize(input: &str) -> Result<Vec<Spanned>, PatternError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token = match c {
+            '[' => { i += 1; Token::LeftBracket }
+            ']' => { i += 1; Token::RightBracket }
+            '(' => { i += 1; Token::LeftParenthesis }
+            ')' => { i += 1; Token::RightParenthesis }
+            ':' => { i += 1; Token::Colon }
+            '.' if !chars.get(i + 1).is_some_and(|next| next.is_ascii_digit()) => { i += 1; Token::Dot }
+            ',' => { i += 1; Token::Comma }
+            '*' => { i += 1; Token::Asterisk }
+            '=' => {
+                i += if chars.get(i + 1) == Some(&'=') { 2 } else { 1 };
+                Token::Equal
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => { i += 2; Token::NotEqual }
+            '<' => match chars.get(i + 1) {
+                Some('=') => { i += 2; Token::LessOrEqual }
+                Some('>') => { i += 2; Token::NotEqual }
+                _ => { i += 1; Token::LessThan }
+            },
+            '>' => {
+                if chars.get(i + 1) == Some(&'=') {
+                    i += 2;
+                    Token::GreaterOrEqual
+                } else {
+                    i += 1;
+                    Token::GreaterThan
+                }
+            }
+            '\'' => {
+                let (text, end) = read_quoted(&chars, i)?;
+                i = end;
+                Token::String(text)
+            }
+            'b' | 'h' | 't' if chars.get(i + 1) == Some(&'\'') => {
+                let (text, end) = read_quoted(&chars, i + 1)?;
+                i = end;
+                match c {
+                    'b' => Token::Binary(text),
+                    'h' => Token::Hex(text),
+                    _ => Token::Timestamp(text),
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
+                    i += 1;
+                }
+                let identifier: String = chars[start..i].iter().collect();
+                match Keyword::from_identifier(&identifier) {
+                    Some(keyword) => Token::Keyword(keyword),
+                    None => Token::Identifier(identifier),
+                }
+            }
+            c if c.is_ascii_digit() || c == '.' || ((c == '-' || c == '+') && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit() || *next == '.')) => {
+                let (token, end) = read_number(&chars, i)?;
+                i = end;
+                token
+            }
+            c => return Err(PatternError::new(start, PatternErrorKind::UnexpectedCharacter(c))),
+        };
+        tokens.push(Spanned { token, position: start });
+    }
+    Ok(tokens)
+}
+
+/// Reads a quoted literal starting at the quote found in `start`, unescaping `\'` and `\\`.
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), PatternError> {
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\'' => return Ok((text, i + 1)),
+            '\\' => match chars.get(i + 1) {
+                Some(escaped @ ('\'' | '\\')) => {
+                    text.push(*escaped);
+                    i += 2;
+                }
+                _ => return Err(PatternError::new(i, PatternErrorKind::InvalidEscapeSequence)),
+            },
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(PatternError::new(start, PatternErrorKind::UnterminatedString))
+}
+
+fn read_number(chars: &[char], start: usize) -> Result<(Token, usize), PatternError> {
+    let mut i = start;
+    if chars[i] == '-' || chars[i] == '+' {
+        i += 1;
+    }
+    let mut is_float = false;
+    while i < chars.len() && (chars[i].is_ascii_digit() || (chars[i] == '.' && !is_float)) {
+        if chars[i] == '.' {
+            is_float = true;
+        }
+        i += 1;
+    }
+    let text: String = chars[start..i].iter().collect();
+    let token = if is_float {
+        text.parse::<f64>().ok().map(Token::Float)
+    } else {
+        text.parse::<i64>().ok().map(Token::Integer)
+    };
+    match token {
+        Some(token) if !text.ends_with('.') => Ok((token, i)),
+        _ => Err(PatternError::new(start, PatternErrorKind::InvalidLiteral(text))),
+    }
+}
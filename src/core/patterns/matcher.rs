@@ -0,0 +1,426 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::net::IpAddr;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::patterns::ast::{
+    Comparison,
+    ComparisonExpression,
+    ComparisonOperator,
+    ListIndex,
+    Literal,
+    ObservationExpression,
+    PathComponent,
+    Pattern,
+    Qualifier,
+};
+use crate::core::sdos::observed_data::ObservedData;
+
+/// Upper bound of the partial matches kept for each node of the pattern, so that a pattern over a large collection cannot exhaust the memory.
+const MAX_PARTIAL_MATCHES: usize = 10_000;
+
+/// A way in which a pattern matches a set of observations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternMatch {
+    pub observations: Vec<ObservationMatch>,
+}
+
+/// An `ObservedData` taking part in a match, along with the objects that satisfied the comparisons.
+/// Objects are identified by their `id` or, for the deprecated embedded `objects`, by their key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservationMatch {
+    pub observed_data_ref: String,
+    pub object_refs: Vec<String>,
+}
+
+/// Whether the pattern matches the given observations.
+pub fn is_match(pattern: &Pattern, observed_data: &[ObservedData], objects: &[Box<dyn STIXObject>]) -> bool {
+    !find_matches(pattern, observed_data, objects).is_empty()
+}
+
+/// Evaluates a pattern against a set of `ObservedData` and the SCOs they reference, returning every way in which it matches.
+///
+/// An `ObservedData` whose `number_observed` is greater than one counts as that many observations, so it may satisfy a `REPEATS` qualifier on its own.
+/// The `first_observed` and `last_observed` timestamps are used to check the order of `FOLLOWEDBY` operands (the second one starting once the first one ended)
+/// and the `WITHIN` and `START`/`STOP` qualifiers.
+/// Comparisons of the same object type joined by `AND` inside an observation must be satisfied by the same object.
+pub fn find_matches(pattern: &Pattern, observed_data: &[ObservedData], objects: &[Box<dyn STIXObject>]) -> Vec<PatternMatch> {
+    let global: HashMap<String, Value> = objects
+        .iter()
+        .map(|object| object.to_value())
+        .filter_map(|value| Some((value.get("id")?.as_str()?.to_string(), value)))
+        .collect();
+    let copies = needed_observations(&pattern.expression);
+    let mut observations = Vec::new();
+    let mut instances = Vec::new();
+    for data in observed_data {
+        let mut candidates: BTreeMap<String, Value> = BTreeMap::new();
+        for id in data.object_refs.iter().flatten() {
+            if let Some(value) = global.get(id) {
+                candidates.insert(id.clone(), value.clone());
+            }
+        }
+        for (key, value) in data.objects.iter().flatten() {
+            candidates.insert(key.clone(), value.clone());
+        }
+        for _ in 0..(data.number_observed as usize).clamp(1, copies) {
+            instances.push(observations.len());
+        }
+        observations.push(Observation {
+            id: data.id.clone(),
+            first_observed: data.first_observed,
+            last_observed: data.last_observed,
+            candidates,
+        });
+    }
+    let evaluator = Evaluator { observations, instances, global };
+    let mut matches: Vec<PatternMatch> = Vec::new();
+    for partial in evaluator.evaluate(&pattern.expression) {
+        let mut by_observation: BTreeMap<usize, BTreeSet<String>> = BTreeMap::new();
+        for (instance, keys) in partial.objects {
+            by_observation.entry(evaluator.instances[instance]).or_default().extend(keys);
+        }
+        let found = PatternMatch {
+            observations: by_observation
+                .into_iter()
+                .map(|(observation, keys)| ObservationMatch {
+                    observed_data_ref: evaluator.observations[observation].id.clone(),
+                    object_refs: keys.into_iter().collect(),
+                })
+                .collect(),
+        };
+        if !matches.contains(&found) {
+            matches.push(found);
+        }
+    }
+    matches
+}
+
+struct Observation {
+    id: String,
+    first_observed: DateTime<Utc>,
+    last_observed: DateTime<Utc>,
+    candidates: BTreeMap<String, Value>,
+}
+
+/// A match of part of the pattern: the observation instances used, their time span and the objects bound in each of them.
+#[derive(Clone)]
+struct Partial {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    objects: BTreeMap<usize, BTreeSet<String>>,
+}
+
+impl Partial {
+    fn is_disjoint(&self, other: &Partial) -> bool {
+        self.objects.keys().all(|instance| !other.objects.contains_key(instance))
+    }
+
+    fn merge(&self, other: &Partial) -> Partial {
+        let mut objects = self.objects.clone();
+        objects.extend(other.objects.clone());
+        Partial { start: self.start.min(other.start), end: self.end.max(other.end), objects }
+    }
+}
+
+/// Objects bound to each object type by a comparison expression.
+type Binding = BTreeMap<String, String>;
+
+struct Evaluator {
+    observations: Vec<Observation>,
+    instances: Vec<usize>,
+    global: HashMap<String, Value>,
+}
+
+impl Evaluator {
+    fn evaluate(&self, expression: &ObservationExpression) -> Vec<Partial> {
+        let mut partials = match expression {
+            ObservationExpression::Observation(comparison) => {
+                let mut partials = Vec::new();
+                for (instance, observation) in self.instances.iter().map(|index| &self.observations[*index]).enumerate() {
+                    let bindings = self.bindings(comparison, observation);
+                    if !bindings.is_empty() {
+                        let keys = bindings.into_iter().flat_map(|binding| binding.into_values()).collect();
+                        partials.push(Partial {
+                            start: observation.first_observed,
+                            end: observation.last_observed,
+                            objects: vec![(instance, keys)].into_iter().collect(),
+                        });
+                    }
+                }
+                partials
+            }
+            ObservationExpression::And(items) => self.join(items, |_, _| true),
+            ObservationExpression::FollowedBy(items) => self.join(items, |previous, next| next.start >= previous.end),
+            ObservationExpression::Or(items) => items.iter().flat_map(|item| self.evaluate(item)).collect(),
+            ObservationExpression::Group(inner) => self.evaluate(inner),
+            ObservationExpression::Qualified(inner, qualifier) => {
+                let partials = self.evaluate(inner);
+                match qualifier {
+                    Qualifier::Within(seconds) => partials
+                        .into_iter()
+                        .filter(|partial| (partial.end - partial.start).num_milliseconds() as f64 <= seconds * 1000.0)
+                        .collect(),
+                    Qualifier::StartStop(start, stop) => partials
+                        .into_iter()
+                        .filter(|partial| partial.start >= *start && partial.end < *stop)
+                        .collect(),
+                    Qualifier::Repeats(times) => repeat(&partials, *times as usize),
+                }
+            }
+        };
+        dedup(&mut partials);
+        partials
+    }
+
+    /// Combines the matches of every operand using different observations for each of them.
+    fn join(&self, items: &[ObservationExpression], accept: fn(&Partial, &Partial) -> bool) -> Vec<Partial> {
+        let mut partials = self.evaluate(&items[0]);
+        for item in &items[1..] {
+            let next_partials = self.evaluate(item);
+            let mut joined = Vec::new();
+            for previous in &partials {
+                for next in &next_partials {
+                    if previous.is_disjoint(next) && accept(previous, next) {
+                        joined.push(previous.merge(next));
+                    }
+                }
+            }
+            dedup(&mut joined);
+            partials = joined;
+        }
+        partials
+    }
+
+    fn bindings(&self, expression: &ComparisonExpression, observation: &Observation) -> Vec<Binding> {
+        match expression {
+            ComparisonExpression::Comparison(comparison) => self.bind(&comparison.path.object_type, observation, |object| {
+                self.test(comparison, object, observation)
+            }),
+            ComparisonExpression::Exists(path) => self.bind(&path.object_type, observation, |object| {
+                !self.resolve(object, &path.components, observation).is_empty()
+            }),
+            ComparisonExpression::Group(inner) => self.bindings(inner, observation),
+            ComparisonExpression::Or(items) => items.iter().flat_map(|item| self.bindings(item, observation)).collect(),
+            ComparisonExpression::And(items) => {
+                let mut bindings = vec![Binding::new()];
+                for item in items {
+                    let item_bindings = self.bindings(item, observation);
+                    let mut joined = Vec::new();
+                    for binding in &bindings {
+                        for item_binding in &item_bindings {
+                            let compatible = item_binding
+                                .iter()
+                                .all(|(object_type, key)| binding.get(object_type).is_none_or(|bound| bound == key));
+                            if compatible {
+                                let mut merged = binding.clone();
+                                merged.extend(item_binding.clone());
+                                if !joined.contains(&merged) {
+                                    joined.push(merged);
+                                }
+                            }
+                        }
+                    }
+                    bindings = joined;
+                }
+                bindings
+            }
+        }
+    }
+
+    fn bind(&self, object_type: &str, observation: &Observation, accept: impl Fn(&Value) -> bool) -> Vec<Binding> {
+        observation
+            .candidates
+            .iter()
+            .filter(|(_, object)| object.get("type").and_then(Value::as_str) == Some(object_type) && accept(object))
+            .map(|(key, _)| vec![(object_type.to_string(), key.clone())].into_iter().collect())
+            .collect()
+    }
+
+    /// Returns the values found following the path from an object, dereferencing `*_ref(s)` properties when needed.
+    fn resolve<'a>(&'a self, object: &'a Value, components: &[PathComponent], observation: &'a Observation) -> Vec<&'a Value> {
+        let mut current = vec![object];
+        for component in components {
+            let mut next = Vec::new();
+            for value in current {
+                match component {
+                    PathComponent::Property(name) => {
+                        let target = match value {
+                            Value::String(reference) => observation.candidates.get(reference).or_else(|| self.global.get(reference)),
+                            _ => Some(value),
+                        };
+                        if let Some(found) = target.and_then(|target| target.get(name)) {
+                            next.push(found);
+                        }
+                    }
+                    PathComponent::Index(ListIndex::Position(position)) => {
+                        if let (Value::Array(items), true) = (value, *position >= 0) {
+                            next.extend(items.get(*position as usize));
+                        }
+                    }
+                    PathComponent::Index(ListIndex::Any) => {
+                        if let Value::Array(items) = value {
+                            next.extend(items.iter());
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+        current
+    }
+
+    fn test(&self, comparison: &Comparison, object: &Value, observation: &Observation) -> bool {
+        let results: Vec<bool> = self
+            .resolve(object, &comparison.path.components, observation)
+            .into_iter()
+            .filter_map(|value| compare(comparison.operator, &comparison.value, value))
+            .collect();
+        if results.is_empty() {
+            return false;
+        }
+        results.into_iter().any(|result| result) != comparison.negated
+    }
+}
+
+/// Combines `times` different matches of the same expression.
+fn repeat(partials: &[Partial], times: usize) -> Vec<Partial> {
+    fn extend(partials: &[Partial], from: usize, times: usize, current: &Partial, found: &mut Vec<Partial>) {
+        if times == 0 {
+            found.push(current.clone());
+            return;
+        }
+        for index in from..partials.len() {
+            if found.len() >= MAX_PARTIAL_MATCHES {
+                return;
+            }
+            if current.is_disjoint(&partials[index]) {
+                extend(partials, index + 1, times - 1, &current.merge(&partials[index]), found);
+            }
+        }
+    }
+    let mut found = Vec::new();
+    for (index, first) in partials.iter().enumerate() {
+        extend(partials, index + 1, times.saturating_sub(1), first, &mut found);
+    }
+    found
+}
+
+fn dedup(partials: &mut Vec<Partial>) {
+    let mut seen = BTreeSet::new();
+    partials.retain(|partial| seen.insert(partial.objects.keys().cloned().collect::<Vec<usize>>()));
+    partials.truncate(MAX_PARTIAL_MATCHES);
+}
+
+/// The number of copies of a single observation that the expression could use at most.
+fn needed_observations(expression: &ObservationExpression) -> usize {
+    let needed = match expression {
+        ObservationExpression::Observation(_) => 1,
+        ObservationExpression::And(items) | ObservationExpression::FollowedBy(items) => items.iter().map(needed_observations).sum(),
+        ObservationExpression::Or(items) => items.iter().map(needed_observations).max().unwrap_or(1),
+        ObservationExpression::Group(inner) => needed_observations(inner),
+        ObservationExpression::Qualified(inner, Qualifier::Repeats(times)) => needed_observations(inner).saturating_mul(*times as usize),
+        ObservationExpression::Qualified(inner, _) => needed_observations(inner),
+    };
+    needed.min(MAX_PARTIAL_MATCHES)
+}
+
+/// Compares a value found in an object with a literal. Returns `None` when they cannot be compared.
+pub(crate) fn compare(operator: ComparisonOperator, literal: &Literal, value: &Value) -> Option<bool> {
+    match operator {
+        ComparisonOperator::Equal => Some(order(literal, value)? == Ordering::Equal),
+        ComparisonOperator::NotEqual => Some(order(literal, value)? != Ordering::Equal),
+        ComparisonOperator::GreaterThan => Some(order(literal, value)? == Ordering::Greater),
+        ComparisonOperator::LessThan => Some(order(literal, value)? == Ordering::Less),
+        ComparisonOperator::GreaterOrEqual => Some(order(literal, value)? != Ordering::Less),
+        ComparisonOperator::LessOrEqual => Some(order(literal, value)? != Ordering::Greater),
+        ComparisonOperator::In => match literal {
+            Literal::Set(items) => Some(items.iter().any(|item| order(item, value) == Some(Ordering::Equal))),
+            _ => None,
+        },
+        ComparisonOperator::Like => match (literal, value) {
+            (Literal::String(pattern), Value::String(text)) => Some(like(pattern, text)),
+            _ => None,
+        },
+        ComparisonOperator::Matches => match (literal, value) {
+            (Literal::String(pattern), Value::String(text)) => Regex::new(pattern).ok().map(|regex| regex.is_match(text)),
+            _ => None,
+        },
+        ComparisonOperator::IsSubset => match (literal, value) {
+            (Literal::String(network), Value::String(text)) => Some(network_contains(&parse_network(network)?, &parse_network(text)?)),
+            _ => None,
+        },
+        ComparisonOperator::IsSuperset => match (literal, value) {
+            (Literal::String(network), Value::String(text)) => Some(network_contains(&parse_network(text)?, &parse_network(network)?)),
+            _ => None,
+        },
+    }
+}
+
+/// Orders a value found in an object with regard to a literal.
+pub(crate) fn order(literal: &Literal, value: &Value) -> Option<Ordering> {
+    match (literal, value) {
+        (Literal::Integer(expected), Value::Number(number)) => match number.as_i64() {
+            Some(found) => Some(found.cmp(expected)),
+            None => number.as_f64()?.partial_cmp(&(*expected as f64)),
+        },
+        (Literal::Float(expected), Value::Number(number)) => number.as_f64()?.partial_cmp(expected),
+        (Literal::Boolean(expected), Value::Bool(found)) => Some(found.cmp(expected)),
+        (Literal::String(expected), Value::String(found)) => Some(found.as_str().cmp(expected.as_str())),
+        (Literal::Binary(expected), Value::String(found)) => Some(found.as_str().cmp(expected.as_str())),
+        (Literal::Hex(expected), Value::String(found)) => Some(found.to_lowercase().cmp(expected)),
+        (Literal::Timestamp(expected), Value::String(found)) => {
+            Some(DateTime::parse_from_rfc3339(found).ok()?.with_timezone(&Utc).cmp(expected))
+        }
+        _ => None,
+    }
+}
+
+/// SQL-like matching where `%` stands for any sequence of characters and `_` for a single one.
+pub fn like(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    // matched[j] tells whether the pattern read so far matches the first j characters of the text.
+    let mut matched = vec![false; text.len() + 1];
+    matched[0] = true;
+    for p in pattern {
+        let mut next = vec![false; text.len() + 1];
+        for j in 0..=text.len() {
+            next[j] = match p {
+                '%' => matched[j] || (j > 0 && next[j - 1]),
+                '_' => j > 0 && matched[j - 1],
+                c => j > 0 && matched[j - 1] && text[j - 1] == c,
+            };
+        }
+        matched = next;
+    }
+    matched[text.len()]
+}
+
+/// Parses an IP address or a CIDR block into the network address and its prefix length.
+pub(crate) fn parse_network(text: &str) -> Option<(IpAddr, u32)> {
+    let mut parts = text.splitn(2, '/');
+    let address: IpAddr = parts.next()?.trim().parse().ok()?;
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = match parts.next() {
+        Some(prefix) => prefix.trim().parse().ok().filter(|prefix| *prefix <= max_prefix)?,
+        None => max_prefix,
+    };
+    Some((address, prefix))
+}
+
+/// Whether the `outer` network contains the `inner` one.
+pub(crate) fn network_contains(outer: &(IpAddr, u32), inner: &(IpAddr, u32)) -> bool {
+    let (outer_bits, inner_bits, width) = match (outer.0, inner.0) {
+        (IpAddr::V4(outer), IpAddr::V4(inner)) => (u32::from(outer) as u128, u32::from(inner) as u128, 32),
+        (IpAddr::V6(outer), IpAddr::V6(inner)) => (u128::from(outer), u128::from(inner), 128),
+        _ => return false,
+    };
+    if outer.1 > inner.1 {
+        return false;
+    }
+    let mask = if outer.1 == 0 { 0 } else { (!0u128 << (width - outer.1)) & (!0u128 >> (128 - width)) };
+    outer_bits & mask == inner_bits & mask
+}
@@ -0,0 +1,29 @@
+/// This module implements the STIX Patterning language, as defined in [Section 9 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+/// Patterns are parsed into a typed abstract syntax tree (see `ast`) which is the starting point for any further processing of the indicators.
+/// The tree can be printed back as pattern text through its `Display` implementation, normalized to compare patterns written in different ways and evaluated against observed data (see `matcher`).
+/// The `equivalence` module decides whether two patterns match the same observations or whether one of them makes the other redundant.
+/// Patterns can also be generated from the SCOs observed (see `generator`), to share them as indicators.
+/// The patterns of the indicators written in other languages (PCRE, Sigma, Snort, Suricata and YARA) are checked by the `pattern_type` module.
+/// The `semantics` module checks the object paths of a pattern against the SCO definitions of the standard.
+/// The `translate` module writes patterns as Sigma, Suricata, Snort and YARA rules for the detection tools.
+///
+/// ```
+/// use stix4rust::core::patterns;
+///
+/// let pattern = patterns::parse("[file:hashes.'SHA-256' = 'aec070645fe53ee3b3763059376134f058cc337247c978add178b6ccdfb0019f']").unwrap();
+/// assert_eq!(pattern.object_paths()[0].object_type, "file");
+/// ```
+pub mod ast;
+pub mod equivalence;
+pub mod generator;
+mod lexer;
+pub mod matcher;
+pub mod normalizer;
+pub mod parser;
+pub mod pattern_type;
+pub mod printer;
+pub mod semantics;
+pub mod translate;
+
+pub use normalizer::normalize;
+pub use parser::{parse, PatternError, PatternErrorKind};
@@ -0,0 +1,113 @@
+use crate::core::patterns::ast::{
+    ComparisonExpression,
+    Literal,
+    ObservationExpression,
+    Pattern,
+};
+
+/// Returns the normalized form of a pattern, so that patterns expressing the same indicator in different ways print the same text.
+///
+/// The normalization:
+///
+/// - removes every parenthesis, as the printer adds back the ones required by the operator precedence,
+/// - flattens nested chains of the same operator (e. g. `(A AND B) AND C` into `A AND B AND C`),
+/// - sorts the operands of the commutative operators (`AND` and `OR`) by their canonical text,
+/// - removes duplicated operands from comparison expressions, and
+/// - sorts and deduplicates the items of the set literals used with `IN`.
+///
+/// Duplicated observations are kept, since `[A] AND [A]` requires two different observations matching `A`.
+pub fn normalize(pattern: &Pattern) -> Pattern {
+    Pattern { expression: normalize_observation(&pattern.expression) }
+}
+
+impl Pattern {
+    /// Returns the canonical text of the pattern, i. e. the text of its normalized form. Useful as a deduplication key.
+    pub fn canonical(&self) -> String {
+        normalize(self).to_string()
+    }
+}
+
+fn normalize_observation(expression: &ObservationExpression) -> ObservationExpression {
+    match expression {
+        ObservationExpression::Observation(comparison) => ObservationExpression::Observation(normalize_comparison(comparison)),
+        ObservationExpression::Group(inner) => normalize_observation(inner),
+        ObservationExpression::Qualified(inner, qualifier) => {
+            ObservationExpression::Qualified(Box::new(normalize_observation(inner)), qualifier.clone())
+        }
+        ObservationExpression::And(items) => {
+            let mut items = flatten_observations(items, &|expression| match expression {
+                ObservationExpression::And(items) => Some(items),
+                _ => None,
+            });
+            sort_by_text(&mut items);
+            ObservationExpression::And(items)
+        }
+        ObservationExpression::Or(items) => {
+            let mut items = flatten_observations(items, &|expression| match expression {
+                ObservationExpression::Or(items) => Some(items),
+                _ => None,
+            });
+            sort_by_text(&mut items);
+            ObservationExpression::Or(items)
+        }
+        ObservationExpression::FollowedBy(items) => {
+            let items = flatten_observations(items, &|expression| match expression {
+                ObservationExpression::FollowedBy(items) => Some(items),
+                _ => None,
+            });
+            ObservationExpression::FollowedBy(items)
+        }
+    }
+}
+
+/// Normalizes the operands of a chain, inlining the operands of those that are chains of the same operator.
+fn flatten_observations(
+    items: &[ObservationExpression],
+    same_operator: &dyn Fn(&ObservationExpression) -> Option<&Vec<ObservationExpression>>,
+) -> Vec<ObservationExpression> {
+    let mut flattened = Vec::new();
+    for item in items {
+        let item = normalize_observation(item);
+        match same_operator(&item) {
+            Some(inner) => flattened.extend(inner.iter().cloned()),
+            None => flattened.push(item),
+        }
+    }
+    flattened
+}
+
+fn normalize_comparison(expression: &ComparisonExpression) -> ComparisonExpression {
+    match expression {
+        ComparisonExpression::Comparison(comparison) => {
+            let mut comparison = comparison.clone();
+            if let Literal::Set(items) = &mut comparison.value {
+                sort_by_text(items);
+                items.dedup();
+            }
+            ComparisonExpression::Comparison(comparison)
+        }
+        ComparisonExpression::Exists(path) => ComparisonExpression::Exists(path.clone()),
+        ComparisonExpression::Group(inner) => normalize_comparison(inner),
+        ComparisonExpression::And(items) | ComparisonExpression::Or(items) => {
+            let is_and = matches!(expression, ComparisonExpression::And(_));
+            let mut flattened = Vec::new();
+            for item in items {
+                match (normalize_comparison(item), is_and) {
+                    (ComparisonExpression::And(inner), true) | (ComparisonExpression::Or(inner), false) => flattened.extend(inner),
+                    (item, _) => flattened.push(item),
+                }
+            }
+            sort_by_text(&mut flattened);
+            flattened.dedup();
+            match (flattened.len(), is_and) {
+                (1, _) => flattened.remove(0),
+                (_, true) => ComparisonExpression::And(flattened),
+                (_, false) => ComparisonExpression::Or(flattened),
+            }
+        }
+    }
+}
+
+fn sort_by_text<T: ToString>(items: &mut [T]) {
+    items.sort_by_cached_key(|item| item.to_string());
+}
@@ -0,0 +1,363 @@
+use std::error::Error;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use crate::core::patterns::ast::{
+    Comparison,
+    ComparisonExpression,
+    ComparisonOperator,
+    ListIndex,
+    Literal,
+    ObjectPath,
+    ObservationExpression,
+    PathComponent,
+    Pattern,
+    Qualifier,
+};
+use crate::core::patterns::lexer::{tokenize, Keyword, Spanned, Token};
+
+/// An error found while parsing a pattern, along with the position (in characters, starting at 0) where it was found.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternError {
+    pub position: usize,
+    pub kind: PatternErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatternErrorKind {
+    UnexpectedCharacter(char),
+    UnterminatedString,
+    InvalidEscapeSequence,
+    InvalidLiteral(String),
+    UnexpectedToken { found: String, expected: String },
+    UnexpectedEnd { expected: String },
+    InvalidQualifier(String),
+}
+
+impl PatternError {
+    pub fn new(position: usize, kind: PatternErrorKind) -> PatternError {
+        PatternError { position, kind }
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            PatternErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c)?,
+            PatternErrorKind::UnterminatedString => write!(f, "unterminated quoted literal")?,
+            PatternErrorKind::InvalidEscapeSequence => write!(f, "invalid escape sequence, only \\' and \\\\ are allowed")?,
+            PatternErrorKind::InvalidLiteral(literal) => write!(f, "invalid literal {}", literal)?,
+            PatternErrorKind::UnexpectedToken { found, expected } => write!(f, "found {} but expected {}", found, expected)?,
+            PatternErrorKind::UnexpectedEnd { expected } => write!(f, "unexpected end of pattern, expected {}", expected)?,
+            PatternErrorKind::InvalidQualifier(reason) => write!(f, "invalid qualifier: {}", reason)?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for PatternError {}
+
+/// Parses a STIX 2.1 pattern into its abstract syntax tree.
+pub fn parse(pattern: &str) -> Result<Pattern, PatternError> {
+    let tokens = tokenize(pattern)?;
+    let mut parser = Parser { tokens, current: 0, end: pattern.chars().count() };
+    let expression = parser.observation_expressions()?;
+    if let Some(spanned) = parser.peek() {
+        return Err(parser.unexpected(spanned, "FOLLOWEDBY, AND, OR or a qualifier"));
+    }
+    Ok(Pattern { expression })
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    current: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.current)
+    }
+
+    fn peek_token(&self) -> Option<&Token> {
+        self.peek().map(|spanned| &spanned.token)
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Spanned, PatternError> {
+        match self.tokens.get(self.current) {
+            Some(spanned) => {
+                self.current += 1;
+                Ok(spanned.clone())
+            }
+            None => Err(PatternError::new(self.end, PatternErrorKind::UnexpectedEnd { expected: expected.to_string() })),
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek_token() == Some(token) {
+            self.current += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<Spanned, PatternError> {
+        let spanned = self.next(expected)?;
+        if spanned.token == token {
+            Ok(spanned)
+        } else {
+            Err(self.unexpected(&spanned, expected))
+        }
+    }
+
+    fn unexpected(&self, spanned: &Spanned, expected: &str) -> PatternError {
+        PatternError::new(
+            spanned.position,
+            PatternErrorKind::UnexpectedToken { found: spanned.token.to_string(), expected: expected.to_string() },
+        )
+    }
+
+    fn observation_expressions(&mut self) -> Result<ObservationExpression, PatternError> {
+        let mut items = vec![self.observation_expression_or()?];
+        while self.eat(&Token::Keyword(Keyword::FollowedBy)) {
+            items.push(self.observation_expression_or()?);
+        }
+        Ok(chain(items, ObservationExpression::FollowedBy))
+    }
+
+    fn observation_expression_or(&mut self) -> Result<ObservationExpression, PatternError> {
+        let mut items = vec![self.observation_expression_and()?];
+        while self.eat(&Token::Keyword(Keyword::Or)) {
+            items.push(self.observation_expression_and()?);
+        }
+        Ok(chain(items, ObservationExpression::Or))
+    }
+
+    fn observation_expression_and(&mut self) -> Result<ObservationExpression, PatternError> {
+        let mut items = vec![self.observation_expression()?];
+        while self.eat(&Token::Keyword(Keyword::And)) {
+            items.push(self.observation_expression()?);
+        }
+        Ok(chain(items, ObservationExpression::And))
+    }
+
+    fn observation_expression(&mut self) -> Result<ObservationExpression, PatternError> {
+        let spanned = self.next("'[' or '('")?;
+        let mut expression = match spanned.token {
+            Token::LeftBracket => {
+                let comparison = self.comparison_expression()?;
+                self.expect(Token::RightBracket, "']'")?;
+                ObservationExpression::Observation(comparison)
+            }
+            Token::LeftParenthesis => {
+                let inner = self.observation_expressions()?;
+                self.expect(Token::RightParenthesis, "')'")?;
+                ObservationExpression::Group(Box::new(inner))
+            }
+            _ => return Err(self.unexpected(&spanned, "'[' or '('")),
+        };
+        while let Some(qualifier) = self.qualifier()? {
+            expression = ObservationExpression::Qualified(Box::new(expression), qualifier);
+        }
+        Ok(expression)
+    }
+
+    fn qualifier(&mut self) -> Result<Option<Qualifier>, PatternError> {
+        let keyword = match self.peek_token() {
+            Some(Token::Keyword(keyword @ (Keyword::Within | Keyword::Repeats | Keyword::Start))) => *keyword,
+            _ => return Ok(None),
+        };
+        self.current += 1;
+        let spanned = self.next("a qualifier value")?;
+        let qualifier = match (keyword, spanned.token.clone()) {
+            (Keyword::Within, Token::Integer(seconds)) if seconds > 0 => Qualifier::Within(seconds as f64),
+            (Keyword::Within, Token::Float(seconds)) if seconds > 0.0 => Qualifier::Within(seconds),
+            (Keyword::Within, Token::Integer(_) | Token::Float(_)) => {
+                return Err(PatternError::new(spanned.position, PatternErrorKind::InvalidQualifier("WITHIN requires a positive number of seconds".to_string())));
+            }
+            (Keyword::Repeats, Token::Integer(times)) if times > 0 => Qualifier::Repeats(times as u64),
+            (Keyword::Repeats, Token::Integer(_)) => {
+                return Err(PatternError::new(spanned.position, PatternErrorKind::InvalidQualifier("REPEATS requires a positive integer".to_string())));
+            }
+            (Keyword::Start, Token::Timestamp(start)) => {
+                let start = parse_timestamp(&start, spanned.position)?;
+                self.expect(Token::Keyword(Keyword::Stop), "STOP")?;
+                let stop_spanned = self.next("a timestamp literal")?;
+                let stop = match stop_spanned.token {
+                    Token::Timestamp(ref stop) => parse_timestamp(stop, stop_spanned.position)?,
+                    _ => return Err(self.unexpected(&stop_spanned, "a timestamp literal")),
+                };
+                if stop <= start {
+                    return Err(PatternError::new(stop_spanned.position, PatternErrorKind::InvalidQualifier("STOP must be later than START".to_string())));
+                }
+                return Ok(Some(Qualifier::StartStop(start, stop)));
+            }
+            (Keyword::Within, _) => return Err(self.unexpected(&spanned, "a number of seconds")),
+            (Keyword::Repeats, _) => return Err(self.unexpected(&spanned, "an integer")),
+            _ => return Err(self.unexpected(&spanned, "a timestamp literal")),
+        };
+        match keyword {
+            Keyword::Within => self.expect(Token::Keyword(Keyword::Seconds), "SECONDS")?,
+            _ => self.expect(Token::Keyword(Keyword::Times), "TIMES")?,
+        };
+        Ok(Some(qualifier))
+    }
+
+    fn comparison_expression(&mut self) -> Result<ComparisonExpression, PatternError> {
+        let mut items = vec![self.comparison_expression_and()?];
+        while self.eat(&Token::Keyword(Keyword::Or)) {
+            items.push(self.comparison_expression_and()?);
+        }
+        Ok(chain(items, ComparisonExpression::Or))
+    }
+
+    fn comparison_expression_and(&mut self) -> Result<ComparisonExpression, PatternError> {
+        let mut items = vec![self.property_test()?];
+        while self.eat(&Token::Keyword(Keyword::And)) {
+            items.push(self.property_test()?);
+        }
+        Ok(chain(items, ComparisonExpression::And))
+    }
+
+    fn property_test(&mut self) -> Result<ComparisonExpression, PatternError> {
+        if self.eat(&Token::LeftParenthesis) {
+            let inner = self.comparison_expression()?;
+            self.expect(Token::RightParenthesis, "')'")?;
+            return Ok(ComparisonExpression::Group(Box::new(inner)));
+        }
+        if self.eat(&Token::Keyword(Keyword::Exists)) {
+            return Ok(ComparisonExpression::Exists(self.object_path()?));
+        }
+        let path = self.object_path()?;
+        let negated = self.eat(&Token::Keyword(Keyword::Not));
+        let expected = "a comparison operator";
+        let spanned = self.next(expected)?;
+        let operator = match spanned.token {
+            Token::Equal => ComparisonOperator::Equal,
+            Token::NotEqual => ComparisonOperator::NotEqual,
+            Token::GreaterThan => ComparisonOperator::GreaterThan,
+            Token::LessThan => ComparisonOperator::LessThan,
+            Token::GreaterOrEqual => ComparisonOperator::GreaterOrEqual,
+            Token::LessOrEqual => ComparisonOperator::LessOrEqual,
+            Token::Keyword(Keyword::In) => ComparisonOperator::In,
+            Token::Keyword(Keyword::Like) => ComparisonOperator::Like,
+            Token::Keyword(Keyword::Matches) => ComparisonOperator::Matches,
+            Token::Keyword(Keyword::IsSubset) => ComparisonOperator::IsSubset,
+            Token::Keyword(Keyword::IsSuperset) => ComparisonOperator::IsSuperset,
+            _ => return Err(self.unexpected(&spanned, expected)),
+        };
+        let value = match operator {
+            ComparisonOperator::Equal | ComparisonOperator::NotEqual => self.primitive_literal(true)?,
+            ComparisonOperator::GreaterThan
+            | ComparisonOperator::LessThan
+            | ComparisonOperator::GreaterOrEqual
+            | ComparisonOperator::LessOrEqual => self.primitive_literal(false)?,
+            ComparisonOperator::In => self.set_literal()?,
+            _ => {
+                let spanned = self.next("a string literal")?;
+                match spanned.token {
+                    Token::String(text) => Literal::String(text),
+                    _ => return Err(self.unexpected(&spanned, "a string literal")),
+                }
+            }
+        };
+        Ok(ComparisonExpression::Comparison(Comparison { path, negated, operator, value }))
+    }
+
+    fn object_path(&mut self) -> Result<ObjectPath, PatternError> {
+        let spanned = self.next("an object type")?;
+        let object_type = match spanned.token {
+            Token::Identifier(identifier) => identifier,
+            _ => return Err(self.unexpected(&spanned, "an object type")),
+        };
+        self.expect(Token::Colon, "':'")?;
+        let spanned = self.next("a property name")?;
+        let mut components = match spanned.token {
+            Token::Identifier(ref identifier) if !identifier.contains('-') => vec![PathComponent::Property(identifier.clone())],
+            Token::String(text) => vec![PathComponent::Property(text)],
+            _ => return Err(self.unexpected(&spanned, "a property name")),
+        };
+        loop {
+            if self.eat(&Token::Dot) {
+                let spanned = self.next("a property name")?;
+                match spanned.token {
+                    Token::Identifier(ref identifier) if !identifier.contains('-') => components.push(PathComponent::Property(identifier.clone())),
+                    Token::String(text) => components.push(PathComponent::Property(text)),
+                    _ => return Err(self.unexpected(&spanned, "a property name")),
+                }
+            } else if self.peek_token() == Some(&Token::LeftBracket) {
+                self.current += 1;
+                let spanned = self.next("a list index or '*'")?;
+                match spanned.token {
+                    Token::Integer(position) => components.push(PathComponent::Index(ListIndex::Position(position))),
+                    Token::Asterisk => components.push(PathComponent::Index(ListIndex::Any)),
+                    _ => return Err(self.unexpected(&spanned, "a list index or '*'")),
+                }
+                self.expect(Token::RightBracket, "']'")?;
+            } else {
+                break;
+            }
+        }
+        Ok(ObjectPath { object_type, components })
+    }
+
+    fn primitive_literal(&mut self, allow_boolean: bool) -> Result<Literal, PatternError> {
+        let expected = if allow_boolean { "a literal" } else { "an orderable literal" };
+        let spanned = self.next(expected)?;
+        let literal = match spanned.token {
+            Token::Integer(value) => Literal::Integer(value),
+            Token::Float(value) => Literal::Float(value),
+            Token::String(ref text) => Literal::String(text.clone()),
+            Token::Binary(ref text) if is_base64(text) => Literal::Binary(text.clone()),
+            Token::Hex(ref text) if text.len().is_multiple_of(2) && text.chars().all(|c| c.is_ascii_hexdigit()) => Literal::Hex(text.to_lowercase()),
+            Token::Binary(ref text) => return Err(PatternError::new(spanned.position, PatternErrorKind::InvalidLiteral(format!("b'{}'", text)))),
+            Token::Hex(ref text) => return Err(PatternError::new(spanned.position, PatternErrorKind::InvalidLiteral(format!("h'{}'", text)))),
+            Token::Timestamp(ref text) => Literal::Timestamp(parse_timestamp(text, spanned.position)?),
+            Token::Keyword(Keyword::True) if allow_boolean => Literal::Boolean(true),
+            Token::Keyword(Keyword::False) if allow_boolean => Literal::Boolean(false),
+            _ => return Err(self.unexpected(&spanned, expected)),
+        };
+        Ok(literal)
+    }
+
+    fn set_literal(&mut self) -> Result<Literal, PatternError> {
+        self.expect(Token::LeftParenthesis, "'('")?;
+        let mut items = Vec::new();
+        if self.eat(&Token::RightParenthesis) {
+            return Ok(Literal::Set(items));
+        }
+        loop {
+            items.push(self.primitive_literal(true)?);
+            let spanned = self.next("',' or ')'")?;
+            match spanned.token {
+                Token::Comma => continue,
+                Token::RightParenthesis => break,
+                _ => return Err(self.unexpected(&spanned, "',' or ')'")),
+            }
+        }
+        Ok(Literal::Set(items))
+    }
+}
+
+/// Builds a chain node out of the operands found, avoiding useless single item chains.
+fn chain<T>(mut items: Vec<T>, build: fn(Vec<T>) -> T) -> T {
+    if items.len() == 1 {
+        items.remove(0)
+    } else {
+        build(items)
+    }
+}
+
+fn parse_timestamp(text: &str, position: usize) -> Result<DateTime<Utc>, PatternError> {
+    match DateTime::parse_from_rfc3339(text) {
+        Ok(timestamp) if text.ends_with('Z') && text.contains('T') => Ok(timestamp.with_timezone(&Utc)),
+        _ => Err(PatternError::new(position, PatternErrorKind::InvalidLiteral(format!("t'{}'", text)))),
+    }
+}
+
+pub(crate) fn is_base64(text: &str) -> bool {
+    let trimmed = text.trim_end_matches('=');
+    text.len().is_multiple_of(4)
+        && text.len() - trimmed.len() <= 2
+        && trimmed.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+}
@@ -0,0 +1,108 @@
+/// This module checks the patterns of the indicators written in the other languages of the `pattern-type-ov` vocabulary.
+/// Each language gets a checker rejecting the malformed patterns: PCRE patterns are compiled, Sigma rules parsed as YAML,
+/// Snort and Suricata rules split into header and options, and YARA rules lexed and checked for their structure.
+mod network;
+mod sigma;
+mod yara;
+
+use std::fmt;
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use crate::core::patterns::{self, semantics};
+
+/// The language of the pattern of an indicator. Since the vocabulary is open, unknown languages are kept as they are.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum PatternType {
+    Stix,
+    Pcre,
+    Sigma,
+    Snort,
+    Suricata,
+    Yara,
+    Other(String),
+}
+
+impl PatternType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PatternType::Stix => "stix",
+            PatternType::Pcre => "pcre",
+            PatternType::Sigma => "sigma",
+            PatternType::Snort => "snort",
+            PatternType::Suricata => "suricata",
+            PatternType::Yara => "yara",
+            PatternType::Other(pattern_type) => pattern_type,
+        }
+    }
+
+    /// Checks that a pattern is well formed in this language, returning the problem found otherwise.
+    /// Patterns in other languages than those of the vocabulary are not checked.
+    pub fn check(&self, pattern: &str) -> Result<(), String> {
+        match self {
+            PatternType::Stix => match patterns::parse(pattern) {
+                Ok(parsed) => match semantics::check(&parsed).into_iter().find(|diagnostic| diagnostic.severity == semantics::Severity::Error) {
+                    Some(diagnostic) => Err(diagnostic.to_string()),
+                    None => Ok(()),
+                },
+                Err(error) => Err(error.to_string()),
+            },
+            PatternType::Pcre => check_pcre(pattern),
+            PatternType::Sigma => sigma::check(pattern),
+            PatternType::Snort | PatternType::Suricata => network::check(pattern),
+            PatternType::Yara => yara::check(pattern),
+            PatternType::Other(_) => Ok(()),
+        }
+    }
+}
+
+impl From<String> for PatternType {
+    fn from(pattern_type: String) -> PatternType {
+        match pattern_type.as_str() {
+            "stix" => PatternType::Stix,
+            "pcre" => PatternType::Pcre,
+            "sigma" => PatternType::Sigma,
+            "snort" => PatternType::Snort,
+            "suricata" => PatternType::Suricata,
+            "yara" => PatternType::Yara,
+            _ => PatternType::Other(pattern_type),
+        }
+    }
+}
+
+impl From<&str> for PatternType {
+    fn from(pattern_type: &str) -> PatternType {
+        PatternType::from(pattern_type.to_string())
+    }
+}
+
+impl From<PatternType> for String {
+    fn from(pattern_type: PatternType) -> String {
+        pattern_type.as_str().to_string()
+    }
+}
+
+impl fmt::Display for PatternType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Compiles a regular expression, written either bare or between slashes followed by its flags (e. g. `/evil\.exe$/i`).
+/// The syntax accepted is that of the `regex` crate, which lacks some PCRE features such as backreferences and lookarounds.
+fn check_pcre(pattern: &str) -> Result<(), String> {
+    let delimited = pattern
+        .strip_prefix('/')
+        .and_then(|rest| rest.rfind('/').map(|end| (&rest[..end], &rest[end + 1..])))
+        .filter(|(_, flags)| flags.chars().all(|flag| "imsxU".contains(flag)));
+    let (regex, flags) = delimited.unwrap_or((pattern, ""));
+    RegexBuilder::new(regex)
+        .case_insensitive(flags.contains('i'))
+        .multi_line(flags.contains('m'))
+        .dot_matches_new_line(flags.contains('s'))
+        .ignore_whitespace(flags.contains('x'))
+        .swap_greed(flags.contains('U'))
+        .build()
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
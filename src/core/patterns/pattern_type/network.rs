@@ -0,0 +1,72 @@
+const ACTIONS: [&str; 9] = ["alert", "log", "pass", "drop", "reject", "sdrop", "rejectsrc", "rejectdst", "rejectboth"];
+
+/// Checks Snort or Suricata rules, one per line, skipping the empty lines and the comments.
+///
+/// Each rule needs an action, a header (either the full `protocol source port direction destination port` one or,
+/// as Snort 3 allows, only a protocol or service) and options between parentheses, among which a `sid`.
+pub fn check(pattern: &str) -> Result<(), String> {
+    let mut rules = 0;
+    for line in pattern.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        rules += 1;
+        check_rule(line).map_err(|error| format!("rule {}: {}", rules, error))?;
+    }
+    if rules == 0 {
+        return Err("no rule found".to_string());
+    }
+    Ok(())
+}
+
+fn check_rule(rule: &str) -> Result<(), String> {
+    let open = rule.find('(').ok_or("the options are missing")?;
+    if !rule.ends_with(')') {
+        return Err("the options are not closed".to_string());
+    }
+    let header: Vec<&str> = rule[..open].split_whitespace().collect();
+    match header.as_slice() {
+        [action, ..] if !ACTIONS.contains(action) => return Err(format!("unknown action '{}'", action)),
+        [_, _] | [_, _, _, _, "->" | "<>", _, _] => {}
+        [_, _, _, _, direction, _, _] => return Err(format!("unknown direction '{}'", direction)),
+        _ => return Err("the header must be 'action protocol source port direction destination port'".to_string()),
+    }
+    let options = split_options(&rule[open + 1..rule.len() - 1])?;
+    for option in &options {
+        let name = option.split(':').next().unwrap_or_default().trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-') {
+            return Err(format!("invalid option '{}'", option));
+        }
+    }
+    let sid = options.iter().find_map(|option| option.trim().strip_prefix("sid:"));
+    match sid.map(|sid| sid.trim().parse::<u64>()) {
+        Some(Ok(_)) => Ok(()),
+        Some(Err(_)) => Err("the sid must be a number".to_string()),
+        None => Err("the sid option is missing".to_string()),
+    }
+}
+
+/// Splits the options at the semicolons, except those escaped or quoted.
+fn split_options(options: &str) -> Result<Vec<String>, String> {
+    let mut found = Vec::new();
+    let mut current = String::new();
+    let (mut quoted, mut escaped) = (false, false);
+    for c in options.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                found.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if quoted {
+        return Err("unterminated quoted option value".to_string());
+    }
+    if !current.trim().is_empty() {
+        return Err(format!("option '{}' is not terminated by a semicolon", current.trim()));
+    }
+    Ok(found.into_iter().filter(|option| !option.is_empty()).collect())
+}
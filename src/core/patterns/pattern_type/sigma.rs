@@ -0,0 +1,74 @@
+use serde::Deserialize;
+use serde_yaml::{Deserializer, Value};
+
+/// Checks a Sigma rule, or a collection of rules written as several YAML documents.
+///
+/// Every rule needs a `title`, a `logsource` and a `detection` with a `condition` naming only the searches it defines.
+/// The `action: global` documents of the collections, which only hold properties shared by the next rules, are not checked.
+pub fn check(pattern: &str) -> Result<(), String> {
+    let mut rules = 0;
+    for (i, document) in Deserializer::from_str(pattern).enumerate() {
+        let rule = Value::deserialize(document).map_err(|error| format!("invalid YAML: {}", error))?;
+        let rule = rule.as_mapping().ok_or_else(|| format!("document {} is not a mapping", i + 1))?;
+        if rule.get("action").and_then(Value::as_str).is_some() {
+            continue;
+        }
+        rules += 1;
+        if !matches!(rule.get("title"), Some(Value::String(_))) {
+            return Err("a rule has no title".to_string());
+        }
+        if !matches!(rule.get("logsource"), Some(Value::Mapping(_))) {
+            return Err("a rule has no logsource".to_string());
+        }
+        let detection = match rule.get("detection") {
+            Some(Value::Mapping(detection)) => detection,
+            _ => return Err("a rule has no detection".to_string()),
+        };
+        let conditions = match detection.get("condition") {
+            Some(Value::String(condition)) => vec![condition.as_str()],
+            Some(Value::Sequence(conditions)) => conditions.iter().filter_map(Value::as_str).collect(),
+            _ => return Err("the detection has no condition".to_string()),
+        };
+        let searches: Vec<&str> = detection.keys().filter_map(Value::as_str).filter(|name| *name != "condition" && *name != "timeframe").collect();
+        for condition in conditions {
+            check_condition(condition, &searches)?;
+        }
+    }
+    if rules == 0 {
+        return Err("no rule found".to_string());
+    }
+    Ok(())
+}
+
+/// Checks that the parentheses of a condition are balanced and that it only refers to the searches defined.
+fn check_condition(condition: &str, searches: &[&str]) -> Result<(), String> {
+    const KEYWORDS: [&str; 8] = ["and", "or", "not", "of", "them", "all", "near", "by"];
+    let mut depth = 0i32;
+    let spaced = condition.replace('(', " ( ").replace(')', " ) ").replace('|', " | ");
+    for word in spaced.split_whitespace() {
+        match word {
+            "(" => depth += 1,
+            ")" => {
+                depth -= 1;
+                if depth < 0 {
+                    return Err(format!("unbalanced parentheses in condition '{}'", condition));
+                }
+            }
+            // Aggregations, e. g. `selection | count() > 5`, are left unchecked.
+            "|" => return if depth == 0 { Ok(()) } else { Err(format!("unbalanced parentheses in condition '{}'", condition)) },
+            _ if KEYWORDS.contains(&word.to_lowercase().as_str()) || word.chars().all(|c| c.is_ascii_digit()) => {}
+            _ if word.contains('*') => {
+                let prefix = word.trim_end_matches('*');
+                if !searches.iter().any(|search| search.starts_with(prefix)) {
+                    return Err(format!("no search matches '{}' in the condition", word));
+                }
+            }
+            _ if !searches.contains(&word) => return Err(format!("the condition refers to the undefined search '{}'", word)),
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(format!("unbalanced parentheses in condition '{}'", condition));
+    }
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    /// `$name`, or `$` alone in the loops over the strings.
+    StringId(String),
+    /// `#name`, `@name` or `!name`, i. e. the count, offset or length of the matches of a string.
+    StringReference(String),
+    Text(String),
+    Hex(String),
+    Regex(String),
+    Number,
+    Symbol(&'static str),
+}
+
+/// Checks YARA rules: the source must be lexically correct and made of imports, includes and rules, each rule
+/// having its optional `meta` and `strings` sections followed by a non-empty `condition` that only refers to the strings defined.
+pub fn check(pattern: &str) -> Result<(), String> {
+    let tokens = tokenize(pattern)?;
+    let mut parser = Parser { tokens: &tokens, position: 0 };
+    let mut names = HashSet::new();
+    let mut rules = 0;
+    while let Some(token) = parser.peek() {
+        match token {
+            Token::Identifier(keyword) if keyword == "import" || keyword == "include" => {
+                parser.position += 1;
+                if !matches!(parser.next(), Some(Token::Text(_))) {
+                    return Err(format!("{} must be followed by a quoted path", keyword));
+                }
+            }
+            _ => {
+                let name = parser.rule()?;
+                if !names.insert(name.clone()) {
+                    return Err(format!("duplicated rule '{}'", name));
+                }
+                rules += 1;
+            }
+        }
+    }
+    if rules == 0 {
+        return Err("no rule found".to_string());
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Identifier(identifier)) if identifier == keyword)
+    }
+
+    fn expect(&mut self, symbol: &'static str, context: &str) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Symbol(found)) if *found == symbol => Ok(()),
+            _ => Err(format!("expected '{}' {}", symbol, context)),
+        }
+    }
+
+    fn identifier(&mut self, context: &str) -> Result<&'a str, String> {
+        match self.next() {
+            Some(Token::Identifier(identifier)) => Ok(identifier),
+            _ => Err(format!("expected an identifier {}", context)),
+        }
+    }
+
+    /// Parses a rule, returning its name.
+    fn rule(&mut self) -> Result<String, String> {
+        while self.is_keyword("private") || self.is_keyword("global") {
+            self.position += 1;
+        }
+        if !self.is_keyword("rule") {
+            return Err("expected a rule, an import or an include".to_string());
+        }
+        self.position += 1;
+        let name = self.identifier("as the rule name")?.to_string();
+        let context = format!("in rule '{}'", name);
+        if self.peek() == Some(&Token::Symbol(":")) {
+            self.position += 1;
+            while let Some(Token::Identifier(_)) = self.peek() {
+                self.position += 1;
+            }
+        }
+        self.expect("{", &context)?;
+        if self.is_keyword("meta") {
+            self.position += 1;
+            self.expect(":", &context)?;
+            while !self.is_keyword("strings") && !self.is_keyword("condition") {
+                self.identifier(&format!("as a meta name {}", context))?;
+                self.expect("=", &context)?;
+                if self.peek() == Some(&Token::Symbol("-")) {
+                    self.position += 1;
+                }
+                match self.next() {
+                    Some(Token::Text(_)) | Some(Token::Number) => {}
+                    Some(Token::Identifier(value)) if value == "true" || value == "false" => {}
+                    _ => return Err(format!("invalid meta value {}", context)),
+                }
+            }
+        }
+        let mut strings = HashSet::new();
+        if self.is_keyword("strings") {
+            self.position += 1;
+            self.expect(":", &context)?;
+            while let Some(Token::StringId(id)) = self.peek() {
+                self.position += 1;
+                if !id.is_empty() && !strings.insert(id.clone()) {
+                    return Err(format!("duplicated string ${} {}", id, context));
+                }
+                self.expect("=", &context)?;
+                match self.next() {
+                    Some(Token::Text(_)) | Some(Token::Hex(_)) | Some(Token::Regex(_)) => {}
+                    _ => return Err(format!("invalid value for string ${} {}", id, context)),
+                }
+                self.modifiers()?;
+            }
+            if strings.is_empty() {
+                return Err(format!("empty strings section {}", context));
+            }
+        }
+        if !self.is_keyword("condition") {
+            return Err(format!("expected the condition {}", context));
+        }
+        self.position += 1;
+        self.expect(":", &context)?;
+        self.condition(&strings, &context)?;
+        Ok(name)
+    }
+
+    /// Skips the modifiers of a string, along with their arguments, e. g. `wide xor(0x01-0xff)`.
+    fn modifiers(&mut self) -> Result<(), String> {
+        const MODIFIERS: [&str; 8] = ["nocase", "wide", "ascii", "xor", "base64", "base64wide", "fullword", "private"];
+        while let Some(Token::Identifier(modifier)) = self.peek() {
+            if modifier == "condition" {
+                break;
+            }
+            if !MODIFIERS.contains(&modifier.as_str()) {
+                return Err(format!("unknown string modifier '{}'", modifier));
+            }
+            self.position += 1;
+            if self.peek() == Some(&Token::Symbol("(")) {
+                while !matches!(self.next(), Some(Token::Symbol(")")) | None) {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the condition up to the brace closing the rule.
+    fn condition(&mut self, strings: &HashSet<String>, context: &str) -> Result<(), String> {
+        let mut depth = 0;
+        let mut empty = true;
+        loop {
+            match self.next() {
+                None => return Err(format!("unterminated condition {}", context)),
+                Some(Token::Symbol("}")) if depth == 0 => break,
+                Some(Token::Symbol("(")) => depth += 1,
+                Some(Token::Symbol(")")) if depth == 0 => return Err(format!("unbalanced parentheses {}", context)),
+                Some(Token::Symbol(")")) => depth -= 1,
+                Some(Token::StringId(id)) | Some(Token::StringReference(id)) if !id.is_empty() => {
+                    let defined = match id.strip_suffix('*') {
+                        Some(prefix) => strings.iter().any(|string| string.starts_with(prefix)),
+                        None => strings.contains(id),
+                    };
+                    if !defined {
+                        return Err(format!("undefined string ${} {}", id, context));
+                    }
+                }
+                _ => {}
+            }
+            empty = false;
+        }
+        if empty || depth != 0 {
+            return Err(format!("empty or unbalanced condition {}", context));
+        }
+        Ok(())
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    const SYMBOLS: [&str; 27] = [
+        "..", "==", "!=", "<=", ">=", "<<", ">>", "{", "}", "(", ")", "[", "]", ":", "=", ",", ".", "<", ">", "+", "-", "*", "\\", "%", "&", "|", "^",
+    ];
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+        // Hex strings and regular expressions are only found as the values of the strings, or after `matches`.
+        let after_string_id = tokens.len() >= 2 && matches!(tokens[tokens.len() - 2], Token::StringId(_)) && tokens[tokens.len() - 1] == Token::Symbol("=");
+        let after_matches = matches!(tokens.last(), Some(Token::Identifier(keyword)) if keyword == "matches");
+        if c.is_whitespace() {
+            i += 1;
+        } else if rest == "//" {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if rest == "/*" {
+            let end = (i + 2..chars.len().saturating_sub(1)).find(|j| chars[*j] == '*' && chars[j + 1] == '/').ok_or("unterminated comment")?;
+            i = end + 2;
+        } else if c == '"' {
+            let (text, end) = delimited(&chars, i, '"').ok_or("unterminated string")?;
+            tokens.push(Token::Text(text));
+            i = end;
+        } else if c == '/' && (after_string_id || after_matches) {
+            let (regex, end) = delimited(&chars, i, '/').ok_or("unterminated regular expression")?;
+            i = end;
+            while i < chars.len() && (chars[i] == 'i' || chars[i] == 's') {
+                i += 1;
+            }
+            tokens.push(Token::Regex(regex));
+        } else if c == '{' && after_string_id {
+            let end = (i..chars.len()).find(|j| chars[*j] == '}').ok_or("unterminated hex string")?;
+            let hex: String = chars[i + 1..end].iter().collect();
+            let valid = hex.chars().all(|c| c.is_ascii_hexdigit() || c.is_whitespace() || "?[]-()|~".contains(c));
+            if !valid || hex.chars().filter(|c| c.is_ascii_hexdigit() || *c == '?').count() == 0 {
+                return Err(format!("invalid hex string {{{}}}", hex));
+            }
+            tokens.push(Token::Hex(hex));
+            i = end + 1;
+        } else if c == '$' || ((c == '#' || c == '@' || c == '!') && chars.get(i + 1).is_some_and(|next| next.is_ascii_alphabetic() || *next == '_')) {
+            let start = i + 1;
+            i = start;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '*') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            tokens.push(if c == '$' { Token::StringId(name) } else { Token::StringReference(name) });
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || (chars[i] == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))) {
+                i += 1;
+            }
+            tokens.push(Token::Number);
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Identifier(chars[start..i].iter().collect()));
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            i += symbol.chars().count();
+        } else if c == '~' || c == '/' {
+            tokens.push(Token::Symbol(if c == '~' { "~" } else { "/" }));
+            i += 1;
+        } else {
+            return Err(format!("unexpected character '{}'", c));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reads the text between two delimiters, skipping the escaped characters. Returns the text and the position after the closing delimiter.
+fn delimited(chars: &[char], start: usize, delimiter: char) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                text.push(chars[i]);
+                text.push(chars[i + 1]);
+                i += 2;
+            }
+            '\n' => return None,
+            c if c == delimiter => return Some((text, i + 1)),
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    None
+}
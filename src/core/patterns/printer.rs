@@ -0,0 +1,218 @@
+use std::fmt;
+use chrono::SecondsFormat;
+use crate::core::patterns::ast::{
+    Comparison,
+    ComparisonExpression,
+    ComparisonOperator,
+    ListIndex,
+    Literal,
+    ObjectPath,
+    ObservationExpression,
+    PathComponent,
+    Pattern,
+    Qualifier,
+};
+
+// Printing never loses information: `Group` nodes are printed as the parentheses they come from and any other
+// parentheses required by the operator precedence are added back, so the output can always be parsed again.
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl ObservationExpression {
+    fn precedence(&self) -> u8 {
+        match self {
+            ObservationExpression::FollowedBy(_) => 1,
+            ObservationExpression::Or(_) => 2,
+            ObservationExpression::And(_) => 3,
+            _ => 4,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter, parent_precedence: u8) -> fmt::Result {
+        if self.precedence() < parent_precedence {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+impl fmt::Display for ObservationExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (items, operator) = match self {
+            ObservationExpression::Observation(comparison) => return write!(f, "[{}]", comparison),
+            ObservationExpression::Group(inner) => return write!(f, "({})", inner),
+            ObservationExpression::Qualified(inner, qualifier) => {
+                inner.fmt_operand(f, 4)?;
+                return write!(f, " {}", qualifier);
+            }
+            ObservationExpression::And(items) => (items, "AND"),
+            ObservationExpression::Or(items) => (items, "OR"),
+            ObservationExpression::FollowedBy(items) => (items, "FOLLOWEDBY"),
+        };
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", operator)?;
+            }
+            // Chains are flat, so a nested chain of the same operator needs its parentheses too.
+            item.fmt_operand(f, self.precedence() + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Qualifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Qualifier::Within(seconds) => write!(f, "WITHIN {} SECONDS", format_float(*seconds, false)),
+            Qualifier::Repeats(times) => write!(f, "REPEATS {} TIMES", times),
+            Qualifier::StartStop(start, stop) => write!(
+                f,
+                "START t'{}' STOP t'{}'",
+                start.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                stop.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            ),
+        }
+    }
+}
+
+impl ComparisonExpression {
+    fn precedence(&self) -> u8 {
+        match self {
+            ComparisonExpression::Or(_) => 1,
+            ComparisonExpression::And(_) => 2,
+            _ => 3,
+        }
+    }
+}
+
+impl fmt::Display for ComparisonExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (items, operator) = match self {
+            ComparisonExpression::Comparison(comparison) => return write!(f, "{}", comparison),
+            ComparisonExpression::Exists(path) => return write!(f, "EXISTS {}", path),
+            ComparisonExpression::Group(inner) => return write!(f, "({})", inner),
+            ComparisonExpression::And(items) => (items, "AND"),
+            ComparisonExpression::Or(items) => (items, "OR"),
+        };
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", operator)?;
+            }
+            if item.precedence() <= self.precedence() {
+                write!(f, "({})", item)?;
+            } else {
+                write!(f, "{}", item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.path)?;
+        if self.negated {
+            write!(f, "NOT ")?;
+        }
+        write!(f, "{} {}", self.operator, self.value)
+    }
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operator = match self {
+            ComparisonOperator::Equal => "=",
+            ComparisonOperator::NotEqual => "!=",
+            ComparisonOperator::GreaterThan => ">",
+            ComparisonOperator::LessThan => "<",
+            ComparisonOperator::GreaterOrEqual => ">=",
+            ComparisonOperator::LessOrEqual => "<=",
+            ComparisonOperator::In => "IN",
+            ComparisonOperator::Like => "LIKE",
+            ComparisonOperator::Matches => "MATCHES",
+            ComparisonOperator::IsSubset => "ISSUBSET",
+            ComparisonOperator::IsSuperset => "ISSUPERSET",
+        };
+        write!(f, "{}", operator)
+    }
+}
+
+impl fmt::Display for ObjectPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", self.object_type)?;
+        for (i, component) in self.components.iter().enumerate() {
+            match component {
+                PathComponent::Property(name) => {
+                    if i > 0 {
+                        write!(f, ".")?;
+                    }
+                    if is_plain_identifier(name) {
+                        write!(f, "{}", name)?;
+                    } else {
+                        write!(f, "'{}'", escape(name))?;
+                    }
+                }
+                PathComponent::Index(ListIndex::Position(position)) => write!(f, "[{}]", position)?,
+                PathComponent::Index(ListIndex::Any) => write!(f, "[*]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Literal::Integer(value) => write!(f, "{}", value),
+            Literal::Float(value) => write!(f, "{}", format_float(*value, true)),
+            Literal::String(text) => write!(f, "'{}'", escape(text)),
+            Literal::Boolean(value) => write!(f, "{}", value),
+            Literal::Binary(text) => write!(f, "b'{}'", text),
+            Literal::Hex(text) => write!(f, "h'{}'", text),
+            Literal::Timestamp(timestamp) => write!(f, "t'{}'", timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+            Literal::Set(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Escapes the backslashes and quotes of a string so that it can be written between single quotes.
+pub fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Whether a property name can be written without quotes, i. e. it is an identifier that the lexer would not take for a keyword.
+fn is_plain_identifier(name: &str) -> bool {
+    const KEYWORDS: [&str; 20] = [
+        "AND", "OR", "NOT", "FOLLOWEDBY", "LIKE", "MATCHES", "ISSUBSET", "ISSUPERSET", "EXISTS", "IN",
+        "START", "STOP", "SECONDS", "WITHIN", "REPEATS", "TIMES", "true", "TRUE", "false", "FALSE",
+    ];
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name)
+}
+
+/// Writes a float without exponent (which the patterns cannot hold), keeping a decimal part only if needed or if `always_decimal` is set,
+/// so that a float literal is never read back as an integer, however large.
+fn format_float(value: f64, always_decimal: bool) -> String {
+    let text = format!("{}", value);
+    if always_decimal && !text.contains('.') {
+        format!("{}.0", text)
+    } else {
+        text
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+use crate::core::patterns::ast::{
+    Comparison,
+    ComparisonExpression,
+    ComparisonOperator,
+    Literal,
+    ObjectPath,
+    PathComponent,
+    Pattern,
+};
+use crate::core::scos::schema::{self, Property, PropertyType};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in an object path of a pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{} in {}: {}", severity, self.path, self.message)
+    }
+}
+
+/// Checks every object path of a pattern against the SCO definitions of the standard.
+///
+/// Errors are reported for unknown object types, properties and extensions, for list indices applied to non-list properties
+/// and for literals whose type does not suit the property compared. Warnings are reported for custom object types, properties
+/// and extensions, which cannot be checked, and for comparisons that are allowed but dubious (e. g. plain strings compared with timestamps).
+pub fn check(pattern: &Pattern) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for observation in pattern.expression.observations() {
+        check_comparison_expression(observation, &mut diagnostics);
+    }
+    diagnostics
+}
+
+/// Whether the pattern has no semantic errors. Warnings are ignored.
+pub fn is_valid(pattern: &Pattern) -> bool {
+    check(pattern).iter().all(|diagnostic| diagnostic.severity != Severity::Error)
+}
+
+fn check_comparison_expression(expression: &ComparisonExpression, diagnostics: &mut Vec<Diagnostic>) {
+    match expression {
+        ComparisonExpression::Comparison(comparison) => check_comparison(comparison, diagnostics),
+        ComparisonExpression::Exists(path) => {
+            Checker { path, diagnostics }.resolve();
+        }
+        ComparisonExpression::And(items) | ComparisonExpression::Or(items) => {
+            for item in items {
+                check_comparison_expression(item, diagnostics);
+            }
+        }
+        ComparisonExpression::Group(inner) => check_comparison_expression(inner, diagnostics),
+    }
+}
+
+fn check_comparison(comparison: &Comparison, diagnostics: &mut Vec<Diagnostic>) {
+    let mut checker = Checker { path: &comparison.path, diagnostics };
+    let property_type = match checker.resolve() {
+        Some(Resolved::Value(property_type)) => property_type,
+        Some(Resolved::Object) => {
+            checker.report(Severity::Error, "an object cannot be compared with a literal".to_string());
+            return;
+        }
+        Some(Resolved::Unchecked) | None => return,
+    };
+    match (property_type, comparison.operator) {
+        (PropertyType::List(_), _) => {
+            checker.report(Severity::Error, "a list must be indexed (e. g. with [*]) to be compared".to_string());
+        }
+        (PropertyType::Dictionary(_) | PropertyType::Object(_), _) => {
+            checker.report(Severity::Error, "an object cannot be compared with a literal".to_string());
+        }
+        (_, ComparisonOperator::Like | ComparisonOperator::Matches) => {
+            if !matches!(property_type, PropertyType::String | PropertyType::Hex | PropertyType::Binary | PropertyType::Any) {
+                checker.report(Severity::Error, format!("{} can only be applied to string properties", comparison.operator));
+            }
+        }
+        (_, ComparisonOperator::IsSubset | ComparisonOperator::IsSuperset) => {
+            let is_address = property_type == PropertyType::String
+                && comparison.path.components.last() == Some(&PathComponent::Property("value".to_string()));
+            if !is_address {
+                checker.report(Severity::Warning, format!("{} is only meaningful for IP address values", comparison.operator));
+            }
+        }
+        (_, ComparisonOperator::In) => {
+            if let Literal::Set(items) = &comparison.value {
+                for item in items {
+                    checker.check_literal(property_type, item);
+                }
+            }
+        }
+        _ => checker.check_literal(property_type, &comparison.value),
+    }
+}
+
+/// What an object path points to.
+enum Resolved {
+    Value(PropertyType),
+    Object,
+    Unchecked,
+}
+
+/// The element reached while following an object path.
+enum Step {
+    Sco(&'static str),
+    Extensions(&'static str),
+    Properties(&'static [Property]),
+    Value(PropertyType),
+}
+
+struct Checker<'a> {
+    path: &'a ObjectPath,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, severity: Severity, message: String) {
+        self.diagnostics.push(Diagnostic { severity, path: self.path.to_string(), message });
+    }
+
+    /// Follows the object path, reporting the problems found. Returns `None` if the path is wrong.
+    fn resolve(&mut self) -> Option<Resolved> {
+        let object_type = self.path.object_type.as_str();
+        let mut step = match schema::OBJECT_TYPES.iter().find(|name| **name == object_type) {
+            Some(name) => Step::Sco(name),
+            None if schema::is_custom_name(object_type) => {
+                self.report(Severity::Warning, format!("custom object type '{}' cannot be checked", object_type));
+                return Some(Resolved::Unchecked);
+            }
+            None => {
+                self.report(Severity::Error, format!("unknown object type '{}'", object_type));
+                return None;
+            }
+        };
+        for component in &self.path.components {
+            step = match (step, component) {
+                (Step::Sco(object_type), PathComponent::Property(name)) if name == "extensions" => Step::Extensions(object_type),
+                (Step::Sco(object_type), PathComponent::Property(name)) => {
+                    match schema::property_type(object_type, name) {
+                        Some(property_type) => Step::Value(property_type),
+                        None => return self.unknown_property(name, object_type),
+                    }
+                }
+                (Step::Extensions(object_type), PathComponent::Property(extension)) => {
+                    match schema::extension_properties(object_type, extension) {
+                        Some(properties) => Step::Properties(properties),
+                        None if schema::is_custom_name(extension) => {
+                            self.report(Severity::Warning, format!("custom extension '{}' cannot be checked", extension));
+                            return Some(Resolved::Unchecked);
+                        }
+                        None => {
+                            self.report(Severity::Error, format!("unknown extension '{}' for object type '{}'", extension, object_type));
+                            return None;
+                        }
+                    }
+                }
+                (Step::Properties(properties), PathComponent::Property(name))
+                | (Step::Value(PropertyType::Object(properties)), PathComponent::Property(name)) => match find(properties, name) {
+                    Some(property_type) => Step::Value(property_type),
+                    None => return self.unknown_property(name, "embedded object"),
+                },
+                (Step::Value(PropertyType::Dictionary(values)), PathComponent::Property(_)) => Step::Value(*values),
+                (Step::Value(PropertyType::Any), _) => return Some(Resolved::Unchecked),
+                (Step::Value(PropertyType::Reference(types)), PathComponent::Property(name)) => {
+                    let targets: Vec<&str> = types.iter().cloned().filter(|target| schema::property_type(target, name).is_some()).collect();
+                    if types.is_empty() {
+                        return Some(Resolved::Unchecked);
+                    }
+                    match targets.first() {
+                        Some(target) => Step::Value(schema::property_type(target, name)?),
+                        None => return self.unknown_property(name, &types.join(" or ")),
+                    }
+                }
+                (Step::Value(PropertyType::List(items)), PathComponent::Index(_)) => Step::Value(*items),
+                (_, PathComponent::Index(_)) => {
+                    self.report(Severity::Error, "list index applied to a property that is not a list".to_string());
+                    return None;
+                }
+                (Step::Value(_), PathComponent::Property(name)) => {
+                    self.report(Severity::Error, format!("'{}' cannot be looked up in a property that is not an object", name));
+                    return None;
+                }
+            };
+        }
+        Some(match step {
+            Step::Value(property_type) => Resolved::Value(property_type),
+            _ => Resolved::Object,
+        })
+    }
+
+    fn unknown_property(&mut self, name: &str, owner: &str) -> Option<Resolved> {
+        if schema::is_custom_name(name) {
+            self.report(Severity::Warning, format!("custom property '{}' cannot be checked", name));
+            Some(Resolved::Unchecked)
+        } else {
+            self.report(Severity::Error, format!("'{}' is not a property of {}", name, owner));
+            None
+        }
+    }
+
+    fn check_literal(&mut self, property_type: PropertyType, literal: &Literal) {
+        let severity = match (property_type, literal) {
+            (PropertyType::Any, _)
+            | (PropertyType::String, Literal::String(_))
+            | (PropertyType::Reference(_), Literal::String(_))
+            | (PropertyType::Integer, Literal::Integer(_))
+            | (PropertyType::Float, Literal::Integer(_) | Literal::Float(_))
+            | (PropertyType::Boolean, Literal::Boolean(_))
+            | (PropertyType::Timestamp, Literal::Timestamp(_))
+            | (PropertyType::Binary, Literal::Binary(_) | Literal::Hex(_))
+            | (PropertyType::Hex, Literal::Hex(_)) => return,
+            (PropertyType::Timestamp | PropertyType::Binary | PropertyType::Hex, Literal::String(_)) => Severity::Warning,
+            _ => Severity::Error,
+        };
+        self.report(severity, format!("{} property compared with {}", describe_type(property_type), literal));
+    }
+}
+
+fn find(properties: &[Property], name: &str) -> Option<PropertyType> {
+    properties.iter().find(|(property, _)| *property == name).map(|(_, property_type)| *property_type)
+}
+
+fn describe_type(property_type: PropertyType) -> &'static str {
+    match property_type {
+        PropertyType::String => "string",
+        PropertyType::Integer => "integer",
+        PropertyType::Float => "float",
+        PropertyType::Boolean => "boolean",
+        PropertyType::Timestamp => "timestamp",
+        PropertyType::Binary => "binary",
+        PropertyType::Hex => "hex",
+        PropertyType::Reference(_) => "reference",
+        _ => "complex",
+    }
+}
@@ -0,0 +1,153 @@
+/// Translators from STIX patterns into the rule languages of the detection tools:
+/// Sigma rules (see `sigma`), Suricata and Snort rules (see `network`) and YARA rule stubs (see `yara`).
+///
+/// The languages cannot express everything a pattern does, so every translation reports the fragments of the pattern it left out.
+/// Comparisons that cannot be translated are dropped from the rule, which may then match more than the pattern,
+/// and the observations of a correlation (`AND`, `FOLLOWEDBY` or a qualifier) are translated on their own.
+pub mod network;
+pub mod sigma;
+pub mod yara;
+
+use std::error::Error;
+use std::fmt;
+use crate::core::patterns::ast::{ComparisonExpression, ObservationExpression, Pattern};
+use crate::core::patterns::equivalence::{canonical_comparison, product, MAX_TERMS};
+use crate::core::patterns::normalizer::normalize;
+use crate::core::patterns::pattern_type::PatternType;
+use crate::core::patterns::PatternError;
+use crate::core::sdos::indicator::Indicator;
+
+/// The rules written for a pattern, along with the parts of the pattern they do not express.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Translation {
+    pub rules: String,
+    pub untranslated: Vec<Untranslated>,
+}
+
+impl Translation {
+    /// Whether the rules express the whole pattern.
+    pub fn is_complete(&self) -> bool {
+        self.untranslated.is_empty()
+    }
+}
+
+/// A fragment of a pattern left out of a translation, and why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Untranslated {
+    pub fragment: String,
+    pub reason: String,
+}
+
+impl Untranslated {
+    pub(crate) fn new<F: ToString, R: ToString>(fragment: F, reason: R) -> Untranslated {
+        Untranslated { fragment: fragment.to_string(), reason: reason.to_string() }
+    }
+}
+
+impl fmt::Display for Untranslated {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.fragment, self.reason)
+    }
+}
+
+/// The description of the translated indicator, written in the metadata of the rules.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleMetadata {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+impl From<&Indicator> for RuleMetadata {
+    fn from(indicator: &Indicator) -> RuleMetadata {
+        RuleMetadata {
+            id: indicator.id.clone(),
+            title: indicator.name.clone().unwrap_or_else(|| indicator.id.clone()),
+            description: indicator.description.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslationError {
+    /// Only the indicators whose `pattern_type` is `stix` can be translated.
+    UnsupportedPatternType(String),
+    InvalidPattern(PatternError),
+}
+
+impl fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranslationError::UnsupportedPatternType(pattern_type) => write!(f, "cannot translate patterns of type '{}'", pattern_type),
+            TranslationError::InvalidPattern(error) => write!(f, "invalid pattern: {}", error),
+        }
+    }
+}
+
+impl Error for TranslationError {}
+
+/// Parses the pattern of an indicator to be translated.
+fn indicator_pattern(indicator: &Indicator) -> Result<(Pattern, RuleMetadata), TranslationError> {
+    if indicator.pattern_type != PatternType::Stix {
+        return Err(TranslationError::UnsupportedPatternType(indicator.pattern_type.to_string()));
+    }
+    let pattern = indicator.parse_pattern().map_err(TranslationError::InvalidPattern)?;
+    Ok((pattern, RuleMetadata::from(indicator)))
+}
+
+/// Splits a pattern into the alternatives a rule can detect, each one being the comparisons that a single object must satisfy,
+/// and reports the correlations between observations that are lost on the way.
+///
+/// The values are written the canonical way (e. g. the hashes in lowercase, as YARA computes them), which leaves the regular expressions
+/// and `LIKE` patterns as they are written.
+fn alternatives(pattern: &Pattern, untranslated: &mut Vec<Untranslated>) -> Vec<Vec<ComparisonExpression>> {
+    let mut alternatives = Vec::new();
+    collect_alternatives(&normalize(pattern).expression, &mut alternatives, untranslated);
+    alternatives
+}
+
+/// Splits a comparison expression into conjunctions of comparisons (or `EXISTS`), with their values written the canonical way.
+/// Nothing is returned if there would be more than `MAX_TERMS` conjunctions.
+fn conjunctions(expression: &ComparisonExpression) -> Option<Vec<Vec<ComparisonExpression>>> {
+    let conjunctions = match expression {
+        ComparisonExpression::Group(inner) => conjunctions(inner)?,
+        ComparisonExpression::Or(items) => items.iter().map(conjunctions).collect::<Option<Vec<_>>>()?.concat(),
+        ComparisonExpression::And(items) => product(items.iter().map(conjunctions).collect::<Option<_>>()?)?
+            .into_iter()
+            .map(|parts| parts.into_iter().flatten().collect())
+            .collect(),
+        ComparisonExpression::Comparison(comparison) => vec![vec![ComparisonExpression::Comparison(canonical_comparison(comparison))]],
+        atom => vec![vec![atom.clone()]],
+    };
+    if conjunctions.len() > MAX_TERMS {
+        None
+    } else {
+        Some(conjunctions)
+    }
+}
+
+fn collect_alternatives(expression: &ObservationExpression, alternatives: &mut Vec<Vec<ComparisonExpression>>, untranslated: &mut Vec<Untranslated>) {
+    let (items, reason) = match expression {
+        ObservationExpression::Observation(comparison) => {
+            match conjunctions(comparison) {
+                Some(conjunctions) => alternatives.extend(conjunctions),
+                None => untranslated.push(Untranslated::new(expression, format!("the observation has more than {} alternatives", MAX_TERMS))),
+            }
+            return;
+        }
+        ObservationExpression::Or(items) => {
+            for item in items {
+                collect_alternatives(item, alternatives, untranslated);
+            }
+            return;
+        }
+        ObservationExpression::Group(inner) => return collect_alternatives(inner, alternatives, untranslated),
+        ObservationExpression::And(items) => (items.iter().collect::<Vec<_>>(), "observations joined by AND cannot be correlated by a single rule".to_string()),
+        ObservationExpression::FollowedBy(items) => (items.iter().collect(), "observations joined by FOLLOWEDBY cannot be correlated by a single rule".to_string()),
+        ObservationExpression::Qualified(inner, qualifier) => (vec![inner.as_ref()], format!("the {} qualifier is not supported", qualifier)),
+    };
+    untranslated.push(Untranslated::new(expression, format!("{}, each observation is translated on its own", reason)));
+    for item in items {
+        collect_alternatives(item, alternatives, untranslated);
+    }
+}
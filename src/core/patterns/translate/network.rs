@@ -0,0 +1,420 @@
+use crate::core::patterns::ast::{Comparison, ComparisonExpression, ComparisonOperator, Literal, Pattern, PathComponent};
+use crate::core::patterns::equivalence::MAX_TERMS;
+use crate::core::patterns::translate::{alternatives, indicator_pattern, RuleMetadata, Translation, TranslationError, Untranslated};
+use crate::core::sdos::indicator::Indicator;
+
+/// The rule language to write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    Suricata,
+    /// Snort 3, whose sticky buffers are named differently and which has no DNS query buffer.
+    Snort,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetworkRuleOptions {
+    pub dialect: Dialect,
+    /// The `sid` of the first rule written, the next rules taking the following ones.
+    pub first_sid: u64,
+}
+
+impl Default for NetworkRuleOptions {
+    fn default() -> NetworkRuleOptions {
+        NetworkRuleOptions { dialect: Dialect::Suricata, first_sid: 1_000_000 }
+    }
+}
+
+/// Translates the pattern of an indicator into Suricata or Snort rules. See `translate_pattern`.
+pub fn translate(indicator: &Indicator, options: NetworkRuleOptions) -> Result<Translation, TranslationError> {
+    let (pattern, metadata) = indicator_pattern(indicator)?;
+    Ok(translate_pattern(&pattern, &metadata, options))
+}
+
+/// Translates a pattern into Suricata or Snort rules, one rule per line.
+///
+/// The `ipv4-addr`, `ipv6-addr`, `domain-name`, `url` and `network-traffic` object paths are translated: addresses, ports and
+/// protocols go to the rule header and DNS queries, URLs and HTTP requests to content matches over the sticky buffers.
+/// Rules cannot match one of several contents, so a set of values compared with `IN` is written as one rule per value,
+/// unless the sets of an alternative would give more than `MAX_TERMS` rules.
+pub fn translate_pattern(pattern: &Pattern, metadata: &RuleMetadata, options: NetworkRuleOptions) -> Translation {
+    let mut untranslated = Vec::new();
+    let mut rules = Vec::new();
+    let mut expanded = Vec::new();
+    for alternative in alternatives(pattern, &mut untranslated) {
+        expanded.extend(expand_sets(&alternative, &mut untranslated));
+    }
+    for alternative in expanded {
+        let mut rule = NetworkRule::default();
+        for atom in &alternative {
+            let result = match atom {
+                ComparisonExpression::Comparison(comparison) => rule.add(comparison, options.dialect),
+                _ => Err("the existence of a property cannot be checked by a rule".to_string()),
+            };
+            if let Err(reason) = result {
+                untranslated.push(Untranslated::new(atom, reason));
+            }
+        }
+        if rule.is_specific() {
+            let sid = options.first_sid + rules.len() as u64;
+            rules.push(rule.write(metadata, sid));
+        }
+    }
+    Translation { rules: rules.iter().map(|rule| format!("{}\n", rule)).collect(), untranslated }
+}
+
+/// Replaces the comparisons with a set of strings by alternatives comparing each string, except for addresses and ports.
+///
+/// When there would be more than `MAX_TERMS` alternatives, the sets are reported as untranslated and left out instead.
+fn expand_sets(alternative: &[ComparisonExpression], untranslated: &mut Vec<Untranslated>) -> Vec<Vec<ComparisonExpression>> {
+    let set_size = |atom: &ComparisonExpression| match atom {
+        ComparisonExpression::Comparison(comparison @ Comparison { negated: false, operator: ComparisonOperator::In, value: Literal::Set(items), .. })
+            if header_field(comparison).is_none() =>
+        {
+            Some(items.len())
+        }
+        _ => None,
+    };
+    let within_limit = alternative
+        .iter()
+        .filter_map(set_size)
+        .try_fold(1usize, |count, size| count.checked_mul(size).filter(|count| *count <= MAX_TERMS))
+        .is_some();
+    let mut expanded = vec![Vec::new()];
+    for atom in alternative {
+        let options = match atom {
+            ComparisonExpression::Comparison(comparison @ Comparison { value: Literal::Set(items), .. }) if set_size(atom).is_some() => {
+                if !within_limit {
+                    untranslated.push(Untranslated::new(atom, format!("the sets compared would be written as more than {} rules", MAX_TERMS)));
+                    continue;
+                }
+                items
+                    .iter()
+                    .map(|item| ComparisonExpression::Comparison(Comparison { operator: ComparisonOperator::Equal, value: item.clone(), ..comparison.clone() }))
+                    .collect()
+            }
+            atom => vec![atom.clone()],
+        };
+        expanded = expanded
+            .iter()
+            .flat_map(|atoms| {
+                options.iter().map(move |option| {
+                    let mut atoms = atoms.clone();
+                    atoms.push(option.clone());
+                    atoms
+                })
+            })
+            .collect();
+    }
+    expanded
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum HeaderField {
+    Address,
+    Source,
+    Destination,
+    SourcePort,
+    DestinationPort,
+    Protocol,
+}
+
+/// The sticky buffers holding the contents matched by the rules.
+#[derive(Clone, Copy)]
+enum Buffer {
+    DnsQuery,
+    HttpUri,
+    HttpHost,
+    HttpMethod,
+}
+
+impl Buffer {
+    fn keyword(self, dialect: Dialect) -> Option<&'static str> {
+        match (self, dialect) {
+            (Buffer::DnsQuery, Dialect::Suricata) => Some("dns.query"),
+            (Buffer::DnsQuery, Dialect::Snort) => None,
+            (Buffer::HttpUri, Dialect::Suricata) => Some("http.uri"),
+            (Buffer::HttpUri, Dialect::Snort) => Some("http_uri"),
+            (Buffer::HttpHost, Dialect::Suricata) => Some("http.host"),
+            (Buffer::HttpHost, Dialect::Snort) => Some("http_header:field host"),
+            (Buffer::HttpMethod, Dialect::Suricata) => Some("http.method"),
+            (Buffer::HttpMethod, Dialect::Snort) => Some("http_method"),
+        }
+    }
+
+    fn protocol(self) -> &'static str {
+        match self {
+            Buffer::DnsQuery => "dns",
+            _ => "http",
+        }
+    }
+}
+
+fn header_field(comparison: &Comparison) -> Option<HeaderField> {
+    let properties: Vec<&str> = comparison
+        .path
+        .components
+        .iter()
+        .map(|component| match component {
+            PathComponent::Property(name) => name.as_str(),
+            PathComponent::Index(_) => "[]",
+        })
+        .collect();
+    match (comparison.path.object_type.as_str(), properties.as_slice()) {
+        ("ipv4-addr" | "ipv6-addr", ["value"]) => Some(HeaderField::Address),
+        ("network-traffic", ["src_ref", "value"]) => Some(HeaderField::Source),
+        ("network-traffic", ["dst_ref", "value"]) => Some(HeaderField::Destination),
+        ("network-traffic", ["src_port"]) => Some(HeaderField::SourcePort),
+        ("network-traffic", ["dst_port"]) => Some(HeaderField::DestinationPort),
+        ("network-traffic", ["protocols", "[]"]) => Some(HeaderField::Protocol),
+        _ => None,
+    }
+}
+
+fn buffer(comparison: &Comparison) -> Option<Buffer> {
+    let properties: Vec<&str> = comparison
+        .path
+        .components
+        .iter()
+        .filter_map(|component| match component {
+            PathComponent::Property(name) => Some(name.as_str()),
+            PathComponent::Index(_) => None,
+        })
+        .collect();
+    match (comparison.path.object_type.as_str(), properties.as_slice()) {
+        ("domain-name", ["value"]) => Some(Buffer::DnsQuery),
+        ("network-traffic", ["extensions", "http-request-ext", "request_method"]) => Some(Buffer::HttpMethod),
+        ("network-traffic", ["extensions", "http-request-ext", "request_value"]) => Some(Buffer::HttpUri),
+        ("network-traffic", ["extensions", "http-request-ext", "request_header", host]) if host.eq_ignore_ascii_case("host") => Some(Buffer::HttpHost),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct NetworkRule {
+    protocol: Option<&'static str>,
+    address: Option<String>,
+    source: Option<String>,
+    destination: Option<String>,
+    source_port: Option<String>,
+    destination_port: Option<String>,
+    options: Vec<String>,
+}
+
+impl NetworkRule {
+    /// Whether the rule checks more than the protocol, as a rule matching every packet would be useless.
+    fn is_specific(&self) -> bool {
+        self.address.is_some()
+            || self.source.is_some()
+            || self.destination.is_some()
+            || self.source_port.is_some()
+            || self.destination_port.is_some()
+            || !self.options.is_empty()
+    }
+
+    fn add(&mut self, comparison: &Comparison, dialect: Dialect) -> Result<(), String> {
+        if let Some(field) = header_field(comparison) {
+            return self.add_header(field, comparison);
+        }
+        if comparison.path.object_type == "url" && comparison.path.components == [PathComponent::Property("value".to_string())] {
+            return self.add_url(comparison, dialect);
+        }
+        match buffer(comparison) {
+            Some(buffer) => self.add_content(buffer, comparison, dialect),
+            None => Err(format!("{} cannot be inspected by a network rule", comparison.path)),
+        }
+    }
+
+    fn add_header(&mut self, field: HeaderField, comparison: &Comparison) -> Result<(), String> {
+        let unsupported = || format!("{} is not supported on {}", comparison.operator, comparison.path);
+        if field == HeaderField::Protocol {
+            return match (&comparison.value, comparison.operator, comparison.negated) {
+                (Literal::String(protocol), ComparisonOperator::Equal, false) => self.set_protocol(protocol),
+                _ => Err(unsupported()),
+            };
+        }
+        let value = if matches!(field, HeaderField::SourcePort | HeaderField::DestinationPort) {
+            port(comparison).ok_or_else(unsupported)?
+        } else {
+            address(comparison).ok_or_else(unsupported)?
+        };
+        let value = if comparison.negated { format!("!{}", value) } else { value };
+        let slot = match field {
+            HeaderField::Address if self.source.is_none() && self.destination.is_none() => &mut self.address,
+            HeaderField::Source if self.address.is_none() => &mut self.source,
+            HeaderField::Destination if self.address.is_none() => &mut self.destination,
+            HeaderField::SourcePort => &mut self.source_port,
+            HeaderField::DestinationPort => &mut self.destination_port,
+            _ => return Err("an address of either direction cannot be combined with the source or destination addresses".to_string()),
+        };
+        if slot.is_some() {
+            return Err(format!("{} is already constrained by another comparison", comparison.path));
+        }
+        *slot = Some(value);
+        Ok(())
+    }
+
+    fn set_protocol(&mut self, protocol: &str) -> Result<(), String> {
+        const TRANSPORTS: [&str; 3] = ["tcp", "udp", "icmp"];
+        const APPLICATIONS: [&str; 6] = ["http", "dns", "tls", "ssh", "ftp", "smtp"];
+        let protocol = protocol.to_lowercase();
+        let known = TRANSPORTS.iter().chain(APPLICATIONS.iter()).find(|known| **known == protocol);
+        match (known, self.protocol) {
+            (None, _) if protocol == "ipv4" || protocol == "ipv6" || protocol == "ip" => Ok(()),
+            (None, _) => Err(format!("protocol '{}' is not known to network rules", protocol)),
+            (Some(known), None) => {
+                self.protocol = Some(known);
+                Ok(())
+            }
+            (Some(known), Some(current)) if TRANSPORTS.contains(known) && APPLICATIONS.contains(&current) => Ok(()),
+            (Some(known), Some(current)) if APPLICATIONS.contains(known) && TRANSPORTS.contains(&current) => {
+                self.protocol = Some(known);
+                Ok(())
+            }
+            (Some(known), Some(current)) if *known == current => Ok(()),
+            (Some(known), Some(current)) => Err(format!("protocol '{}' conflicts with '{}'", known, current)),
+        }
+    }
+
+    fn add_url(&mut self, comparison: &Comparison, dialect: Dialect) -> Result<(), String> {
+        let url = match (&comparison.value, comparison.operator, comparison.negated) {
+            (Literal::String(url), ComparisonOperator::Equal, false) => url,
+            _ => return Err(format!("{} is not supported on {}", comparison.operator, comparison.path)),
+        };
+        let rest = match url.split_once("://") {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case("http") => rest,
+            Some((scheme, _)) => return Err(format!("{} URLs cannot be inspected by a network rule", scheme)),
+            None => url.as_str(),
+        };
+        let (host, uri) = match rest.find('/') {
+            Some(slash) => (&rest[..slash], &rest[slash..]),
+            None => (rest, "/"),
+        };
+        self.add_exact_content(Buffer::HttpHost, host, true, dialect)?;
+        self.add_exact_content(Buffer::HttpUri, uri, false, dialect)
+    }
+
+    fn add_content(&mut self, buffer: Buffer, comparison: &Comparison, dialect: Dialect) -> Result<(), String> {
+        let unsupported = || format!("{} is not supported on {}", comparison.operator, comparison.path);
+        let text = match &comparison.value {
+            Literal::String(text) => text,
+            _ => return Err(unsupported()),
+        };
+        let case_insensitive = matches!(buffer, Buffer::DnsQuery | Buffer::HttpHost);
+        match (comparison.operator, comparison.negated) {
+            (ComparisonOperator::Equal, false) => self.add_exact_content(buffer, text, case_insensitive, dialect),
+            (ComparisonOperator::Like, false) => self.add_pcre(buffer, &format!("^{}$", like_to_regex(text)), case_insensitive, dialect),
+            (ComparisonOperator::Matches, false) => self.add_pcre(buffer, text, false, dialect),
+            _ => Err(unsupported()),
+        }
+    }
+
+    fn sticky_buffer(&mut self, buffer: Buffer, dialect: Dialect) -> Result<(), String> {
+        let keyword = buffer.keyword(dialect).ok_or_else(|| format!("{:?} rules cannot inspect the {} traffic", dialect, buffer.protocol()))?;
+        self.set_protocol(buffer.protocol())?;
+        self.options.push(keyword.to_string());
+        Ok(())
+    }
+
+    fn add_exact_content(&mut self, buffer: Buffer, text: &str, case_insensitive: bool, dialect: Dialect) -> Result<(), String> {
+        self.sticky_buffer(buffer, dialect)?;
+        self.options.push(format!("content:\"{}\"", escape_content(text)));
+        if case_insensitive {
+            self.options.push("nocase".to_string());
+        }
+        let length = match dialect {
+            Dialect::Suricata => "bsize",
+            Dialect::Snort => "bufferlen",
+        };
+        self.options.push(format!("{}:{}", length, text.len()));
+        Ok(())
+    }
+
+    fn add_pcre(&mut self, buffer: Buffer, regex: &str, case_insensitive: bool, dialect: Dialect) -> Result<(), String> {
+        self.sticky_buffer(buffer, dialect)?;
+        let flags = if case_insensitive { "i" } else { "" };
+        self.options.push(format!("pcre:\"/{}/{}\"", escape_pcre(regex), flags));
+        Ok(())
+    }
+
+    fn write(&self, metadata: &RuleMetadata, sid: u64) -> String {
+        let any = "any".to_string();
+        let (source, direction, destination) = match &self.address {
+            Some(address) => (&any, "<>", address),
+            None => (self.source.as_ref().unwrap_or(&any), "->", self.destination.as_ref().unwrap_or(&any)),
+        };
+        let mut options = vec![format!("msg:\"{}\"", escape_content(&metadata.title))];
+        options.extend(self.options.iter().cloned());
+        options.push(format!("metadata:stix_id {}", metadata.id));
+        options.push(format!("sid:{}", sid));
+        options.push("rev:1".to_string());
+        format!(
+            "alert {} {} {} {} {} {} ({};)",
+            self.protocol.unwrap_or("ip"),
+            source,
+            self.source_port.as_ref().unwrap_or(&any),
+            direction,
+            destination,
+            self.destination_port.as_ref().unwrap_or(&any),
+            options.join("; ")
+        )
+    }
+}
+
+/// The address or the list of addresses compared, as written in a rule header.
+fn address(comparison: &Comparison) -> Option<String> {
+    let text = |literal: &Literal| match literal {
+        Literal::String(text) => Some(text.clone()),
+        _ => None,
+    };
+    match (comparison.operator, &comparison.value) {
+        (ComparisonOperator::Equal | ComparisonOperator::IsSubset, literal) => text(literal),
+        (ComparisonOperator::In, Literal::Set(items)) => Some(format!("[{}]", items.iter().map(text).collect::<Option<Vec<_>>>()?.join(","))),
+        _ => None,
+    }
+}
+
+/// The port or the range of ports compared, as written in a rule header.
+fn port(comparison: &Comparison) -> Option<String> {
+    let number = |literal: &Literal| match literal {
+        Literal::Integer(port) if (0..=65535).contains(port) => Some(*port),
+        _ => None,
+    };
+    Some(match (comparison.operator, &comparison.value) {
+        (ComparisonOperator::Equal, literal) => number(literal)?.to_string(),
+        (ComparisonOperator::In, Literal::Set(items)) => {
+            format!("[{}]", items.iter().map(|item| number(item).map(|port| port.to_string())).collect::<Option<Vec<_>>>()?.join(","))
+        }
+        // No port is above 65535 or below 0, and such ranges cannot be written.
+        (ComparisonOperator::GreaterThan, literal) => format!("{}:", number(literal).filter(|port| *port < 65535)? + 1),
+        (ComparisonOperator::GreaterOrEqual, literal) => format!("{}:", number(literal)?),
+        (ComparisonOperator::LessThan, literal) => format!(":{}", number(literal).filter(|port| *port > 0)? - 1),
+        (ComparisonOperator::LessOrEqual, literal) => format!(":{}", number(literal)?),
+        _ => return None,
+    })
+}
+
+/// Writes the characters that cannot appear in a quoted content as hexadecimal bytes.
+fn escape_content(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '"' | ';' | '\\' | '|' => format!("|{:02X}|", c as u8),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn escape_pcre(regex: &str) -> String {
+    regex.replace('/', "\\/").replace('"', "\\\"").replace(';', "\\;")
+}
+
+/// Writes a `LIKE` pattern as a regular expression.
+fn like_to_regex(pattern: &str) -> String {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '%' => ".*".to_string(),
+            '_' => ".".to_string(),
+            c => regex::escape(&c.to_string()),
+        })
+        .collect()
+}
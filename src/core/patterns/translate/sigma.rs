@@ -0,0 +1,333 @@
+use serde_yaml::{Mapping, Value};
+use uuid::Uuid;
+use crate::core::patterns::ast::{Comparison, ComparisonExpression, ComparisonOperator, ListIndex, Literal, ObjectPath, PathComponent, Pattern};
+use crate::core::patterns::translate::{alternatives, indicator_pattern, RuleMetadata, Translation, TranslationError, Untranslated};
+use crate::core::sdos::indicator::Indicator;
+
+/// Translates the pattern of an indicator into Sigma rules. See `translate_pattern`.
+pub fn translate(indicator: &Indicator) -> Result<Translation, TranslationError> {
+    let (pattern, metadata) = indicator_pattern(indicator)?;
+    Ok(translate_pattern(&pattern, &metadata))
+}
+
+/// Translates a pattern into Sigma rules over the Windows process creation, file and registry events, with the Sysmon field names.
+///
+/// The `process`, `file` and `windows-registry-key` object paths are translated. A Sigma rule reads a single log source,
+/// so a pattern about several kinds of objects is written as a collection of rules, one YAML document each.
+pub fn translate_pattern(pattern: &Pattern, metadata: &RuleMetadata) -> Translation {
+    let mut untranslated = Vec::new();
+    let mut rules: Vec<Rule> = Vec::new();
+    for alternative in alternatives(pattern, &mut untranslated) {
+        let mut log_source = None;
+        let mut criteria = Vec::new();
+        for atom in &alternative {
+            match criterion(atom) {
+                Ok((source, criterion)) if log_source.is_none() || log_source == Some(source) => {
+                    log_source = Some(source);
+                    criteria.push(criterion);
+                }
+                Ok((_, _)) => untranslated.push(Untranslated::new(atom, "the comparison reads another log source than the rest of the observation")),
+                Err(reason) => untranslated.push(Untranslated::new(atom, reason)),
+            }
+        }
+        if let Some(log_source) = log_source {
+            let position = match rules.iter().position(|rule| rule.log_source == log_source) {
+                Some(position) => position,
+                None => {
+                    rules.push(Rule { log_source, detection: Mapping::new(), conditions: Vec::new(), selections: 0, filters: 0 });
+                    rules.len() - 1
+                }
+            };
+            rules[position].add(criteria);
+        }
+    }
+    let count = rules.len();
+    let documents: Vec<String> = rules
+        .into_iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            let title = if count > 1 { format!("{} ({})", metadata.title, rule.log_source.category) } else { metadata.title.clone() };
+            // Sigma identifiers are UUIDs: the one of the indicator is kept for its first rule.
+            let id = match metadata.id.rsplit("--").next().map(Uuid::parse_str) {
+                Some(Ok(uuid)) if i == 0 => uuid,
+                _ => Uuid::new_v4(),
+            };
+            let mut document = Mapping::new();
+            document.insert("title".into(), title.into());
+            document.insert("id".into(), id.to_string().into());
+            document.insert("status".into(), "experimental".into());
+            if let Some(description) = &metadata.description {
+                document.insert("description".into(), description.clone().into());
+            }
+            document.insert("references".into(), Value::Sequence(vec![metadata.id.clone().into()]));
+            let mut log_source = Mapping::new();
+            log_source.insert("category".into(), rule.log_source.category.into());
+            log_source.insert("product".into(), rule.log_source.product.into());
+            document.insert("logsource".into(), Value::Mapping(log_source));
+            let mut detection = rule.detection;
+            let condition = if rule.conditions.len() == 1 {
+                rule.conditions[0].clone()
+            } else {
+                rule.conditions.iter().map(|condition| format!("({})", condition)).collect::<Vec<_>>().join(" or ")
+            };
+            detection.insert("condition".into(), condition.into());
+            document.insert("detection".into(), Value::Mapping(detection));
+            document.insert("level".into(), "medium".into());
+            serde_yaml::to_string(&document).expect("YAML mappings are always serializable")
+        })
+        .collect();
+    Translation { rules: documents.join("---\n"), untranslated }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LogSource {
+    category: &'static str,
+    product: &'static str,
+}
+
+const PROCESS_CREATION: LogSource = LogSource { category: "process_creation", product: "windows" };
+const FILE_EVENT: LogSource = LogSource { category: "file_event", product: "windows" };
+const REGISTRY_EVENT: LogSource = LogSource { category: "registry_event", product: "windows" };
+
+/// How the value of a property is found in an event field.
+#[derive(Clone, Copy)]
+enum FieldKind {
+    /// The field holds the value itself.
+    Exact,
+    /// The field holds a path ending with the value.
+    Basename,
+    /// The field holds a path inside the directory.
+    Directory,
+    /// The field holds the hashes of a file, as `ALGORITHM=value` items.
+    Hash(&'static str),
+    /// The field holds a registry path, with the abbreviated hive names.
+    RegistryKey,
+}
+
+/// A condition on an event field, e. g. `Image|endswith: '\cmd.exe'`, negated if it is a filter.
+struct Criterion {
+    key: String,
+    value: Value,
+    negated: bool,
+}
+
+struct Rule {
+    log_source: LogSource,
+    detection: Mapping,
+    conditions: Vec<String>,
+    selections: usize,
+    filters: usize,
+}
+
+impl Rule {
+    /// Adds the criteria of an alternative: the positive ones are gathered in selections, each negated one is a filter.
+    fn add(&mut self, criteria: Vec<Criterion>) {
+        let mut selections: Vec<Mapping> = Vec::new();
+        let mut condition = Vec::new();
+        for criterion in criteria {
+            if criterion.negated {
+                self.filters += 1;
+                let name = format!("filter{}", self.filters);
+                let mut filter = Mapping::new();
+                filter.insert(criterion.key.into(), criterion.value);
+                self.detection.insert(name.clone().into(), Value::Mapping(filter));
+                condition.push(format!("not {}", name));
+                continue;
+            }
+            let key: Value = criterion.key.into();
+            match selections.iter_mut().find(|selection| !selection.contains_key(&key)) {
+                Some(selection) => {
+                    selection.insert(key, criterion.value);
+                }
+                None => {
+                    let mut selection = Mapping::new();
+                    selection.insert(key, criterion.value);
+                    selections.push(selection);
+                }
+            }
+        }
+        let mut names = Vec::new();
+        for selection in selections {
+            self.selections += 1;
+            let name = format!("selection{}", self.selections);
+            self.detection.insert(name.clone().into(), Value::Mapping(selection));
+            names.push(name);
+        }
+        names.extend(condition);
+        self.conditions.push(names.join(" and "));
+    }
+}
+
+fn criterion(atom: &ComparisonExpression) -> Result<(LogSource, Criterion), String> {
+    let (path, comparison) = match atom {
+        ComparisonExpression::Comparison(comparison) => (&comparison.path, Some(comparison)),
+        ComparisonExpression::Exists(path) => (path, None),
+        _ => unreachable!("alternatives are made of comparisons"),
+    };
+    let (log_source, field, kind) = field(path).ok_or_else(|| format!("{} has no field in the Sigma log sources", path))?;
+    let comparison = match comparison {
+        Some(comparison) => comparison,
+        None => return match kind {
+            FieldKind::Exact => Ok((log_source, Criterion { key: format!("{}|exists", field), value: true.into(), negated: false })),
+            _ => Err(format!("the existence of {} cannot be checked", path)),
+        },
+    };
+    let (modifier, value) = criterion_value(comparison, kind)?;
+    let negated = comparison.negated != (comparison.operator == ComparisonOperator::NotEqual);
+    Ok((log_source, Criterion { key: format!("{}{}", field, modifier), value, negated }))
+}
+
+/// The field modifier and the value that express a comparison.
+fn criterion_value(comparison: &Comparison, kind: FieldKind) -> Result<(&'static str, Value), String> {
+    use ComparisonOperator::*;
+    let unsupported = || format!("{} is not supported on {}", comparison.operator, comparison.path);
+    match comparison.operator {
+        Equal | NotEqual | In => {
+            let modifier = match kind {
+                FieldKind::Exact => "",
+                FieldKind::Basename => "|endswith",
+                FieldKind::Directory | FieldKind::RegistryKey => "|startswith",
+                FieldKind::Hash(_) => "|contains",
+            };
+            let convert = |literal: &Literal| -> Result<Value, String> {
+                let text = match (literal, kind) {
+                    (Literal::Integer(value), FieldKind::Exact) => return Ok((*value).into()),
+                    (Literal::Float(value), FieldKind::Exact) => return Ok((*value).into()),
+                    (Literal::Boolean(value), FieldKind::Exact) => return Ok((*value).into()),
+                    (Literal::String(text) | Literal::Hex(text) | Literal::Binary(text), _) => escape_wildcards(text),
+                    _ => return Err(format!("{} cannot be compared with {}", comparison.path, literal)),
+                };
+                Ok(match kind {
+                    FieldKind::Exact => text,
+                    FieldKind::Basename => format!("\\{}", text),
+                    FieldKind::Directory => format!("{}\\", text.trim_end_matches('\\')),
+                    FieldKind::Hash(algorithm) => format!("{}={}", algorithm, text),
+                    FieldKind::RegistryKey => abbreviate_hive(&text),
+                }
+                .into())
+            };
+            let value = match &comparison.value {
+                Literal::Set(items) => Value::Sequence(items.iter().map(convert).collect::<Result<_, _>>()?),
+                literal => convert(literal)?,
+            };
+            Ok((modifier, value))
+        }
+        Like => {
+            let wildcards = match &comparison.value {
+                Literal::String(text) => like_to_wildcards(text),
+                _ => return Err(unsupported()),
+            };
+            let value = match kind {
+                FieldKind::Exact => wildcards,
+                FieldKind::Basename => format!("*\\{}", wildcards),
+                FieldKind::Directory => format!("{}\\*", wildcards.trim_end_matches('\\')),
+                FieldKind::RegistryKey => abbreviate_hive(&wildcards),
+                FieldKind::Hash(_) => return Err(unsupported()),
+            };
+            Ok(("", value.into()))
+        }
+        Matches => match (&comparison.value, kind) {
+            (Literal::String(regex), FieldKind::Exact) => Ok(("|re", regex.clone().into())),
+            _ => Err(unsupported()),
+        },
+        GreaterThan | GreaterOrEqual | LessThan | LessOrEqual => {
+            let modifier = match comparison.operator {
+                GreaterThan => "|gt",
+                GreaterOrEqual => "|gte",
+                LessThan => "|lt",
+                _ => "|lte",
+            };
+            match (&comparison.value, kind) {
+                (Literal::Integer(value), FieldKind::Exact) => Ok((modifier, (*value).into())),
+                (Literal::Float(value), FieldKind::Exact) => Ok((modifier, (*value).into())),
+                _ => Err(unsupported()),
+            }
+        }
+        IsSubset | IsSuperset => Err(unsupported()),
+    }
+}
+
+/// The log source and the Sysmon field where the value of an object path is found.
+fn field(path: &ObjectPath) -> Option<(LogSource, &'static str, FieldKind)> {
+    let properties = property_path(path);
+    let hash = |prefix: &str| {
+        properties.strip_prefix(prefix).and_then(|algorithm| match algorithm {
+            "MD5" => Some(FieldKind::Hash("MD5")),
+            "SHA-1" => Some(FieldKind::Hash("SHA1")),
+            "SHA-256" => Some(FieldKind::Hash("SHA256")),
+            _ => None,
+        })
+    };
+    let found = match (path.object_type.as_str(), properties.as_str()) {
+        ("process", "command_line") => (PROCESS_CREATION, "CommandLine", FieldKind::Exact),
+        ("process", "name" | "image_ref.name") => (PROCESS_CREATION, "Image", FieldKind::Basename),
+        ("process", "image_ref.parent_directory_ref.path") => (PROCESS_CREATION, "Image", FieldKind::Directory),
+        ("process", "pid") => (PROCESS_CREATION, "ProcessId", FieldKind::Exact),
+        ("process", "cwd") => (PROCESS_CREATION, "CurrentDirectory", FieldKind::Exact),
+        ("process", "creator_user_ref.user_id" | "creator_user_ref.account_login") => (PROCESS_CREATION, "User", FieldKind::Exact),
+        ("process", "extensions.windows-process-ext.integrity_level") => (PROCESS_CREATION, "IntegrityLevel", FieldKind::Exact),
+        ("process", "parent_ref.command_line") => (PROCESS_CREATION, "ParentCommandLine", FieldKind::Exact),
+        ("process", "parent_ref.name" | "parent_ref.image_ref.name") => (PROCESS_CREATION, "ParentImage", FieldKind::Basename),
+        ("process", "parent_ref.pid") => (PROCESS_CREATION, "ParentProcessId", FieldKind::Exact),
+        ("process", _) => (PROCESS_CREATION, "Hashes", hash("image_ref.hashes.")?),
+        ("file", "name") => (FILE_EVENT, "TargetFilename", FieldKind::Basename),
+        ("file", "parent_directory_ref.path") => (FILE_EVENT, "TargetFilename", FieldKind::Directory),
+        ("windows-registry-key", "key") => (REGISTRY_EVENT, "TargetObject", FieldKind::RegistryKey),
+        ("windows-registry-key", "values[*].name") => (REGISTRY_EVENT, "TargetObject", FieldKind::Basename),
+        ("windows-registry-key", "values[*].data") => (REGISTRY_EVENT, "Details", FieldKind::Exact),
+        _ => return None,
+    };
+    Some(found)
+}
+
+/// The components of an object path written as dotted property names, e. g. `values[*].name`.
+fn property_path(path: &ObjectPath) -> String {
+    let mut text = String::new();
+    for component in &path.components {
+        match component {
+            PathComponent::Property(name) => {
+                if !text.is_empty() {
+                    text.push('.');
+                }
+                text.push_str(name);
+            }
+            PathComponent::Index(ListIndex::Any) => text.push_str("[*]"),
+            PathComponent::Index(ListIndex::Position(position)) => text.push_str(&format!("[{}]", position)),
+        }
+    }
+    text
+}
+
+/// Escapes the characters that Sigma would read as wildcards.
+fn escape_wildcards(text: &str) -> String {
+    text.replace('*', "\\*").replace('?', "\\?")
+}
+
+/// Writes a `LIKE` pattern with the Sigma wildcards.
+fn like_to_wildcards(pattern: &str) -> String {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '%' => "*".to_string(),
+            '_' => "?".to_string(),
+            '*' | '?' => format!("\\{}", c),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Writes the hive of a registry key the way Sysmon does, e. g. `HKEY_LOCAL_MACHINE\...` as `HKLM\...`.
+fn abbreviate_hive(key: &str) -> String {
+    const HIVES: [(&str, &str); 4] = [
+        ("HKEY_LOCAL_MACHINE", "HKLM"),
+        ("HKEY_CURRENT_USER", "HKCU"),
+        ("HKEY_CLASSES_ROOT", "HKCR"),
+        ("HKEY_USERS", "HKU"),
+    ];
+    for (hive, abbreviation) in HIVES.iter() {
+        if let Some(prefix) = key.get(..hive.len()).filter(|prefix| prefix.eq_ignore_ascii_case(hive)) {
+            return format!("{}{}", abbreviation, &key[prefix.len()..]);
+        }
+    }
+    key.to_string()
+}
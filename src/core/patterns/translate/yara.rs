@@ -0,0 +1,178 @@
+use crate::core::patterns::ast::{Comparison, ComparisonExpression, ComparisonOperator, Literal, PathComponent, Pattern};
+use crate::core::patterns::translate::{alternatives, indicator_pattern, RuleMetadata, Translation, TranslationError, Untranslated};
+use crate::core::sdos::indicator::Indicator;
+
+/// Translates the pattern of an indicator into a YARA rule stub. See `translate_pattern`.
+pub fn translate(indicator: &Indicator) -> Result<Translation, TranslationError> {
+    let (pattern, metadata) = indicator_pattern(indicator)?;
+    Ok(translate_pattern(&pattern, &metadata))
+}
+
+/// Translates a pattern into a YARA rule stub, to be completed with the strings describing the content of the file.
+///
+/// The `file` hashes (MD5, SHA-1 and SHA-256, through the `hash` module), size, magic number and name are translated.
+/// YARA does not know the name of the file scanned, so the name is compared with the `filename` external variable,
+/// which must be defined when the rule is compiled.
+pub fn translate_pattern(pattern: &Pattern, metadata: &RuleMetadata) -> Translation {
+    let mut untranslated = Vec::new();
+    let mut stub = Stub::default();
+    let mut conditions = Vec::new();
+    for alternative in alternatives(pattern, &mut untranslated) {
+        let mut terms = Vec::new();
+        for atom in &alternative {
+            let result = match atom {
+                ComparisonExpression::Comparison(comparison) => stub.condition(comparison),
+                _ => Err("the existence of a property cannot be checked by YARA".to_string()),
+            };
+            match result {
+                Ok(term) => terms.push(term),
+                Err(reason) => untranslated.push(Untranslated::new(atom, reason)),
+            }
+        }
+        if !terms.is_empty() {
+            conditions.push(terms.join(" and "));
+        }
+    }
+    if conditions.is_empty() {
+        return Translation { rules: String::new(), untranslated };
+    }
+    let condition = if conditions.len() == 1 {
+        conditions.remove(0)
+    } else {
+        conditions.iter().map(|condition| format!("({})", condition)).collect::<Vec<_>>().join(" or\n        ")
+    };
+    let mut rule = String::new();
+    if stub.uses_hash {
+        rule.push_str("import \"hash\"\n\n");
+    }
+    if stub.uses_filename {
+        rule.push_str("// The filename external variable must be defined when compiling this rule.\n");
+    }
+    rule.push_str(&format!("rule {} {{\n    meta:\n", identifier(&metadata.title)));
+    if let Some(description) = &metadata.description {
+        rule.push_str(&format!("        description = \"{}\"\n", escape(description)));
+    }
+    rule.push_str(&format!("        stix_id = \"{}\"\n", escape(&metadata.id)));
+    if !stub.strings.is_empty() {
+        rule.push_str("    strings:\n");
+        for string in &stub.strings {
+            rule.push_str(&format!("        {}\n", string));
+        }
+    }
+    rule.push_str(&format!("    condition:\n        {}\n}}\n", condition));
+    Translation { rules: rule, untranslated }
+}
+
+#[derive(Default)]
+struct Stub {
+    strings: Vec<String>,
+    uses_hash: bool,
+    uses_filename: bool,
+}
+
+impl Stub {
+    /// The YARA condition expressing a comparison.
+    fn condition(&mut self, comparison: &Comparison) -> Result<String, String> {
+        let unsupported = || format!("{} is not supported on {}", comparison.operator, comparison.path);
+        if comparison.path.object_type != "file" {
+            return Err(format!("{} cannot be checked by YARA, which only scans files", comparison.path));
+        }
+        let properties: Vec<&str> = comparison
+            .path
+            .components
+            .iter()
+            .map(|component| match component {
+                PathComponent::Property(name) => name.as_str(),
+                PathComponent::Index(_) => "[]",
+            })
+            .collect();
+        let (subject, quoted) = match properties.as_slice() {
+            ["hashes", algorithm] => {
+                let function = match *algorithm {
+                    "MD5" => "md5",
+                    "SHA-1" => "sha1",
+                    "SHA-256" => "sha256",
+                    _ => return Err(format!("the {} hash cannot be computed by YARA", algorithm)),
+                };
+                self.uses_hash = true;
+                (format!("hash.{}(0, filesize)", function), true)
+            }
+            ["name"] => {
+                self.uses_filename = true;
+                ("filename".to_string(), true)
+            }
+            ["size"] => ("filesize".to_string(), false),
+            ["magic_number_hex"] => return self.magic_number(comparison).ok_or_else(unsupported),
+            _ => return Err(format!("{} cannot be checked by YARA", comparison.path)),
+        };
+        let value = |literal: &Literal| match (literal, quoted) {
+            (Literal::String(text) | Literal::Hex(text), true) => Some(format!("\"{}\"", escape(text))),
+            (Literal::Integer(value), false) => Some(value.to_string()),
+            _ => None,
+        };
+        let condition = match (comparison.operator, &comparison.value) {
+            (ComparisonOperator::In, Literal::Set(items)) => {
+                let alternatives = items
+                    .iter()
+                    .map(|item| value(item).map(|value| format!("{} == {}", subject, value)))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(unsupported)?;
+                format!("({})", alternatives.join(" or "))
+            }
+            (ComparisonOperator::Like, Literal::String(pattern)) if quoted => format!("{} matches /^{}$/", subject, like_to_regex(pattern)),
+            (ComparisonOperator::Matches, Literal::String(regex)) if quoted => format!("{} matches /{}/", subject, regex.replace('/', "\\/")),
+            (operator, literal) => {
+                let operator = match operator {
+                    ComparisonOperator::Equal => "==",
+                    ComparisonOperator::NotEqual => "!=",
+                    ComparisonOperator::GreaterThan if !quoted => ">",
+                    ComparisonOperator::GreaterOrEqual if !quoted => ">=",
+                    ComparisonOperator::LessThan if !quoted => "<",
+                    ComparisonOperator::LessOrEqual if !quoted => "<=",
+                    _ => return Err(unsupported()),
+                };
+                format!("{} {} {}", subject, operator, value(literal).ok_or_else(unsupported)?)
+            }
+        };
+        Ok(if comparison.negated { format!("not {}", condition) } else { condition })
+    }
+
+    /// Writes the magic number as a hex string that must be found at the start of the file.
+    fn magic_number(&mut self, comparison: &Comparison) -> Option<String> {
+        let hex = match (&comparison.operator, &comparison.value) {
+            (ComparisonOperator::Equal, Literal::Hex(hex) | Literal::String(hex)) if hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit()) => hex,
+            _ => return None,
+        };
+        let bytes: Vec<String> = hex.as_bytes().chunks(2).map(|pair| String::from_utf8_lossy(pair).to_uppercase()).collect();
+        let name = format!("$magic{}", self.strings.len() + 1);
+        self.strings.push(format!("{} = {{ {} }}", name, bytes.join(" ")));
+        let condition = format!("{} at 0", name);
+        Some(if comparison.negated { format!("not {}", condition) } else { condition })
+    }
+}
+
+/// Turns the title of the indicator into a rule identifier.
+fn identifier(title: &str) -> String {
+    let identifier: String = title.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).take(128).collect();
+    match identifier.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => identifier,
+        _ => format!("stix_{}", identifier),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Writes a `LIKE` pattern as a regular expression.
+fn like_to_regex(pattern: &str) -> String {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '%' => ".*".to_string(),
+            '_' => ".".to_string(),
+            '/' => "\\/".to_string(),
+            c => regex::escape(&c.to_string()),
+        })
+        .collect()
+}
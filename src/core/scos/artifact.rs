@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::Validate;
+use crate::core::STIXObject;
+use crate::core::types::GranularMarking;
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct Artifact {
+    // Required common properties
+    pub id: String,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defanged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<HashMap<String, Value>>,
+    // Specific properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_bin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashes: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption_algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decryption_key: Option<String>
+}
+
+#[typetag::serde(name = "artifact")]
+impl STIXObject for Artifact {}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::Validate;
+use crate::core::STIXObject;
+use crate::core::types::GranularMarking;
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct AutonomousSystem {
+    // Required common properties
+    pub id: String,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defanged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<HashMap<String, Value>>,
+    // Specific properties
+    pub number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rir: Option<String>
+}
+
+#[typetag::serde(name = "autonomous-system")]
+impl STIXObject for AutonomousSystem {}
\ No newline at end of file
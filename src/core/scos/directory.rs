@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::Validate;
+use crate::core::STIXObject;
+use crate::core::types::GranularMarking;
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct Directory {
+    // Required common properties
+    pub id: String,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defanged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<HashMap<String, Value>>,
+    // Specific properties
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_enc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctime: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub atime: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains_refs: Option<Vec<String>>
+}
+
+#[typetag::serde(name = "directory")]
+impl STIXObject for Directory {}
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::Validate;
+use crate::core::STIXObject;
+use crate::core::types::GranularMarking;
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct DomainName {
+    // Required common properties
+    pub id: String,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defanged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<HashMap<String, Value>>,
+    // Specific properties
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolves_to_refs: Option<Vec<String>>
+}
+
+#[typetag::serde(name = "domain-name")]
+impl STIXObject for DomainName {}
\ No newline at end of file
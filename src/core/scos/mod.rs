@@ -0,0 +1,44 @@
+/// This module defines de Rust structures that represent the STIX Cyber-observable Objects, a. k. a. SCOs, as defined in [Section 6 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+/// The set of STIX Cyber-observable Objects of this crate includes the following structures, being the name of the struct itself specified between brackets:
+///
+/// - Artifact (`Artifact`)
+/// - Autonomous System (`AutonomousSystem`)
+/// - Directory (`Directory`)
+/// - Domain Name (`DomainName`)
+/// - Email Address (`EmailAddr`)
+/// - Email Message (`EmailMessage`)
+/// - File (`File`)
+/// - IPv4 Address (`Ipv4Addr`)
+/// - IPv6 Address (`Ipv6Addr`)
+/// - MAC Address (`MacAddr`)
+/// - Mutex (`Mutex`)
+/// - Network Traffic (`NetworkTraffic`)
+/// - Process (`Process`)
+/// - Software (`Software`)
+/// - URL (`Url`)
+/// - User Account (`UserAccount`)
+/// - Windows Registry Key (`WindowsRegistryKey`)
+/// - X.509 Certificate (`X509Certificate`)
+///
+/// Each of these objects represents a fact observed in a system or network (a file, an IP address…) and implement the `STIXObject` trait so as to include the (de)serializing features associated to them.
+/// Unlike SDOs, SCOs do not carry `created` and `modified` timestamps and their extensions are kept as generic JSON values.
+/// The `schema` module describes the properties and predefined extensions of each SCO type, e. g. to check the object paths used in patterns.
+pub mod artifact;
+pub mod autonomous_system;
+pub mod directory;
+pub mod domain_name;
+pub mod email_addr;
+pub mod email_message;
+pub mod file;
+pub mod ipv4_addr;
+pub mod ipv6_addr;
+pub mod mac_addr;
+pub mod mutex;
+pub mod network_traffic;
+pub mod process;
+pub mod schema;
+pub mod software;
+pub mod url;
+pub mod user_account;
+pub mod windows_registry_key;
+pub mod x509_certificate;
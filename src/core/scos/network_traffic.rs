@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::Validate;
+use crate::core::STIXObject;
+use crate::core::types::GranularMarking;
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct NetworkTraffic {
+    // Required common properties
+    pub id: String,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defanged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<HashMap<String, Value>>,
+    // Specific properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst_port: Option<u16>,
+    pub protocols: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_byte_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst_byte_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_packets: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst_packets: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipfix: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_payload_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst_payload_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encapsulates_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encapsulated_by_ref: Option<String>
+}
+
+#[typetag::serde(name = "network-traffic")]
+impl STIXObject for NetworkTraffic {}
\ No newline at end of file
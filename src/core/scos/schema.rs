@@ -0,0 +1,451 @@
+/// The type of a property of an SCO, as needed to check the object paths of the patterns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropertyType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Timestamp,
+    Binary,
+    Hex,
+    /// A property whose values may be of any type, e. g. those of open dictionaries.
+    Any,
+    /// A dictionary with arbitrary keys whose values have the given type.
+    Dictionary(&'static PropertyType),
+    /// An identifier of an object of one of the given types.
+    Reference(&'static [&'static str]),
+    List(&'static PropertyType),
+    /// An embedded object with a known set of properties.
+    Object(&'static [Property]),
+}
+
+pub type Property = (&'static str, PropertyType);
+
+const STRING_LIST: PropertyType = PropertyType::List(&PropertyType::String);
+const HASHES: PropertyType = PropertyType::Dictionary(&PropertyType::String);
+const DICTIONARY: PropertyType = PropertyType::Dictionary(&PropertyType::Any);
+
+const fn reference(types: &'static [&'static str]) -> PropertyType {
+    PropertyType::Reference(types)
+}
+
+/// The SCO types defined by the standard.
+pub const OBJECT_TYPES: &[&str] = &[
+    "artifact",
+    "autonomous-system",
+    "directory",
+    "domain-name",
+    "email-addr",
+    "email-message",
+    "file",
+    "ipv4-addr",
+    "ipv6-addr",
+    "mac-addr",
+    "mutex",
+    "network-traffic",
+    "process",
+    "software",
+    "url",
+    "user-account",
+    "windows-registry-key",
+    "x509-certificate",
+];
+
+const GRANULAR_MARKING: &[Property] = &[
+    ("lang", PropertyType::String),
+    ("marking_ref", reference(&["marking-definition"])),
+    ("selectors", STRING_LIST),
+];
+
+/// The common properties shared by every SCO.
+///
+/// The `extensions` are given as an open dictionary, the predefined ones being described by `extension_properties`.
+pub const COMMON_PROPERTIES: &[Property] = &[
+    ("type", PropertyType::String),
+    ("id", PropertyType::String),
+    ("spec_version", PropertyType::String),
+    ("object_marking_refs", PropertyType::List(&PropertyType::Reference(&["marking-definition"]))),
+    ("granular_markings", PropertyType::List(&PropertyType::Object(GRANULAR_MARKING))),
+    ("defanged", PropertyType::Boolean),
+    ("extensions", DICTIONARY),
+];
+
+const ARTIFACT: &[Property] = &[
+    ("mime_type", PropertyType::String),
+    ("payload_bin", PropertyType::Binary),
+    ("url", PropertyType::String),
+    ("hashes", HASHES),
+    ("encryption_algorithm", PropertyType::String),
+    ("decryption_key", PropertyType::String),
+];
+
+const AUTONOMOUS_SYSTEM: &[Property] = &[
+    ("number", PropertyType::Integer),
+    ("name", PropertyType::String),
+    ("rir", PropertyType::String),
+];
+
+const DIRECTORY: &[Property] = &[
+    ("path", PropertyType::String),
+    ("path_enc", PropertyType::String),
+    ("ctime", PropertyType::Timestamp),
+    ("mtime", PropertyType::Timestamp),
+    ("atime", PropertyType::Timestamp),
+    ("contains_refs", PropertyType::List(&PropertyType::Reference(&["file", "directory"]))),
+];
+
+const DOMAIN_NAME: &[Property] = &[
+    ("value", PropertyType::String),
+    ("resolves_to_refs", PropertyType::List(&PropertyType::Reference(&["ipv4-addr", "ipv6-addr", "domain-name"]))),
+];
+
+const EMAIL_ADDR: &[Property] = &[
+    ("value", PropertyType::String),
+    ("display_name", PropertyType::String),
+    ("belongs_to_ref", reference(&["user-account"])),
+];
+
+const EMAIL_MIME_COMPONENT: &[Property] = &[
+    ("body", PropertyType::String),
+    ("body_raw_ref", reference(&["artifact", "file"])),
+    ("content_type", PropertyType::String),
+    ("content_disposition", PropertyType::String),
+];
+
+const EMAIL_MESSAGE: &[Property] = &[
+    ("is_multipart", PropertyType::Boolean),
+    ("date", PropertyType::Timestamp),
+    ("content_type", PropertyType::String),
+    ("from_ref", reference(&["email-addr"])),
+    ("sender_ref", reference(&["email-addr"])),
+    ("to_refs", PropertyType::List(&PropertyType::Reference(&["email-addr"]))),
+    ("cc_refs", PropertyType::List(&PropertyType::Reference(&["email-addr"]))),
+    ("bcc_refs", PropertyType::List(&PropertyType::Reference(&["email-addr"]))),
+    ("message_id", PropertyType::String),
+    ("subject", PropertyType::String),
+    ("received_lines", STRING_LIST),
+    ("additional_header_fields", DICTIONARY),
+    ("body", PropertyType::String),
+    ("body_multipart", PropertyType::List(&PropertyType::Object(EMAIL_MIME_COMPONENT))),
+    ("raw_email_ref", reference(&["artifact"])),
+];
+
+const FILE: &[Property] = &[
+    ("hashes", HASHES),
+    ("size", PropertyType::Integer),
+    ("name", PropertyType::String),
+    ("name_enc", PropertyType::String),
+    ("magic_number_hex", PropertyType::Hex),
+    ("mime_type", PropertyType::String),
+    ("ctime", PropertyType::Timestamp),
+    ("mtime", PropertyType::Timestamp),
+    ("atime", PropertyType::Timestamp),
+    ("parent_directory_ref", reference(&["directory"])),
+    ("contains_refs", PropertyType::List(&PropertyType::Reference(&[]))),
+    ("content_ref", reference(&["artifact"])),
+];
+
+const IP_ADDR: &[Property] = &[
+    ("value", PropertyType::String),
+    ("resolves_to_refs", PropertyType::List(&PropertyType::Reference(&["mac-addr"]))),
+    ("belongs_to_refs", PropertyType::List(&PropertyType::Reference(&["autonomous-system"]))),
+];
+
+const MAC_ADDR: &[Property] = &[("value", PropertyType::String)];
+
+const MUTEX: &[Property] = &[("name", PropertyType::String)];
+
+const NETWORK_TRAFFIC: &[Property] = &[
+    ("start", PropertyType::Timestamp),
+    ("end", PropertyType::Timestamp),
+    ("is_active", PropertyType::Boolean),
+    ("src_ref", reference(&["ipv4-addr", "ipv6-addr", "mac-addr", "domain-name"])),
+    ("dst_ref", reference(&["ipv4-addr", "ipv6-addr", "mac-addr", "domain-name"])),
+    ("src_port", PropertyType::Integer),
+    ("dst_port", PropertyType::Integer),
+    ("protocols", STRING_LIST),
+    ("src_byte_count", PropertyType::Integer),
+    ("dst_byte_count", PropertyType::Integer),
+    ("src_packets", PropertyType::Integer),
+    ("dst_packets", PropertyType::Integer),
+    ("ipfix", DICTIONARY),
+    ("src_payload_ref", reference(&["artifact"])),
+    ("dst_payload_ref", reference(&["artifact"])),
+    ("encapsulates_refs", PropertyType::List(&PropertyType::Reference(&["network-traffic"]))),
+    ("encapsulated_by_ref", reference(&["network-traffic"])),
+];
+
+const PROCESS: &[Property] = &[
+    ("is_hidden", PropertyType::Boolean),
+    ("pid", PropertyType::Integer),
+    ("created_time", PropertyType::Timestamp),
+    ("cwd", PropertyType::String),
+    ("command_line", PropertyType::String),
+    ("environment_variables", HASHES),
+    ("opened_connection_refs", PropertyType::List(&PropertyType::Reference(&["network-traffic"]))),
+    ("creator_user_ref", reference(&["user-account"])),
+    ("image_ref", reference(&["file"])),
+    ("parent_ref", reference(&["process"])),
+    ("child_refs", PropertyType::List(&PropertyType::Reference(&["process"]))),
+];
+
+const SOFTWARE: &[Property] = &[
+    ("name", PropertyType::String),
+    ("cpe", PropertyType::String),
+    ("swid", PropertyType::String),
+    ("languages", STRING_LIST),
+    ("vendor", PropertyType::String),
+    ("version", PropertyType::String),
+];
+
+const URL: &[Property] = &[("value", PropertyType::String)];
+
+const USER_ACCOUNT: &[Property] = &[
+    ("user_id", PropertyType::String),
+    ("credential", PropertyType::String),
+    ("account_login", PropertyType::String),
+    ("account_type", PropertyType::String),
+    ("display_name", PropertyType::String),
+    ("is_service_account", PropertyType::Boolean),
+    ("is_privileged", PropertyType::Boolean),
+    ("can_escalate_privs", PropertyType::Boolean),
+    ("is_disabled", PropertyType::Boolean),
+    ("account_created", PropertyType::Timestamp),
+    ("account_expires", PropertyType::Timestamp),
+    ("credential_last_changed", PropertyType::Timestamp),
+    ("account_first_login", PropertyType::Timestamp),
+    ("account_last_login", PropertyType::Timestamp),
+];
+
+const WINDOWS_REGISTRY_VALUE: &[Property] = &[
+    ("name", PropertyType::String),
+    ("data", PropertyType::String),
+    ("data_type", PropertyType::String),
+];
+
+const WINDOWS_REGISTRY_KEY: &[Property] = &[
+    ("key", PropertyType::String),
+    ("values", PropertyType::List(&PropertyType::Object(WINDOWS_REGISTRY_VALUE))),
+    ("modified_time", PropertyType::Timestamp),
+    ("creator_user_ref", reference(&["user-account"])),
+    ("number_of_subkeys", PropertyType::Integer),
+];
+
+const X509_CERTIFICATE: &[Property] = &[
+    ("is_self_signed", PropertyType::Boolean),
+    ("hashes", HASHES),
+    ("version", PropertyType::String),
+    ("serial_number", PropertyType::String),
+    ("signature_algorithm", PropertyType::String),
+    ("issuer", PropertyType::String),
+    ("validity_not_before", PropertyType::Timestamp),
+    ("validity_not_after", PropertyType::Timestamp),
+    ("subject", PropertyType::String),
+    ("subject_public_key_algorithm", PropertyType::String),
+    ("subject_public_key_modulus", PropertyType::String),
+    ("subject_public_key_exponent", PropertyType::Integer),
+    ("x509_v3_extensions", HASHES),
+];
+
+const ARCHIVE_EXT: &[Property] = &[
+    ("contains_refs", PropertyType::List(&PropertyType::Reference(&["file", "directory"]))),
+    ("comment", PropertyType::String),
+];
+
+const NTFS_ALTERNATE_DATA_STREAM: &[Property] = &[
+    ("name", PropertyType::String),
+    ("hashes", HASHES),
+    ("size", PropertyType::Integer),
+];
+
+const NTFS_EXT: &[Property] = &[
+    ("sid", PropertyType::String),
+    ("alternate_data_streams", PropertyType::List(&PropertyType::Object(NTFS_ALTERNATE_DATA_STREAM))),
+];
+
+const PDF_EXT: &[Property] = &[
+    ("version", PropertyType::String),
+    ("is_optimized", PropertyType::Boolean),
+    ("document_info_dict", HASHES),
+    ("pdfid0", PropertyType::String),
+    ("pdfid1", PropertyType::String),
+];
+
+const RASTER_IMAGE_EXT: &[Property] = &[
+    ("image_height", PropertyType::Integer),
+    ("image_width", PropertyType::Integer),
+    ("bits_per_pixel", PropertyType::Integer),
+    ("exif_tags", DICTIONARY),
+];
+
+const WINDOWS_PE_OPTIONAL_HEADER: &[Property] = &[
+    ("magic_hex", PropertyType::Hex),
+    ("major_linker_version", PropertyType::Integer),
+    ("minor_linker_version", PropertyType::Integer),
+    ("size_of_code", PropertyType::Integer),
+    ("size_of_initialized_data", PropertyType::Integer),
+    ("size_of_uninitialized_data", PropertyType::Integer),
+    ("address_of_entry_point", PropertyType::Integer),
+    ("base_of_code", PropertyType::Integer),
+    ("base_of_data", PropertyType::Integer),
+    ("image_base", PropertyType::Integer),
+    ("section_alignment", PropertyType::Integer),
+    ("file_alignment", PropertyType::Integer),
+    ("major_os_version", PropertyType::Integer),
+    ("minor_os_version", PropertyType::Integer),
+    ("major_image_version", PropertyType::Integer),
+    ("minor_image_version", PropertyType::Integer),
+    ("major_subsystem_version", PropertyType::Integer),
+    ("minor_subsystem_version", PropertyType::Integer),
+    ("win32_version_value_hex", PropertyType::Hex),
+    ("size_of_image", PropertyType::Integer),
+    ("size_of_headers", PropertyType::Integer),
+    ("checksum_hex", PropertyType::Hex),
+    ("subsystem_hex", PropertyType::Hex),
+    ("dll_characteristics_hex", PropertyType::Hex),
+    ("size_of_stack_reserve", PropertyType::Integer),
+    ("size_of_stack_commit", PropertyType::Integer),
+    ("size_of_heap_reserve", PropertyType::Integer),
+    ("size_of_heap_commit", PropertyType::Integer),
+    ("loader_flags_hex", PropertyType::Hex),
+    ("number_of_rva_and_sizes", PropertyType::Integer),
+    ("hashes", HASHES),
+];
+
+const WINDOWS_PE_SECTION: &[Property] = &[
+    ("name", PropertyType::String),
+    ("size", PropertyType::Integer),
+    ("entropy", PropertyType::Float),
+    ("hashes", HASHES),
+];
+
+const WINDOWS_PEBINARY_EXT: &[Property] = &[
+    ("pe_type", PropertyType::String),
+    ("imphash", PropertyType::String),
+    ("machine_hex", PropertyType::Hex),
+    ("number_of_sections", PropertyType::Integer),
+    ("time_date_stamp", PropertyType::Timestamp),
+    ("pointer_to_symbol_table_hex", PropertyType::Hex),
+    ("number_of_symbols", PropertyType::Integer),
+    ("size_of_optional_header", PropertyType::Integer),
+    ("characteristics_hex", PropertyType::Hex),
+    ("file_header_hashes", HASHES),
+    ("optional_header", PropertyType::Object(WINDOWS_PE_OPTIONAL_HEADER)),
+    ("sections", PropertyType::List(&PropertyType::Object(WINDOWS_PE_SECTION))),
+];
+
+const HTTP_REQUEST_EXT: &[Property] = &[
+    ("request_method", PropertyType::String),
+    ("request_value", PropertyType::String),
+    ("request_version", PropertyType::String),
+    ("request_header", DICTIONARY),
+    ("message_body_length", PropertyType::Integer),
+    ("message_body_data_ref", reference(&["artifact"])),
+];
+
+const ICMP_EXT: &[Property] = &[
+    ("icmp_type_hex", PropertyType::Hex),
+    ("icmp_code_hex", PropertyType::Hex),
+];
+
+const SOCKET_EXT: &[Property] = &[
+    ("address_family", PropertyType::String),
+    ("is_blocking", PropertyType::Boolean),
+    ("is_listening", PropertyType::Boolean),
+    ("options", DICTIONARY),
+    ("socket_type", PropertyType::String),
+    ("socket_descriptor", PropertyType::Integer),
+    ("socket_handle", PropertyType::Integer),
+];
+
+const TCP_EXT: &[Property] = &[
+    ("src_flags_hex", PropertyType::Hex),
+    ("dst_flags_hex", PropertyType::Hex),
+];
+
+const WINDOWS_PROCESS_EXT: &[Property] = &[
+    ("aslr_enabled", PropertyType::Boolean),
+    ("dep_enabled", PropertyType::Boolean),
+    ("priority", PropertyType::String),
+    ("owner_sid", PropertyType::String),
+    ("window_title", PropertyType::String),
+    ("startup_info", DICTIONARY),
+    ("integrity_level", PropertyType::String),
+];
+
+const WINDOWS_SERVICE_EXT: &[Property] = &[
+    ("service_name", PropertyType::String),
+    ("descriptions", STRING_LIST),
+    ("display_name", PropertyType::String),
+    ("group_name", PropertyType::String),
+    ("start_type", PropertyType::String),
+    ("service_dll_refs", PropertyType::List(&PropertyType::Reference(&["file"]))),
+    ("service_type", PropertyType::String),
+    ("service_status", PropertyType::String),
+];
+
+const UNIX_ACCOUNT_EXT: &[Property] = &[
+    ("gid", PropertyType::Integer),
+    ("groups", STRING_LIST),
+    ("home_dir", PropertyType::String),
+    ("shell", PropertyType::String),
+];
+
+/// Returns the specific properties of an SCO type defined by the standard, or `None` if the type is unknown.
+pub fn properties(object_type: &str) -> Option<&'static [Property]> {
+    let properties = match object_type {
+        "artifact" => ARTIFACT,
+        "autonomous-system" => AUTONOMOUS_SYSTEM,
+        "directory" => DIRECTORY,
+        "domain-name" => DOMAIN_NAME,
+        "email-addr" => EMAIL_ADDR,
+        "email-message" => EMAIL_MESSAGE,
+        "file" => FILE,
+        "ipv4-addr" | "ipv6-addr" => IP_ADDR,
+        "mac-addr" => MAC_ADDR,
+        "mutex" => MUTEX,
+        "network-traffic" => NETWORK_TRAFFIC,
+        "process" => PROCESS,
+        "software" => SOFTWARE,
+        "url" => URL,
+        "user-account" => USER_ACCOUNT,
+        "windows-registry-key" => WINDOWS_REGISTRY_KEY,
+        "x509-certificate" => X509_CERTIFICATE,
+        _ => return None,
+    };
+    Some(properties)
+}
+
+/// Returns the properties of a predefined extension of an SCO type, or `None` if the standard does not define it.
+pub fn extension_properties(object_type: &str, extension: &str) -> Option<&'static [Property]> {
+    let properties = match (object_type, extension) {
+        ("file", "archive-ext") => ARCHIVE_EXT,
+        ("file", "ntfs-ext") => NTFS_EXT,
+        ("file", "pdf-ext") => PDF_EXT,
+        ("file", "raster-image-ext") => RASTER_IMAGE_EXT,
+        ("file", "windows-pebinary-ext") => WINDOWS_PEBINARY_EXT,
+        ("network-traffic", "http-request-ext") => HTTP_REQUEST_EXT,
+        ("network-traffic", "icmp-ext") => ICMP_EXT,
+        ("network-traffic", "socket-ext") => SOCKET_EXT,
+        ("network-traffic", "tcp-ext") => TCP_EXT,
+        ("process", "windows-process-ext") => WINDOWS_PROCESS_EXT,
+        ("process", "windows-service-ext") => WINDOWS_SERVICE_EXT,
+        ("user-account", "unix-account-ext") => UNIX_ACCOUNT_EXT,
+        _ => return None,
+    };
+    Some(properties)
+}
+
+/// Returns the type of a property of an SCO type, looking both at the specific and the common properties.
+pub fn property_type(object_type: &str, property: &str) -> Option<PropertyType> {
+    properties(object_type)?
+        .iter()
+        .chain(COMMON_PROPERTIES.iter())
+        .find(|(name, _)| *name == property)
+        .map(|(_, property_type)| *property_type)
+}
+
+/// Whether an object type, property or extension name is a custom one, i. e. it is not defined by the standard but by its producer.
+pub fn is_custom_name(name: &str) -> bool {
+    name.starts_with("x-") || name.starts_with("x_") || name.starts_with("extension-definition--")
+}
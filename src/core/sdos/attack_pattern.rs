@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -39,4 +40,12 @@ pub struct AttackPattern {
 }
 
 #[typetag::serde(name = "attack-pattern")]
-impl STIXObject for AttackPattern {}
\ No newline at end of file
+impl STIXObject for AttackPattern {}
+
+sdo_builder!(AttackPattern, AttackPatternBuilder, "attack-pattern", {
+    name: String,
+}, {
+    description: String,
+    aliases: Vec<String>,
+    kill_chain_phases: Vec<String>,
+});
@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use validator::ValidationErrors;
+use crate::core::types::{ExternalReference, GranularMarking};
+
+/// The version of the specification the objects built follow.
+pub const SPEC_VERSION: &str = "2.1";
+
+#[derive(Debug, PartialEq)]
+pub enum BuildError {
+    /// This required property has not been given.
+    MissingProperty(String),
+    /// The identifier given is not one of an object of the type built.
+    InvalidId(String),
+    /// The object built does not pass its validation.
+    Invalid(ValidationErrors),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingProperty(property) => write!(f, "the {} property is required", property),
+            BuildError::InvalidId(id) => write!(f, "{} is not an identifier of the type built", id),
+            BuildError::Invalid(errors) => write!(f, "invalid object: {}", errors),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+impl From<ValidationErrors> for BuildError {
+    fn from(errors: ValidationErrors) -> BuildError {
+        BuildError::Invalid(errors)
+    }
+}
+
+/// The common properties of the SDOs and SROs given to a builder. Those not given are left out, except the identifier and the timestamps which are generated.
+#[derive(Debug, Default)]
+pub struct CommonProperties {
+    pub id: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub created_by_ref: Option<String>,
+    pub revoked: Option<bool>,
+    pub labels: Option<Vec<String>>,
+    pub confidence: Option<u8>,
+    pub lang: Option<String>,
+    pub external_references: Option<Vec<ExternalReference>>,
+    pub object_marking_refs: Option<Vec<String>>,
+    pub granular_markings: Option<Vec<GranularMarking>>,
+}
+
+/// The builders of the SDOs and SROs, through which their common properties can be set whatever the type built (see `factory::ObjectFactory`).
+pub trait SdoBuilder {
+    type Object;
+
+    fn common_properties(&mut self) -> &mut CommonProperties;
+
+    fn build(self) -> Result<Self::Object, BuildError>;
+}
+
+/// Defines the builder of an SDO or an SRO, given the type of the object, its required specific properties and its optional ones.
+///
+/// The builder has a setter for each property, and its `build` method gives the object a new identifier (unless one is given),
+/// sets its `spec_version` and its `created` and `modified` timestamps to now at millisecond precision (unless they are given), then validates it.
+macro_rules! sdo_builder {
+    ($object:ident, $builder:ident, $object_type:literal, { $($required:ident: $required_type:ty),* $(,)? }, { $($optional:ident: $optional_type:ty),* $(,)? }) => {
+        #[doc = concat!("Builds a `", stringify!($object), "`, see `", stringify!($object), "::builder`.")]
+        #[derive(Debug, Default)]
+        pub struct $builder {
+            common: $crate::core::sdos::builder::CommonProperties,
+            $($required: Option<$required_type>,)*
+            $($optional: Option<$optional_type>,)*
+        }
+
+        impl $object {
+            #[doc = concat!("Starts building a `", stringify!($object), "` with a new identifier, created and last modified now.")]
+            pub fn builder() -> $builder {
+                $builder::default()
+            }
+        }
+
+        impl $builder {
+            pub fn id(mut self, id: impl Into<String>) -> Self {
+                self.common.id = Some(id.into());
+                self
+            }
+
+            pub fn created(mut self, created: chrono::DateTime<chrono::Utc>) -> Self {
+                self.common.created = Some(created);
+                self
+            }
+
+            pub fn modified(mut self, modified: chrono::DateTime<chrono::Utc>) -> Self {
+                self.common.modified = Some(modified);
+                self
+            }
+
+            pub fn created_by_ref(mut self, created_by_ref: impl Into<String>) -> Self {
+                self.common.created_by_ref = Some(created_by_ref.into());
+                self
+            }
+
+            pub fn revoked(mut self, revoked: bool) -> Self {
+                self.common.revoked = Some(revoked);
+                self
+            }
+
+            pub fn labels(mut self, labels: Vec<String>) -> Self {
+                self.common.labels = Some(labels);
+                self
+            }
+
+            pub fn confidence(mut self, confidence: u8) -> Self {
+                self.common.confidence = Some(confidence);
+                self
+            }
+
+            pub fn lang(mut self, lang: impl Into<String>) -> Self {
+                self.common.lang = Some(lang.into());
+                self
+            }
+
+            pub fn external_references(mut self, external_references: Vec<$crate::core::types::ExternalReference>) -> Self {
+                self.common.external_references = Some(external_references);
+                self
+            }
+
+            pub fn object_marking_refs(mut self, object_marking_refs: Vec<String>) -> Self {
+                self.common.object_marking_refs = Some(object_marking_refs);
+                self
+            }
+
+            pub fn granular_markings(mut self, granular_markings: Vec<$crate::core::types::GranularMarking>) -> Self {
+                self.common.granular_markings = Some(granular_markings);
+                self
+            }
+
+            $(
+                pub fn $required(mut self, $required: impl Into<$required_type>) -> Self {
+                    self.$required = Some($required.into());
+                    self
+                }
+            )*
+
+            $(
+                pub fn $optional(mut self, $optional: impl Into<$optional_type>) -> Self {
+                    self.$optional = Some($optional.into());
+                    self
+                }
+            )*
+
+            #[doc = concat!("Builds the `", stringify!($object), "`, failing if a required property is missing, if the identifier given is not one of a `", $object_type, "` or if the object is not valid.")]
+            pub fn build(self) -> Result<$object, $crate::core::sdos::builder::BuildError> {
+                use chrono::SubsecRound;
+                use validator::Validate;
+                use $crate::core::STIXObject;
+                use $crate::core::sdos::builder::BuildError;
+                let common = self.common;
+                let created = common.created.unwrap_or_else(|| chrono::Utc::now().trunc_subsecs(3));
+                let mut object = $object {
+                    id: String::new(),
+                    spec_version: $crate::core::sdos::builder::SPEC_VERSION.to_string(),
+                    created,
+                    modified: common.modified.unwrap_or(created),
+                    created_by_ref: common.created_by_ref,
+                    revoked: common.revoked,
+                    labels: common.labels,
+                    confidence: common.confidence.map(Into::into),
+                    lang: common.lang,
+                    external_references: common.external_references,
+                    object_marking_refs: common.object_marking_refs,
+                    granular_markings: common.granular_markings,
+                    $($required: self.$required.ok_or_else(|| BuildError::MissingProperty(stringify!($required).to_string()))?,)*
+                    $($optional: self.$optional,)*
+                };
+                object.id = match common.id {
+                    Some(id) if id.starts_with(concat!($object_type, "--")) => id,
+                    Some(id) => return Err(BuildError::InvalidId(id)),
+                    None => object.build_new_id_for_type($object_type.to_string()),
+                };
+                object.validate()?;
+                Ok(object)
+            }
+        }
+
+        impl $crate::core::sdos::builder::SdoBuilder for $builder {
+            type Object = $object;
+
+            fn common_properties(&mut self) -> &mut $crate::core::sdos::builder::CommonProperties {
+                &mut self.common
+            }
+
+            fn build(self) -> Result<$object, $crate::core::sdos::builder::BuildError> {
+                $builder::build(self)
+            }
+        }
+    };
+}
+
+pub(crate) use sdo_builder;
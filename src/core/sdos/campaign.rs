@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -43,4 +44,14 @@ pub struct Campaign {
 }
 
 #[typetag::serde(name = "campaign")]
-impl STIXObject for Campaign {}
\ No newline at end of file
+impl STIXObject for Campaign {}
+
+sdo_builder!(Campaign, CampaignBuilder, "campaign", {
+    name: String,
+}, {
+    description: String,
+    aliases: Vec<String>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    objective: String,
+});
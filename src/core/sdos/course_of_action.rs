@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -35,4 +36,10 @@ pub struct CourseOfAction {
 }
 
 #[typetag::serde(name = "course-of-action")]
-impl STIXObject for CourseOfAction {}
\ No newline at end of file
+impl STIXObject for CourseOfAction {}
+
+sdo_builder!(CourseOfAction, CourseOfActionBuilder, "course-of-action", {
+    name: String,
+}, {
+    description: String,
+});
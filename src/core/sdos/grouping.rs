@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -38,4 +39,12 @@ pub struct Grouping {
 }
 
 #[typetag::serde(name = "grouping")]
-impl STIXObject for Grouping {}
\ No newline at end of file
+impl STIXObject for Grouping {}
+
+sdo_builder!(Grouping, GroupingBuilder, "grouping", {
+    context: String,
+    object_refs: Vec<String>,
+}, {
+    name: String,
+    description: String,
+});
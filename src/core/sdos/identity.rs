@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -45,3 +46,13 @@ pub struct Identity {
 
 #[typetag::serde(name = "identity")]
 impl STIXObject for Identity {}
+
+sdo_builder!(Identity, IdentityBuilder, "identity", {
+    name: String,
+}, {
+    description: String,
+    roles: Vec<String>,
+    identity_class: String,
+    sectors: Vec<String>,
+    contact_information: String,
+});
@@ -0,0 +1,126 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
+use crate::core::patterns::{self, ast::Pattern, pattern_type::PatternType, PatternError};
+use crate::core::types::{ExternalReference, GranularMarking, KillChainPhase};
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+#[validate(schema(function = "validate_pattern"))]
+pub struct Indicator {
+    // Required common properties
+    pub id: String,
+    pub spec_version: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_references: Option<Vec<ExternalReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    // Specific properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indicator_types: Option<Vec<String>>,
+    pub pattern: String,
+    pub pattern_type: PatternType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern_version: Option<String>,
+    pub valid_from: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kill_chain_phases: Option<Vec<KillChainPhase>>
+}
+
+#[typetag::serde(name = "indicator")]
+impl STIXObject for Indicator {}
+
+sdo_builder!(Indicator, IndicatorBuilder, "indicator", {
+    pattern: String,
+    pattern_type: PatternType,
+    valid_from: DateTime<Utc>,
+}, {
+    name: String,
+    description: String,
+    indicator_types: Vec<String>,
+    pattern_version: String,
+    valid_until: DateTime<Utc>,
+    kill_chain_phases: Vec<KillChainPhase>,
+});
+
+impl Indicator {
+    /// Creates an indicator detecting the given STIX pattern, valid from its creation on.
+    pub fn from_pattern(pattern: &Pattern) -> Indicator {
+        let now = Utc::now().round_subsecs(3);
+        Indicator {
+            id: format!("indicator--{}", Uuid::new_v4()),
+            spec_version: "2.1".to_string(),
+            created: now,
+            modified: now,
+            created_by_ref: None,
+            revoked: None,
+            labels: None,
+            confidence: None,
+            lang: None,
+            external_references: None,
+            object_marking_refs: None,
+            granular_markings: None,
+            name: None,
+            description: None,
+            indicator_types: None,
+            pattern: pattern.to_string(),
+            pattern_type: PatternType::Stix,
+            pattern_version: Some("2.1".to_string()),
+            valid_from: now,
+            valid_until: None,
+            kill_chain_phases: None,
+        }
+    }
+
+    /// Parses the `pattern` of the indicator. Only meaningful when `pattern_type` is `stix`.
+    pub fn parse_pattern(&self) -> Result<Pattern, PatternError> {
+        patterns::parse(&self.pattern)
+    }
+}
+
+/// Rejects the patterns that are malformed in their language (see `PatternType::check`) and the validity windows that end before they start.
+fn validate_pattern(indicator: &Indicator) -> Result<(), ValidationError> {
+    if let Err(message) = indicator.pattern_type.check(&indicator.pattern) {
+        let code = match indicator.pattern_type {
+            PatternType::Stix => "invalid_stix_pattern",
+            PatternType::Pcre => "invalid_pcre_pattern",
+            PatternType::Sigma => "invalid_sigma_pattern",
+            PatternType::Snort => "invalid_snort_pattern",
+            PatternType::Suricata => "invalid_suricata_pattern",
+            PatternType::Yara => "invalid_yara_pattern",
+            PatternType::Other(_) => "invalid_pattern",
+        };
+        let mut error = ValidationError::new(code);
+        error.message = Some(message.into());
+        return Err(error);
+    }
+    if let Some(valid_until) = indicator.valid_until {
+        if valid_until <= indicator.valid_from {
+            return Err(ValidationError::new("valid_until_before_valid_from"));
+        }
+    }
+    Ok(())
+}
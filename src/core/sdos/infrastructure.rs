@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -45,4 +46,15 @@ pub struct Infrastructure {
 }
 
 #[typetag::serde(name = "infrastructure")]
-impl STIXObject for Infrastructure {}
\ No newline at end of file
+impl STIXObject for Infrastructure {}
+
+sdo_builder!(Infrastructure, InfrastructureBuilder, "infrastructure", {
+    name: String,
+}, {
+    description: String,
+    infrastructure_types: Vec<String>,
+    aliases: Vec<String>,
+    kill_chain_phases: Vec<String>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+});
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -47,4 +48,16 @@ pub struct IntrusionSet {
 }
 
 #[typetag::serde(name = "intrusion-set")]
-impl STIXObject for IntrusionSet {}
\ No newline at end of file
+impl STIXObject for IntrusionSet {}
+
+sdo_builder!(IntrusionSet, IntrusionSetBuilder, "intrusion-set", {
+    name: String,
+}, {
+    description: String,
+    aliases: Vec<String>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    goals: Vec<String>,
+    primary_motivation: String,
+    secondary_motivations: Vec<String>,
+});
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference,GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -56,4 +57,18 @@ pub struct Location {
 }
 
 #[typetag::serde(name = "location")]
-impl STIXObject for Location {}
\ No newline at end of file
+impl STIXObject for Location {}
+
+sdo_builder!(Location, LocationBuilder, "location", {}, {
+    name: String,
+    description: String,
+    latitude: f64,
+    longitude: f64,
+    precision: f64,
+    region: String,
+    country: String,
+    administrative_area: String,
+    city: String,
+    street_address: String,
+    postal_code: String,
+});
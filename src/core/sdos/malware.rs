@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking, KillChainPhase};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -56,4 +57,21 @@ pub struct Malware {
 }
 
 #[typetag::serde(name = "malware")]
-impl STIXObject for Malware {}
\ No newline at end of file
+impl STIXObject for Malware {}
+
+sdo_builder!(Malware, MalwareBuilder, "malware", {
+    malware_types: Vec<String>,
+    is_family: bool,
+}, {
+    name: String,
+    description: String,
+    aliases: Vec<String>,
+    kill_chain_phases: Vec<KillChainPhase>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    os_execution_envs: Vec<String>,
+    architecture_execution_envs: Vec<String>,
+    implementation_languages: Vec<String>,
+    capabilities: Vec<String>,
+    sample_refs: Vec<String>,
+});
@@ -22,19 +22,21 @@
 ///
 /// Each of these objects corresponds to a concept commonly used in Cyber Threat Intelligence investigations and implement the `STIXObject` trait so as to include the (de)serializing features associated to them.
 /// Note that required parameters and optional parameters compatibility is majorly enforced so as to implement the requirements defined in the STIX standard itself.
+/// Each object can also be built property by property (e. g. `Malware::builder().malware_types(vec![…]).is_family(true).build()?`), with a new identifier and timestamps (see `builder`).
 pub mod attack_pattern;
+pub mod builder;
 pub mod campaign;
 pub mod course_of_action;
 pub mod grouping;
 pub mod identity;
-// pub mod indicator;
+pub mod indicator;
 pub mod infrastructure;
 pub mod intrusion_set;
 pub mod location;
 pub mod malware;
 //pub mod malware_analysis;
 pub mod note;
-//pub mod observed_data;
+pub mod observed_data;
 pub mod opinion;
 pub mod report;
 pub mod threat_actor;
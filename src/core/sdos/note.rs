@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference,GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -40,4 +41,12 @@ pub struct Note {
 }
 
 #[typetag::serde(name = "note")]
-impl STIXObject for Note {}
\ No newline at end of file
+impl STIXObject for Note {}
+
+sdo_builder!(Note, NoteBuilder, "note", {
+    content: String,
+}, {
+    summary: String,
+    authors: Vec<String>,
+    object_refs: Vec<String>,
+});
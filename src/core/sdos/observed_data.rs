@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError};
+use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
+use crate::core::types::{ExternalReference, GranularMarking};
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+#[validate(schema(function = "validate_observed_data"))]
+pub struct ObservedData {
+    // Required common properties
+    pub id: String,
+    pub spec_version: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_references: Option<Vec<ExternalReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    // Specific properties
+    pub first_observed: DateTime<Utc>,
+    pub last_observed: DateTime<Utc>,
+    #[validate(range(min = 1, max = 999999999))]
+    pub number_observed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub objects: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_refs: Option<Vec<String>>
+}
+
+#[typetag::serde(name = "observed-data")]
+impl STIXObject for ObservedData {}
+
+/// Rejects the observed data last observed before they were first observed, or giving both or none of `objects` and `object_refs`.
+fn validate_observed_data(observed_data: &ObservedData) -> Result<(), ValidationError> {
+    if observed_data.last_observed < observed_data.first_observed {
+        return Err(ValidationError::new("last_observed_before_first_observed"));
+    }
+    if observed_data.objects.is_some() == observed_data.object_refs.is_some() {
+        return Err(ValidationError::new("objects_and_object_refs_not_exclusive"));
+    }
+    Ok(())
+}
+
+sdo_builder!(ObservedData, ObservedDataBuilder, "observed-data", {
+    first_observed: DateTime<Utc>,
+    last_observed: DateTime<Utc>,
+    number_observed: u64,
+}, {
+    objects: HashMap<String, Value>,
+    object_refs: Vec<String>,
+});
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -49,4 +50,12 @@ pub enum OpinionEnum {
 }
 
 #[typetag::serde(name = "opinion")]
-impl STIXObject for Opinion {}
\ No newline at end of file
+impl STIXObject for Opinion {}
+
+sdo_builder!(Opinion, OpinionBuilder, "opinion", {
+    opinion: OpinionEnum,
+}, {
+    explanation: String,
+    authors: Vec<String>,
+    object_refs: Vec<String>,
+});
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference,GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -40,4 +41,13 @@ pub struct Report {
 }
 
 #[typetag::serde(name = "report")]
-impl STIXObject for Report {}
\ No newline at end of file
+impl STIXObject for Report {}
+
+sdo_builder!(Report, ReportBuilder, "report", {
+    name: String,
+    report_types: Vec<String>,
+}, {
+    description: String,
+    published: DateTime<Utc>,
+    object_refs: Vec<String>,
+});
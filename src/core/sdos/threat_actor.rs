@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference, GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -56,4 +57,21 @@ pub struct ThreatActor {
 }
 
 #[typetag::serde(name = "threat-actor")]
-impl STIXObject for ThreatActor {}
\ No newline at end of file
+impl STIXObject for ThreatActor {}
+
+sdo_builder!(ThreatActor, ThreatActorBuilder, "threat-actor", {
+    name: String,
+    threat_actor_types: Vec<String>,
+}, {
+    description: String,
+    aliases: Vec<String>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    roles: Vec<String>,
+    goals: Vec<String>,
+    sophistication: String,
+    resource_level: String,
+    primary_motivation: String,
+    secondary_motivations: Vec<String>,
+    personal_motivations: Vec<String>,
+});
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference,GranularMarking,KillChainPhase};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -42,4 +43,14 @@ pub struct Tool {
 }
 
 #[typetag::serde(name = "tool")] 
-impl STIXObject for Tool {}
\ No newline at end of file
+impl STIXObject for Tool {}
+
+sdo_builder!(Tool, ToolBuilder, "tool", {
+    name: String,
+    tool_types: Vec<String>,
+}, {
+    description: String,
+    aliases: Vec<String>,
+    kill_chain_phases: Vec<KillChainPhase>,
+    tool_version: String,
+});
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use validator::Validate;
 use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
 use crate::core::types::{ExternalReference,GranularMarking};
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
@@ -36,4 +37,10 @@ pub struct Vulnerability {
 }
 
 #[typetag::serde(name = "vulnerability")] 
-impl STIXObject for Vulnerability {}
\ No newline at end of file
+impl STIXObject for Vulnerability {}
+
+sdo_builder!(Vulnerability, VulnerabilityBuilder, "vulnerability", {
+    name: String,
+}, {
+    description: String,
+});
@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::DateTime;
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::similarity::object::{self, Graphs, Similarity, SimilarityError, Weights};
+use crate::core::stores::Store;
+
+/// The best match found for an object of either graph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairScore {
+    /// The identifier of the object.
+    pub lhs: String,
+    /// The identifier of the object of the other graph it matches best.
+    pub rhs: String,
+    pub similarity: Similarity,
+}
+
+/// The similarity of two graphs, with the best match of each object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphSimilarity {
+    /// The score, between 0 and 100.
+    pub score: f64,
+    pub matching_score: f64,
+    /// The number of objects matched, i. e. the length of the summary.
+    pub len_pairs: usize,
+    /// The best match of each object, sorted by identifier.
+    pub summary: Vec<PairScore>,
+}
+
+/// Scores how similar two collections of objects are, from 0 to 100, as the `graph_similarity` function of python-stix2 does.
+///
+/// Every object is compared with each object of the same type of the other collection, following their references within
+/// their own collection up to the `max_depth` of the weights (e. g. comparing the `object_refs` of two reports through the
+/// objects they refer to). The score is the average of the best score of each object. Only the latest version of the objects is compared.
+pub fn graph_similarity(first: &[Box<dyn STIXObject>], second: &[Box<dyn STIXObject>], weights: &Weights) -> Result<GraphSimilarity, SimilarityError> {
+    let first = latest(first.iter().map(|object| object.to_value()));
+    let second = latest(second.iter().map(|object| object.to_value()));
+    similarity(&first, &second, weights)
+}
+
+/// Scores how similar the objects of two stores are (see `graph_similarity`).
+pub fn store_similarity(first: &dyn Store, second: &dyn Store, weights: &Weights) -> Result<GraphSimilarity, SimilarityError> {
+    let load = |store: &dyn Store| -> Result<HashMap<String, Value>, SimilarityError> {
+        let mut objects = HashMap::new();
+        for id in store.ids()? {
+            if let Some(object) = store.get(&id)? {
+                objects.insert(id, object.to_value());
+            }
+        }
+        Ok(objects)
+    };
+    similarity(&load(first)?, &load(second)?, weights)
+}
+
+/// Keeps the latest version of each object, by identifier.
+fn latest(values: impl Iterator<Item = Value>) -> HashMap<String, Value> {
+    let modified = |value: &Value| value.get("modified").and_then(Value::as_str).and_then(|modified| DateTime::parse_from_rfc3339(modified).ok());
+    let mut objects: HashMap<String, Value> = HashMap::new();
+    for value in values {
+        let id = value.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+        if objects.get(&id).is_none_or(|kept| modified(kept) < modified(&value)) {
+            objects.insert(id, value);
+        }
+    }
+    objects
+}
+
+fn similarity(first: &HashMap<String, Value>, second: &HashMap<String, Value>, weights: &Weights) -> Result<GraphSimilarity, SimilarityError> {
+    let (first_types, second_types) = (by_type(first), by_type(second));
+    let graphs = Graphs { first, second };
+    let reversed = Graphs { first: second, second: first };
+    let mut best: BTreeMap<String, PairScore> = BTreeMap::new();
+    for (object_type, first_objects) in &first_types {
+        for first_object in first_objects {
+            for second_object in second_types.get(object_type).into_iter().flatten() {
+                let first_id = first_object["id"].as_str().unwrap_or_default();
+                let second_id = second_object["id"].as_str().unwrap_or_default();
+                let forward = object::value_similarity(first_object, second_object, weights, Some(&graphs), weights.max_depth)?;
+                keep_best(&mut best, first_id, second_id, forward);
+                let backward = object::value_similarity(second_object, first_object, weights, Some(&reversed), weights.max_depth)?;
+                keep_best(&mut best, second_id, first_id, backward);
+            }
+        }
+    }
+    let matching_score: f64 = best.values().map(|pair| pair.similarity.score).sum();
+    let len_pairs = best.len();
+    let score = if len_pairs > 0 { matching_score / len_pairs as f64 } else { 0.0 };
+    Ok(GraphSimilarity { score, matching_score, len_pairs, summary: best.into_values().collect() })
+}
+
+/// Groups the objects by type.
+fn by_type(objects: &HashMap<String, Value>) -> BTreeMap<&str, Vec<&Value>> {
+    let mut buckets: BTreeMap<&str, Vec<&Value>> = BTreeMap::new();
+    for value in objects.values() {
+        buckets.entry(value.get("type").and_then(Value::as_str).unwrap_or_default()).or_default().push(value);
+    }
+    for bucket in buckets.values_mut() {
+        bucket.sort_by_key(|value| value["id"].as_str());
+    }
+    buckets
+}
+
+fn keep_best(best: &mut BTreeMap<String, PairScore>, lhs: &str, rhs: &str, similarity: Similarity) {
+    if best.get(lhs).is_none_or(|pair| similarity.score > pair.similarity.score) {
+        best.insert(lhs.to_string(), PairScore { lhs: lhs.to_string(), rhs: rhs.to_string(), similarity });
+    }
+}
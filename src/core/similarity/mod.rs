@@ -0,0 +1,6 @@
+/// This module scores how similar STIX objects are, following the semantic equivalence algorithms of the STIX Best Practices Guide.
+/// The `object` module compares two objects of the same type through weighted comparisons of their properties, as the `object_similarity`
+/// function of python-stix2 does, so that duplicates coming from different producers can be recognized and merged.
+/// The `graph` module compares whole bundles or stores, pairing their objects and following their references, as `graph_similarity` does.
+pub mod graph;
+pub mod object;
@@ -0,0 +1,385 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use chrono::DateTime;
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::patterns::{self, equivalence};
+use crate::core::stores::StoreError;
+
+/// The property name under which the `latitude` and `longitude` of the locations are compared together.
+pub const LONGITUDE_LATITUDE: &str = "longitude_latitude";
+
+/// How the values of a property are compared, each comparison giving a score between 0 and 1.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Comparison {
+    /// Strings compared through the ratio of their sorted tokens, as `token_sort_ratio` of rapidfuzz.
+    PartialString,
+    /// Lists compared through the number of items they share over the size of the largest one.
+    PartialList,
+    /// Values that must be equal.
+    Exact,
+    /// Timestamps that are closer the higher the score, the score reaching 0 once they are this number of days apart.
+    PartialTimestamp { tolerance_days: f64 },
+    /// Coordinates that are closer the higher the score, the score reaching 0 once they are this number of kilometers apart.
+    LocationDistance { threshold_km: f64 },
+    /// External references, pairing them in order: those of the sources defined by STIX (`veris`, `cve`, `capec`, `mitre-attack`)
+    /// are a perfect match as soon as one of them shares its `external_id` or `url`.
+    ExternalReferences,
+    /// STIX patterns, which must be equivalent (see `patterns::equivalence`).
+    Pattern,
+    /// References to objects of the same type, scored by the similarity of these objects. Only compared within graphs (see `similarity::graph`).
+    Reference,
+    /// Lists of references, pairing the objects of the same type and averaging the best score of each of them. Only compared within graphs.
+    ReferenceList,
+}
+
+/// The weight and comparison of a property.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyWeight {
+    pub property: String,
+    pub weight: f64,
+    pub comparison: Comparison,
+}
+
+impl PropertyWeight {
+    pub fn new(property: &str, weight: f64, comparison: Comparison) -> PropertyWeight {
+        PropertyWeight { property: property.to_string(), weight, comparison }
+    }
+}
+
+/// The properties compared for each type of object. The default ones are those of python-stix2.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weights {
+    types: HashMap<String, Vec<PropertyWeight>>,
+    /// Whether objects of different `spec_version` can be compared.
+    pub ignore_spec_version: bool,
+    /// How many references are followed in a row when comparing graphs, the properties holding references being left out beyond.
+    pub max_depth: usize,
+}
+
+impl Weights {
+    /// Weights comparing no type at all.
+    pub fn empty() -> Weights {
+        Weights { types: HashMap::new(), ignore_spec_version: false, max_depth: 1 }
+    }
+
+    /// Sets the properties compared for a type, replacing the previous ones.
+    pub fn set(&mut self, object_type: &str, properties: Vec<PropertyWeight>) {
+        self.types.insert(object_type.to_string(), properties);
+    }
+
+    pub fn get(&self, object_type: &str) -> Option<&[PropertyWeight]> {
+        self.types.get(object_type).map(Vec::as_slice)
+    }
+}
+
+impl Default for Weights {
+    fn default() -> Weights {
+        use Comparison::*;
+        let property = PropertyWeight::new;
+        let mut weights = Weights::empty();
+        weights.set("attack-pattern", vec![property("name", 30.0, PartialString), property("external_references", 70.0, ExternalReferences)]);
+        weights.set("campaign", vec![property("name", 60.0, PartialString), property("aliases", 40.0, PartialList)]);
+        weights.set("course-of-action", vec![property("name", 60.0, PartialString), property("external_references", 40.0, ExternalReferences)]);
+        weights.set("grouping", vec![property("name", 20.0, PartialString), property("context", 20.0, PartialString), property("object_refs", 60.0, ReferenceList)]);
+        weights.set("identity", vec![property("name", 60.0, PartialString), property("identity_class", 20.0, Exact), property("sectors", 20.0, PartialList)]);
+        weights.set(
+            "indicator",
+            vec![
+                property("indicator_types", 15.0, PartialList),
+                property("pattern", 80.0, Pattern),
+                property("valid_from", 5.0, PartialTimestamp { tolerance_days: 1.0 }),
+            ],
+        );
+        weights.set(
+            "intrusion-set",
+            vec![property("name", 20.0, PartialString), property("external_references", 60.0, ExternalReferences), property("aliases", 20.0, PartialList)],
+        );
+        weights.set(
+            "location",
+            vec![
+                property(LONGITUDE_LATITUDE, 34.0, LocationDistance { threshold_km: 1000.0 }),
+                property("region", 33.0, Exact),
+                property("country", 33.0, Exact),
+            ],
+        );
+        weights.set("malware", vec![property("malware_types", 20.0, PartialList), property("name", 80.0, PartialString)]);
+        weights.set("marking-definition", vec![property("name", 20.0, Exact), property("definition", 60.0, Exact), property("definition_type", 20.0, Exact)]);
+        weights.set("relationship", vec![property("relationship_type", 20.0, Exact), property("source_ref", 40.0, Reference), property("target_ref", 40.0, Reference)]);
+        weights.set(
+            "report",
+            vec![
+                property("name", 30.0, PartialString),
+                property("published", 10.0, PartialTimestamp { tolerance_days: 1.0 }),
+                property("object_refs", 60.0, ReferenceList),
+            ],
+        );
+        weights.set(
+            "sighting",
+            vec![
+                property("first_seen", 5.0, PartialTimestamp { tolerance_days: 1.0 }),
+                property("last_seen", 5.0, PartialTimestamp { tolerance_days: 1.0 }),
+                property("sighting_of_ref", 40.0, Reference),
+                property("observed_data_refs", 20.0, ReferenceList),
+                property("where_sighted_refs", 20.0, ReferenceList),
+                property("summary", 10.0, Exact),
+            ],
+        );
+        weights.set(
+            "threat-actor",
+            vec![property("name", 60.0, PartialString), property("threat_actor_types", 20.0, PartialList), property("aliases", 20.0, PartialList)],
+        );
+        weights.set("tool", vec![property("tool_types", 20.0, PartialList), property("name", 80.0, PartialString)]);
+        weights.set("vulnerability", vec![property("name", 30.0, PartialString), property("external_references", 70.0, ExternalReferences)]);
+        weights
+    }
+}
+
+/// The contribution of a property to the score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyScore {
+    pub property: String,
+    pub weight: f64,
+    /// The weight times the score of the comparison.
+    pub contributing_score: f64,
+}
+
+/// The similarity of two objects, with the breakdown of the properties compared.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Similarity {
+    /// The score, between 0 and 100.
+    pub score: f64,
+    /// The properties present in both objects, which are the only ones compared.
+    pub properties: Vec<PropertyScore>,
+    pub matching_score: f64,
+    pub sum_weights: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SimilarityError {
+    /// Only objects of the same type can be compared.
+    DifferentTypes(String, String),
+    /// Only objects of the same `spec_version` can be compared, unless the weights ignore it.
+    DifferentSpecVersions(String, String),
+    /// The objects of a store could not be loaded.
+    Store(StoreError),
+}
+
+impl fmt::Display for SimilarityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimilarityError::DifferentTypes(first, second) => write!(f, "objects of types {} and {} cannot be compared", first, second),
+            SimilarityError::DifferentSpecVersions(first, second) => write!(f, "objects of spec versions {} and {} cannot be compared", first, second),
+            SimilarityError::Store(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for SimilarityError {}
+
+impl From<StoreError> for SimilarityError {
+    fn from(error: StoreError) -> SimilarityError {
+        SimilarityError::Store(error)
+    }
+}
+
+/// Scores how similar two objects of the same type are, from 0 to 100.
+///
+/// Each property of the weights of the type that both objects have is compared, contributing its weight times the score of the
+/// comparison, and the score is the sum of the contributions over the sum of the weights of the properties compared.
+/// Objects of types without weights, or without properties to compare, score 0.
+pub fn object_similarity(first: &dyn STIXObject, second: &dyn STIXObject, weights: &Weights) -> Result<Similarity, SimilarityError> {
+    value_similarity(&first.to_value(), &second.to_value(), weights, None, weights.max_depth)
+}
+
+/// Whether two objects are similar enough to be considered the same, i. e. whether their score reaches the threshold.
+pub fn object_equivalence(first: &dyn STIXObject, second: &dyn STIXObject, threshold: f64, weights: &Weights) -> Result<bool, SimilarityError> {
+    Ok(object_similarity(first, second, weights)?.score >= threshold)
+}
+
+/// The objects of the two graphs compared, by identifier, through which the references are resolved.
+pub(crate) struct Graphs<'a> {
+    pub first: &'a HashMap<String, Value>,
+    pub second: &'a HashMap<String, Value>,
+}
+
+/// Scores two objects, comparing their references within the graphs if given and `depth` allows it.
+pub(crate) fn value_similarity(first: &Value, second: &Value, weights: &Weights, graphs: Option<&Graphs>, depth: usize) -> Result<Similarity, SimilarityError> {
+    let text = |value: &Value, property: &str, default: &str| value.get(property).and_then(Value::as_str).unwrap_or(default).to_string();
+    let (first_type, second_type) = (text(first, "type", ""), text(second, "type", ""));
+    if first_type != second_type {
+        return Err(SimilarityError::DifferentTypes(first_type, second_type));
+    }
+    let (first_version, second_version) = (text(first, "spec_version", "2.0"), text(second, "spec_version", "2.0"));
+    if !weights.ignore_spec_version && first_version != second_version {
+        return Err(SimilarityError::DifferentSpecVersions(first_version, second_version));
+    }
+    let mut similarity = Similarity { score: 0.0, properties: Vec::new(), matching_score: 0.0, sum_weights: 0.0 };
+    for property in weights.get(&first_type).unwrap_or_default() {
+        let score = match &property.comparison {
+            Comparison::LocationDistance { threshold_km } => match (coordinates(first), coordinates(second)) {
+                (Some(first), Some(second)) => Some(location_distance(first, second, *threshold_km)),
+                _ => None,
+            },
+            Comparison::Reference | Comparison::ReferenceList => match (graphs, first.get(&property.property), second.get(&property.property)) {
+                (Some(graphs), Some(first), Some(second)) if depth > 0 => Some(match property.comparison {
+                    Comparison::Reference => reference(first.as_str().unwrap_or_default(), second.as_str().unwrap_or_default(), weights, graphs, depth - 1),
+                    _ => reference_list(first, second, weights, graphs, depth - 1),
+                }),
+                _ => None,
+            },
+            comparison => match (first.get(&property.property), second.get(&property.property)) {
+                (Some(first), Some(second)) => Some(compare(comparison, first, second)),
+                _ => None,
+            },
+        };
+        if let Some(score) = score {
+            let contributing_score = property.weight * score;
+            similarity.matching_score += contributing_score;
+            similarity.sum_weights += property.weight;
+            similarity.properties.push(PropertyScore { property: property.property.clone(), weight: property.weight, contributing_score });
+        }
+    }
+    if similarity.sum_weights > 0.0 {
+        similarity.score = similarity.matching_score / similarity.sum_weights * 100.0;
+    }
+    Ok(similarity)
+}
+
+fn compare(comparison: &Comparison, first: &Value, second: &Value) -> f64 {
+    match comparison {
+        Comparison::PartialString => match (first.as_str(), second.as_str()) {
+            (Some(first), Some(second)) => token_sort_ratio(first, second),
+            _ => 0.0,
+        },
+        Comparison::PartialList => partial_list(first, second),
+        Comparison::Exact => (first == second) as u8 as f64,
+        Comparison::PartialTimestamp { tolerance_days } => {
+            let timestamp = |value: &Value| value.as_str().and_then(|text| DateTime::parse_from_rfc3339(text).ok());
+            match (timestamp(first), timestamp(second)) {
+                (Some(first), Some(second)) => {
+                    let seconds = (first - second).num_milliseconds().abs() as f64 / 1000.0;
+                    1.0 - (seconds / (tolerance_days * 86400.0)).min(1.0)
+                }
+                _ => 0.0,
+            }
+        }
+        Comparison::ExternalReferences => external_references(first, second),
+        Comparison::Pattern => match (first.as_str(), second.as_str()) {
+            (Some(first), Some(second)) => match (patterns::parse(first), patterns::parse(second)) {
+                (Ok(first), Ok(second)) => equivalence::equivalent(&first, &second) as u8 as f64,
+                _ => (first == second) as u8 as f64,
+            },
+            _ => 0.0,
+        },
+        Comparison::LocationDistance { .. } | Comparison::Reference | Comparison::ReferenceList => 0.0,
+    }
+}
+
+/// Scores two references through the similarity of the objects they refer to, which must be of the same type and found in the graphs.
+fn reference(first: &str, second: &str, weights: &Weights, graphs: &Graphs, depth: usize) -> f64 {
+    let object_type = first.split("--").next().unwrap_or_default();
+    if object_type != second.split("--").next().unwrap_or_default() || weights.get(object_type).is_none() {
+        return 0.0;
+    }
+    match (graphs.first.get(first), graphs.second.get(second)) {
+        (Some(first), Some(second)) => value_similarity(first, second, weights, Some(graphs), depth).map_or(0.0, |similarity| similarity.score / 100.0),
+        _ => 0.0,
+    }
+}
+
+/// Scores two lists of references: every reference is paired with those of the other list of the same type, keeping its best score,
+/// and these scores are averaged. As in python-stix2, the references missing from the graphs or to objects of a type without weights are not paired.
+fn reference_list(first: &Value, second: &Value, weights: &Weights, graphs: &Graphs, depth: usize) -> f64 {
+    let references = |value: &Value| value.as_array().map(|references| references.iter().filter_map(Value::as_str).map(str::to_string).collect::<Vec<_>>()).unwrap_or_default();
+    let (first, second) = (references(first), references(second));
+    let mut best: HashMap<&str, f64> = HashMap::new();
+    for first_reference in first.iter().filter(|reference| graphs.first.contains_key(*reference)) {
+        let object_type = first_reference.split("--").next().unwrap_or_default();
+        if weights.get(object_type).is_none() {
+            continue;
+        }
+        let paired = second.iter().filter(|reference| reference.split("--").next() == Some(object_type) && graphs.second.contains_key(*reference));
+        for second_reference in paired {
+            let score = reference(first_reference, second_reference, weights, graphs, depth);
+            for id in [first_reference, second_reference] {
+                let entry = best.entry(id).or_insert(score);
+                *entry = entry.max(score);
+            }
+        }
+    }
+    if best.is_empty() {
+        return 0.0;
+    }
+    best.values().sum::<f64>() / best.len() as f64
+}
+
+/// The ratio of the strings once their tokens are sorted, i. e. twice their longest common subsequence over their total length.
+pub fn token_sort_ratio(first: &str, second: &str) -> f64 {
+    let sort = |text: &str| {
+        let mut tokens: Vec<&str> = text.split_whitespace().collect();
+        tokens.sort_unstable();
+        tokens.join(" ").chars().collect::<Vec<char>>()
+    };
+    let (first, second) = (sort(first), sort(second));
+    if first.is_empty() && second.is_empty() {
+        return 1.0;
+    }
+    let mut lengths = vec![0usize; second.len() + 1];
+    for a in &first {
+        let mut diagonal = 0;
+        for (j, b) in second.iter().enumerate() {
+            let above = lengths[j + 1];
+            lengths[j + 1] = if a == b { diagonal + 1 } else { above.max(lengths[j]) };
+            diagonal = above;
+        }
+    }
+    2.0 * lengths[second.len()] as f64 / (first.len() + second.len()) as f64
+}
+
+fn partial_list(first: &Value, second: &Value) -> f64 {
+    let items = |value: &Value| value.as_array().map(|items| items.iter().map(Value::to_string).collect::<HashSet<String>>()).unwrap_or_default();
+    let (first, second) = (items(first), items(second));
+    let largest = first.len().max(second.len());
+    if largest == 0 {
+        return 0.0;
+    }
+    first.intersection(&second).count() as f64 / largest as f64
+}
+
+fn external_references(first: &Value, second: &Value) -> f64 {
+    const STIX_SOURCES: [&str; 4] = ["veris", "cve", "capec", "mitre-attack"];
+    let (first, second) = match (first.as_array(), second.as_array()) {
+        (Some(first), Some(second)) if !first.is_empty() || !second.is_empty() => (first, second),
+        _ => return 0.0,
+    };
+    let mut matches = 0;
+    for (first, second) in first.iter().zip(second) {
+        let same = |property: &str| first.get(property).is_some() && first.get(property) == second.get(property);
+        let source_name = first.get("source_name").and_then(Value::as_str).filter(|_| same("source_name"));
+        let defined_by_stix = source_name.is_some_and(|source_name| STIX_SOURCES.contains(&source_name));
+        if source_name.is_some() && (same("external_id") || same("url")) && defined_by_stix {
+            return 1.0;
+        }
+        if (source_name.is_some() || same("external_id") || same("url")) && !defined_by_stix {
+            matches += 1;
+        }
+    }
+    matches as f64 / first.len().max(second.len()) as f64
+}
+
+fn coordinates(location: &Value) -> Option<(f64, f64)> {
+    Some((location.get("latitude")?.as_f64()?, location.get("longitude")?.as_f64()?))
+}
+
+/// The closeness of two locations given by their latitude and longitude, through the haversine distance between them.
+fn location_distance((first_latitude, first_longitude): (f64, f64), (second_latitude, second_longitude): (f64, f64), threshold_km: f64) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0088;
+    let (first_latitude, second_latitude) = (first_latitude.to_radians(), second_latitude.to_radians());
+    let latitude_delta = second_latitude - first_latitude;
+    let longitude_delta = (second_longitude - first_longitude).to_radians();
+    let a = (latitude_delta / 2.0).sin().powi(2) + first_latitude.cos() * second_latitude.cos() * (longitude_delta / 2.0).sin().powi(2);
+    let distance = 2.0 * EARTH_RADIUS_KM * a.sqrt().asin();
+    (1.0 - distance / threshold_km).max(0.0)
+}
@@ -0,0 +1,10 @@
+/// This module defines de Rust structures that represent the STIX Relationship Objects, a. k. a. SROs, as defined in [Section 5 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+/// The set of STIX Relationship Objects of this crate includes the following structures, being the name of the struct itself specified between brackets:
+///
+/// - Relationship (`Relationship`)
+/// - Sighting (`Sighting`)
+///
+/// SROs connect the SDOs together, turning a collection of objects into a graph that can be navigated (see the `graph::navigation` module).
+/// Like the SDOs, they can be built property by property (e. g. `Relationship::builder().relationship_type("uses").source_ref(…).target_ref(…).build()?`, see `sdos::builder`).
+pub mod relationship;
+pub mod sighting;
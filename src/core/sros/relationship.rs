@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use validator::{Validate, ValidationError};
+use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
+use crate::core::types::{ExternalReference, GranularMarking};
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+#[validate(schema(function = "validate_times"))]
+pub struct Relationship {
+    // Required common properties
+    pub id: String,
+    pub spec_version: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_references: Option<Vec<ExternalReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    // Specific properties
+    pub relationship_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub source_ref: String,
+    pub target_ref: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_time: Option<DateTime<Utc>>
+}
+
+#[typetag::serde(name = "relationship")]
+impl STIXObject for Relationship {}
+
+/// Rejects the relationships that stop before they start.
+fn validate_times(relationship: &Relationship) -> Result<(), ValidationError> {
+    if let (Some(start_time), Some(stop_time)) = (relationship.start_time, relationship.stop_time) {
+        if stop_time <= start_time {
+            return Err(ValidationError::new("stop_time_before_start_time"));
+        }
+    }
+    Ok(())
+}
+
+sdo_builder!(Relationship, RelationshipBuilder, "relationship", {
+    relationship_type: String,
+    source_ref: String,
+    target_ref: String,
+}, {
+    description: String,
+    start_time: DateTime<Utc>,
+    stop_time: DateTime<Utc>,
+});
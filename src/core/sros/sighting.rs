@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use validator::{Validate, ValidationError};
+use crate::core::STIXObject;
+use crate::core::sdos::builder::sdo_builder;
+use crate::core::types::{ExternalReference, GranularMarking};
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+#[validate(schema(function = "validate_sighting"))]
+pub struct Sighting {
+    // Required common properties
+    pub id: String,
+    pub spec_version: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    // Optional common properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_references: Option<Vec<ExternalReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_marking_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granular_markings: Option<Vec<GranularMarking>>,
+    // Specific properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<DateTime<Utc>>,
+    #[validate(range(min = 0, max = 999999999))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    pub sighting_of_ref: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observed_data_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub where_sighted_refs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<bool>
+}
+
+#[typetag::serde(name = "sighting")]
+impl STIXObject for Sighting {}
+
+/// Rejects the sightings last seen before they were first seen, or whose observed data are not `observed-data` objects.
+fn validate_sighting(sighting: &Sighting) -> Result<(), ValidationError> {
+    if let (Some(first_seen), Some(last_seen)) = (sighting.first_seen, sighting.last_seen) {
+        if last_seen < first_seen {
+            return Err(ValidationError::new("last_seen_before_first_seen"));
+        }
+    }
+    if sighting.observed_data_refs.iter().flatten().any(|id| !id.starts_with("observed-data--")) {
+        return Err(ValidationError::new("observed_data_refs_of_other_type"));
+    }
+    Ok(())
+}
+
+sdo_builder!(Sighting, SightingBuilder, "sighting", {
+    sighting_of_ref: String,
+}, {
+    description: String,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    count: u64,
+    observed_data_refs: Vec<String>,
+    where_sighted_refs: Vec<String>,
+    summary: bool,
+});
@@ -0,0 +1,216 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::stores::{version_key, Store, StoreError};
+use crate::core::stores::filter::{self, Filter, Operator};
+
+/// Keeps the objects as JSON files, one per version, in the layout of python-stix2:
+/// `<root>/<type>/<id>/<modified>.json`, the timestamp written as `YYYYmmddHHMMSSffffff` (i. e. down to the microsecond),
+/// or `<root>/<type>/<id>.json` for the objects without `modified`. Files holding a bundle are also read, as older versions of python-stix2 wrote them.
+#[derive(Debug)]
+pub struct FileSystemStore {
+    root: PathBuf,
+}
+
+impl FileSystemStore {
+    /// Opens the store kept in the given directory, which is created when the first object is added.
+    pub fn new(root: impl Into<PathBuf>) -> FileSystemStore {
+        FileSystemStore { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Removes a single version of an object.
+    pub fn remove_version(&mut self, id: &str, modified: &DateTime<Utc>) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        let path = self.version_path(id, modified)?;
+        let object = match read(&path, id)? {
+            Some(object) => object,
+            None => return Ok(None),
+        };
+        fs::remove_file(&path)?;
+        let directory = self.directory(id)?;
+        if entries(&directory)?.is_empty() {
+            fs::remove_dir(directory)?;
+        }
+        Ok(Some(object))
+    }
+
+    /// The paths are built from the identifiers, which are checked first so that none of them leads out of the root.
+    fn directory(&self, id: &str) -> Result<PathBuf, StoreError> {
+        check_id(id)?;
+        Ok(self.root.join(object_type(id)).join(id))
+    }
+
+    fn version_path(&self, id: &str, modified: &DateTime<Utc>) -> Result<PathBuf, StoreError> {
+        Ok(self.directory(id)?.join(format!("{}.json", modified.format("%Y%m%d%H%M%S%6f"))))
+    }
+
+    fn unversioned_path(&self, id: &str) -> Result<PathBuf, StoreError> {
+        check_id(id)?;
+        Ok(self.root.join(object_type(id)).join(format!("{}.json", id)))
+    }
+
+    /// Returns the files of the versions of an object, from the oldest to the latest.
+    fn version_paths(&self, id: &str) -> Result<Vec<PathBuf>, StoreError> {
+        let mut paths: Vec<PathBuf> = entries(&self.directory(id)?)?.into_iter().filter(|path| is_json(path)).collect();
+        paths.sort();
+        let unversioned = self.unversioned_path(id)?;
+        if unversioned.is_file() {
+            paths.insert(0, unversioned);
+        }
+        Ok(paths)
+    }
+
+    /// Returns the identifiers of the objects of a type.
+    fn ids_of_type(&self, object_type: &str) -> Result<Vec<String>, StoreError> {
+        let mut ids: Vec<String> = entries(&self.root.join(object_type))?
+            .into_iter()
+            .filter_map(|path| match path.file_name()?.to_str()? {
+                name if path.is_dir() => Some(name.to_string()),
+                name => name.strip_suffix(".json").map(str::to_string),
+            })
+            .collect();
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    /// Returns the types whose directories have to be looked through to satisfy the filters. The types no object can have are left out.
+    fn types(&self, filters: &[Filter]) -> Result<Vec<String>, StoreError> {
+        for filter in filters.iter().filter(|filter| filter.property == "type") {
+            let types = match (filter.operator, &filter.value) {
+                (Operator::Equal, Value::String(object_type)) => vec![object_type.as_str()],
+                (Operator::In, Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+                _ => continue,
+            };
+            return Ok(types.into_iter().filter(|object_type| is_valid_type(object_type)).map(str::to_string).collect());
+        }
+        let types = entries(&self.root)?.into_iter().filter(|path| path.is_dir());
+        Ok(types.filter_map(|path| Some(path.file_name()?.to_str()?.to_string())).collect())
+    }
+}
+
+impl Store for FileSystemStore {
+    fn add(&mut self, object: Box<dyn STIXObject>) -> Result<(), StoreError> {
+        let (id, modified) = version_key(object.as_ref())?;
+        let path = match &modified {
+            Some(modified) => self.version_path(&id, modified)?,
+            None => self.unversioned_path(&id)?,
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let text = serde_json::to_string_pretty(&object.to_value()).expect("STIX objects are always serializable");
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        match self.version_paths(id)?.last() {
+            Some(path) => read(path, id),
+            None => Ok(None),
+        }
+    }
+
+    fn get_version(&self, id: &str, modified: &DateTime<Utc>) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        read(&self.version_path(id, modified)?, id)
+    }
+
+    fn all_versions(&self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let mut versions = Vec::new();
+        for path in self.version_paths(id)? {
+            versions.extend(read(&path, id)?);
+        }
+        Ok(versions)
+    }
+
+    fn remove(&mut self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let versions = self.all_versions(id)?;
+        let directory = self.directory(id)?;
+        if directory.is_dir() {
+            fs::remove_dir_all(directory)?;
+        }
+        let unversioned = self.unversioned_path(id)?;
+        if unversioned.is_file() {
+            fs::remove_file(unversioned)?;
+        }
+        Ok(versions)
+    }
+
+    fn ids(&self) -> Result<Vec<String>, StoreError> {
+        let mut ids = Vec::new();
+        for object_type in self.types(&[])? {
+            ids.extend(self.ids_of_type(&object_type)?);
+        }
+        Ok(ids)
+    }
+
+    /// Only the directories of the types filtered, if any, are looked through.
+    fn query(&self, filters: &[Filter]) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let mut found = Vec::new();
+        for object_type in self.types(filters)? {
+            for id in self.ids_of_type(&object_type)? {
+                if let Some(object) = self.get(&id)? {
+                    if filter::matches_all(filters, &object.to_value()) {
+                        found.push(object);
+                    }
+                }
+            }
+        }
+        Ok(found)
+    }
+}
+
+/// The type of an object, as found in its identifier.
+fn object_type(id: &str) -> &str {
+    id.split("--").next().unwrap_or_default()
+}
+
+/// Whether a type is made of the characters the types are made of, which makes it a plain directory name.
+fn is_valid_type(object_type: &str) -> bool {
+    !object_type.is_empty() && object_type.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Checks that an identifier is a type followed by `--` and letters, digits or hyphens (usually a UUID),
+/// which rules out the separators, `..` and the absolute paths.
+fn check_id(id: &str) -> Result<(), StoreError> {
+    match id.split_once("--") {
+        Some((object_type, rest)) if is_valid_type(object_type) && !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') => Ok(()),
+        _ => Err(StoreError::InvalidId(id.to_string())),
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "json")
+}
+
+/// Lists the entries of a directory, a missing directory being empty.
+fn entries(directory: &Path) -> Result<Vec<PathBuf>, StoreError> {
+    match fs::read_dir(directory) {
+        Ok(entries) => Ok(entries.map(|entry| entry.map(|entry| entry.path())).collect::<Result<_, _>>()?),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Reads the object with the given identifier from a file, holding either the object itself or a bundle with it.
+fn read(path: &Path, id: &str) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let invalid = || StoreError::InvalidContent(path.display().to_string());
+    let mut value: Value = serde_json::from_str(&text).map_err(|_| invalid())?;
+    if value.get("type").and_then(Value::as_str) == Some("bundle") {
+        let objects = value.get_mut("objects").and_then(Value::as_array_mut).ok_or_else(invalid)?;
+        let position = objects.iter().position(|object| object.get("id").and_then(Value::as_str) == Some(id)).ok_or_else(invalid)?;
+        value = objects.swap_remove(position);
+    }
+    <dyn STIXObject>::from_value(value).map(Some).map_err(|_| invalid())
+}
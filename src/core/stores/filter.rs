@@ -0,0 +1,123 @@
+use std::cmp::Ordering;
+use chrono::DateTime;
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    /// The property equals one of the values of the list given.
+    In,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+    /// The property is a list holding the value given, or a string holding it as a substring.
+    Contains,
+}
+
+/// A condition on a property of the objects, e. g. `Filter::new("is_family", Operator::Equal, true)`.
+///
+/// The property is a dotted path through the nested properties (e. g. `kill_chain_phases.phase_name`), where lists are
+/// looked through: the filter holds if any of the values found satisfies it. Numeric segments select an item of a list instead.
+/// Timestamps are compared as such, whatever their precision, and objects lacking the property never match, not even with `!=`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    pub property: String,
+    pub operator: Operator,
+    pub value: Value,
+}
+
+impl Filter {
+    pub fn new(property: &str, operator: Operator, value: impl Into<Value>) -> Filter {
+        Filter {
+            property: property.to_string(),
+            operator,
+            value: value.into(),
+        }
+    }
+
+    /// Whether the object, given as its JSON representation, satisfies the filter.
+    pub fn matches(&self, object: &Value) -> bool {
+        let mut found = Vec::new();
+        resolve(object, &self.property.split('.').collect::<Vec<_>>(), &mut found);
+        if found.is_empty() {
+            return false;
+        }
+        match self.operator {
+            Operator::NotEqual => !found.iter().flat_map(|value| items(value)).any(|value| equals(value, &self.value)),
+            Operator::Contains => found.iter().any(|value| match value {
+                Value::Array(values) => values.iter().any(|item| equals(item, &self.value)),
+                Value::String(text) => self.value.as_str().is_some_and(|part| text.contains(part)),
+                _ => false,
+            }),
+            _ => found.iter().flat_map(|value| items(value)).any(|value| self.holds_for(value)),
+        }
+    }
+
+    fn holds_for(&self, value: &Value) -> bool {
+        match self.operator {
+            Operator::Equal => equals(value, &self.value),
+            Operator::In => self.value.as_array().is_some_and(|values| values.iter().any(|item| equals(value, item))),
+            Operator::LessThan => compare(value, &self.value) == Some(Ordering::Less),
+            Operator::LessOrEqual => matches!(compare(value, &self.value), Some(Ordering::Less | Ordering::Equal)),
+            Operator::GreaterThan => compare(value, &self.value) == Some(Ordering::Greater),
+            Operator::GreaterOrEqual => matches!(compare(value, &self.value), Some(Ordering::Greater | Ordering::Equal)),
+            Operator::NotEqual | Operator::Contains => unreachable!("handled on the whole property"),
+        }
+    }
+}
+
+/// Whether the object satisfies every filter.
+pub fn matches_all(filters: &[Filter], object: &Value) -> bool {
+    filters.iter().all(|filter| filter.matches(object))
+}
+
+/// Collects the values found at the end of the path, looking through the lists met on the way.
+fn resolve<'a>(value: &'a Value, path: &[&str], found: &mut Vec<&'a Value>) {
+    let (segment, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return found.push(value),
+    };
+    match value {
+        Value::Object(properties) => {
+            if let Some(value) = properties.get(*segment) {
+                resolve(value, rest, found);
+            }
+        }
+        Value::Array(values) => match segment.parse::<usize>() {
+            Ok(index) => {
+                if let Some(value) = values.get(index) {
+                    resolve(value, rest, found);
+                }
+            }
+            Err(_) => values.iter().for_each(|value| resolve(value, path, found)),
+        },
+        _ => {}
+    }
+}
+
+/// The values to compare: the items of a list, or the value itself.
+fn items(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(values) => values.iter().collect(),
+        _ => vec![value],
+    }
+}
+
+fn equals(value: &Value, other: &Value) -> bool {
+    value == other || compare(value, other) == Some(Ordering::Equal)
+}
+
+/// Orders numbers, timestamps and strings. Other values, or values of different kinds, are not ordered.
+fn compare(value: &Value, other: &Value) -> Option<Ordering> {
+    match (value, other) {
+        (Value::Number(value), Value::Number(other)) => value.as_f64()?.partial_cmp(&other.as_f64()?),
+        (Value::String(value), Value::String(other)) => match (DateTime::parse_from_rfc3339(value), DateTime::parse_from_rfc3339(other)) {
+            (Ok(value), Ok(other)) => Some(value.cmp(&other)),
+            _ => Some(value.cmp(other)),
+        },
+        (Value::Bool(value), Value::Bool(other)) => Some(value.cmp(other)),
+        _ => None,
+    }
+}
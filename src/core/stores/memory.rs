@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::core::STIXObject;
+use crate::core::bundle::Bundle;
+use crate::core::stores::{version_key, Store, StoreError, Versions};
+use crate::core::stores::filter::{self, Filter};
+
+/// Holds every version of the objects added, ordered by their `modified` timestamp.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    objects: HashMap<String, Versions>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+
+    /// Adds an object, replacing the version with the same `modified` timestamp if it was already stored.
+    pub fn add(&mut self, object: Box<dyn STIXObject>) -> Result<(), StoreError> {
+        let (id, modified) = version_key(object.as_ref())?;
+        self.objects.entry(id).or_default().insert(modified, object);
+        Ok(())
+    }
+
+    /// Adds every object of a bundle. Objects are checked before any of them is added, so a failure leaves the store unchanged.
+    pub fn add_bundle(&mut self, bundle: Bundle) -> Result<(), StoreError> {
+        let keys = bundle.objects.iter().map(|object| version_key(object.as_ref())).collect::<Result<Vec<_>, _>>()?;
+        for ((id, modified), object) in keys.into_iter().zip(bundle.objects) {
+            self.objects.entry(id).or_default().insert(modified, object);
+        }
+        Ok(())
+    }
+
+    /// Returns the latest version of an object.
+    pub fn get(&self, id: &str) -> Option<&dyn STIXObject> {
+        self.objects.get(id)?.values().next_back().map(AsRef::as_ref)
+    }
+
+    /// Returns the version of an object last modified at the given time.
+    pub fn get_version(&self, id: &str, modified: &DateTime<Utc>) -> Option<&dyn STIXObject> {
+        self.objects.get(id)?.get(&Some(*modified)).map(AsRef::as_ref)
+    }
+
+    /// Returns every version of an object, from the oldest to the latest.
+    pub fn all_versions(&self, id: &str) -> Vec<&dyn STIXObject> {
+        self.objects.get(id).map(|versions| versions.values().map(AsRef::as_ref).collect()).unwrap_or_default()
+    }
+
+    /// Removes every version of an object, returning them from the oldest to the latest.
+    pub fn remove(&mut self, id: &str) -> Vec<Box<dyn STIXObject>> {
+        self.objects.remove(id).map(|versions| versions.into_values().collect()).unwrap_or_default()
+    }
+
+    /// Removes a single version of an object.
+    pub fn remove_version(&mut self, id: &str, modified: &DateTime<Utc>) -> Option<Box<dyn STIXObject>> {
+        let versions = self.objects.get_mut(id)?;
+        let removed = versions.remove(&Some(*modified));
+        if versions.is_empty() {
+            self.objects.remove(id);
+        }
+        removed
+    }
+
+    /// Returns the identifiers of the objects stored.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.objects.keys().map(String::as_str)
+    }
+
+    /// Returns the latest version of every object stored.
+    pub fn latest(&self) -> impl Iterator<Item = &dyn STIXObject> {
+        self.objects.values().filter_map(|versions| versions.values().next_back().map(AsRef::as_ref))
+    }
+
+    /// Returns the latest version of the objects satisfying every filter.
+    pub fn query(&self, filters: &[Filter]) -> Vec<&dyn STIXObject> {
+        self.latest().filter(|object| filter::matches_all(filters, &object.to_value())).collect()
+    }
+
+    /// Returns the number of objects stored, regardless of their versions.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
+
+/// Copies an object by rebuilding it from its JSON representation.
+fn copy(object: &dyn STIXObject) -> Box<dyn STIXObject> {
+    <dyn STIXObject>::from_value(object.to_value()).expect("stored objects are always deserializable")
+}
+
+impl Store for MemoryStore {
+    fn add(&mut self, object: Box<dyn STIXObject>) -> Result<(), StoreError> {
+        MemoryStore::add(self, object)
+    }
+
+    fn add_bundle(&mut self, bundle: Bundle) -> Result<(), StoreError> {
+        MemoryStore::add_bundle(self, bundle)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        Ok(MemoryStore::get(self, id).map(copy))
+    }
+
+    fn get_version(&self, id: &str, modified: &DateTime<Utc>) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        Ok(MemoryStore::get_version(self, id, modified).map(copy))
+    }
+
+    fn all_versions(&self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        Ok(MemoryStore::all_versions(self, id).into_iter().map(copy).collect())
+    }
+
+    fn remove(&mut self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        Ok(MemoryStore::remove(self, id))
+    }
+
+    fn ids(&self) -> Result<Vec<String>, StoreError> {
+        Ok(MemoryStore::ids(self).map(str::to_string).collect())
+    }
+
+    fn query(&self, filters: &[Filter]) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        Ok(MemoryStore::query(self, filters).into_iter().map(copy).collect())
+    }
+}
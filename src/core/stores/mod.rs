@@ -0,0 +1,113 @@
+/// This module provides stores that hold the STIX objects once parsed, keeping every version of them.
+/// Versions are told apart by their `modified` timestamp, as described in [Section 3.6 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+/// Objects without `modified` (e. g. SCOs) have a single version, replaced whenever the object is added again.
+/// The objects of a store are looked up by their identifier or queried through the `filter` module.
+///
+/// Every store implements the `Store` trait, so the features working on collections of objects do not depend on where they are kept:
+/// - `memory::MemoryStore` keeps the objects in memory, also lending them through its own methods.
+/// - `filesystem::FileSystemStore` keeps them as JSON files, in the same layout as the `FileSystemSource` and `FileSystemSink` of python-stix2.
+/// - `sqlite::SqliteStore` keeps them in an SQLite database indexing their main properties and references, for the large collections.
+///   It is only available with the `sqlite` feature.
+///
+/// ```
+/// use stix4rust::core::stores::memory::MemoryStore;
+///
+/// let mut store = MemoryStore::new();
+/// store.add(serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#).unwrap()).unwrap();
+/// assert!(store.get("domain-name--1").is_some());
+/// ```
+pub mod filter;
+pub mod filesystem;
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::bundle::Bundle;
+use crate::core::stores::filter::Filter;
+
+#[derive(Debug, PartialEq)]
+pub enum StoreError {
+    /// An object without `id` was given.
+    MissingId,
+    /// This identifier is not of the form `<type>--<uuid>`, so it cannot be stored or looked up.
+    InvalidId(String),
+    /// The `modified` timestamp of the object with this identifier is not valid.
+    InvalidModified(String),
+    /// The storage could not be read or written, with the reason.
+    Io(String),
+    /// The content stored at this location is not a STIX object.
+    InvalidContent(String),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::MissingId => write!(f, "the object has no id"),
+            StoreError::InvalidId(id) => write!(f, "{} is not a valid identifier", id),
+            StoreError::InvalidModified(id) => write!(f, "the modified timestamp of {} is not valid", id),
+            StoreError::Io(reason) => write!(f, "the store could not be accessed: {}", reason),
+            StoreError::InvalidContent(location) => write!(f, "{} does not hold a STIX object", location),
+        }
+    }
+}
+
+impl Error for StoreError {}
+
+impl From<std::io::Error> for StoreError {
+    fn from(error: std::io::Error) -> StoreError {
+        StoreError::Io(error.to_string())
+    }
+}
+
+/// The operations shared by every store. The objects are returned as owned copies, since most stores have to load them.
+pub trait Store {
+    /// Adds an object, replacing the version with the same `modified` timestamp if it was already stored.
+    fn add(&mut self, object: Box<dyn STIXObject>) -> Result<(), StoreError>;
+
+    /// Adds every object of a bundle.
+    fn add_bundle(&mut self, bundle: Bundle) -> Result<(), StoreError> {
+        bundle.objects.into_iter().try_for_each(|object| self.add(object))
+    }
+
+    /// Returns the latest version of an object.
+    fn get(&self, id: &str) -> Result<Option<Box<dyn STIXObject>>, StoreError>;
+
+    /// Returns the version of an object last modified at the given time.
+    fn get_version(&self, id: &str, modified: &DateTime<Utc>) -> Result<Option<Box<dyn STIXObject>>, StoreError>;
+
+    /// Returns every version of an object, from the oldest to the latest.
+    fn all_versions(&self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError>;
+
+    /// Removes every version of an object, returning them from the oldest to the latest.
+    fn remove(&mut self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError>;
+
+    /// Returns the identifiers of the objects stored.
+    fn ids(&self) -> Result<Vec<String>, StoreError>;
+
+    /// Returns the latest version of the objects satisfying every filter.
+    fn query(&self, filters: &[Filter]) -> Result<Vec<Box<dyn STIXObject>>, StoreError>;
+}
+
+/// The versions of an object, by `modified` timestamp.
+pub(crate) type Versions = BTreeMap<Option<DateTime<Utc>>, Box<dyn STIXObject>>;
+
+/// Returns the identifier and the `modified` timestamp (if any) of an object, which together identify one of its versions.
+pub(crate) fn version_key(object: &dyn STIXObject) -> Result<(String, Option<DateTime<Utc>>), StoreError> {
+    let value = object.to_value();
+    let id = value.get("id").and_then(Value::as_str).ok_or(StoreError::MissingId)?;
+    let modified = match value.get("modified") {
+        Some(Value::String(modified)) => {
+            let modified = DateTime::parse_from_rfc3339(modified).map_err(|_| StoreError::InvalidModified(id.to_string()))?;
+            Some(modified.with_timezone(&Utc))
+        }
+        Some(_) => return Err(StoreError::InvalidModified(id.to_string())),
+        None => None,
+    };
+    Ok((id.to_string(), modified))
+}
@@ -0,0 +1,233 @@
+use std::path::Path;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde_json::Value;
+use crate::core::STIXObject;
+use crate::core::bundle::Bundle;
+use crate::core::graph::navigation;
+use crate::core::stores::{version_key, Store, StoreError};
+use crate::core::stores::filter::{self, Filter, Operator};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS objects (
+        id TEXT NOT NULL,
+        modified TEXT NOT NULL,
+        type TEXT NOT NULL,
+        created_by_ref TEXT,
+        object TEXT NOT NULL,
+        PRIMARY KEY (id, modified)
+    );
+    CREATE INDEX IF NOT EXISTS objects_type ON objects (type);
+    CREATE INDEX IF NOT EXISTS objects_modified ON objects (modified);
+    CREATE INDEX IF NOT EXISTS objects_created_by_ref ON objects (created_by_ref);
+    CREATE TABLE IF NOT EXISTS refs (
+        id TEXT NOT NULL,
+        modified TEXT NOT NULL,
+        property TEXT NOT NULL,
+        target TEXT NOT NULL,
+        FOREIGN KEY (id, modified) REFERENCES objects (id, modified) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS refs_source ON refs (id, modified);
+    CREATE INDEX IF NOT EXISTS refs_target ON refs (target, property);
+    PRAGMA foreign_keys = ON;
+";
+
+/// The condition selecting the latest version of each object, for the queries run on the `objects` table aliased as `o`.
+const LATEST: &str = "o.modified = (SELECT MAX(modified) FROM objects WHERE id = o.id)";
+
+/// Keeps the objects in an SQLite database, available with the `sqlite` feature.
+///
+/// Besides the object itself, each version gets its `type`, `modified` and `created_by_ref` indexed, along with the targets
+/// of every `*_ref` and `*_refs` property, nested ones included (e. g. `granular_markings.marking_ref`).
+/// The filters on these properties are run by the database, while the others are checked on the objects it selects.
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(error: rusqlite::Error) -> StoreError {
+        StoreError::Io(error.to_string())
+    }
+}
+
+impl SqliteStore {
+    /// Opens the database kept in the given file, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<SqliteStore, StoreError> {
+        SqliteStore::with_connection(Connection::open(path)?)
+    }
+
+    /// Opens a database living in memory, lost once the store is dropped.
+    pub fn open_in_memory() -> Result<SqliteStore, StoreError> {
+        SqliteStore::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<SqliteStore, StoreError> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(SqliteStore { connection })
+    }
+
+    /// Removes a single version of an object.
+    pub fn remove_version(&mut self, id: &str, modified: &DateTime<Utc>) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        let object = self.get_version(id, modified)?;
+        self.connection.execute("DELETE FROM objects WHERE id = ?1 AND modified = ?2", params![id, modified_key(Some(modified))])?;
+        Ok(object)
+    }
+
+    /// Returns the latest version of the objects referring to the given identifier through any of their `*_ref` or `*_refs` properties.
+    pub fn referencing(&self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let sql = format!(
+            "SELECT o.id, o.object FROM objects o WHERE {} AND EXISTS (SELECT 1 FROM refs r WHERE r.id = o.id AND r.modified = o.modified AND r.target = ?1)",
+            LATEST
+        );
+        self.select(&sql, &[id.to_string()])
+    }
+
+    fn select(&self, sql: &str, parameters: &[String]) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let mut statement = self.connection.prepare(sql)?;
+        let rows = statement.query_map(params_from_iter(parameters), |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        let mut objects = Vec::new();
+        for row in rows {
+            let (id, text) = row?;
+            objects.push(parse(&id, &text)?);
+        }
+        Ok(objects)
+    }
+}
+
+/// Adds an object through a connection or a transaction.
+fn insert(connection: &Connection, object: &dyn STIXObject) -> Result<(), StoreError> {
+    let (id, modified) = version_key(object)?;
+    let value = object.to_value();
+    let modified = modified_key(modified.as_ref());
+    connection.execute("DELETE FROM objects WHERE id = ?1 AND modified = ?2", params![id, modified])?;
+    connection.execute(
+        "INSERT INTO objects (id, modified, type, created_by_ref, object) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, modified, value.get("type").and_then(Value::as_str), value.get("created_by_ref").and_then(Value::as_str), value.to_string()],
+    )?;
+    let mut statement = connection.prepare_cached("INSERT INTO refs (id, modified, property, target) VALUES (?1, ?2, ?3, ?4)")?;
+    for (property, target) in navigation::references(&value) {
+        statement.execute(params![id, modified, property, target])?;
+    }
+    Ok(())
+}
+
+impl Store for SqliteStore {
+    /// The version is replaced in a single transaction, so a failure leaves the version stored before.
+    fn add(&mut self, object: Box<dyn STIXObject>) -> Result<(), StoreError> {
+        let transaction = self.connection.transaction()?;
+        insert(&transaction, object.as_ref())?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// The objects are added in a single transaction, so a failure leaves the store unchanged.
+    fn add_bundle(&mut self, bundle: Bundle) -> Result<(), StoreError> {
+        let transaction = self.connection.transaction()?;
+        for object in &bundle.objects {
+            insert(&transaction, object.as_ref())?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        let text: Option<String> = self
+            .connection
+            .query_row("SELECT object FROM objects WHERE id = ?1 ORDER BY modified DESC LIMIT 1", params![id], |row| row.get(0))
+            .optional()?;
+        text.map(|text| parse(id, &text)).transpose()
+    }
+
+    fn get_version(&self, id: &str, modified: &DateTime<Utc>) -> Result<Option<Box<dyn STIXObject>>, StoreError> {
+        let text: Option<String> = self
+            .connection
+            .query_row("SELECT object FROM objects WHERE id = ?1 AND modified = ?2", params![id, modified_key(Some(modified))], |row| row.get(0))
+            .optional()?;
+        text.map(|text| parse(id, &text)).transpose()
+    }
+
+    fn all_versions(&self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        self.select("SELECT id, object FROM objects WHERE id = ?1 ORDER BY modified", &[id.to_string()])
+    }
+
+    fn remove(&mut self, id: &str) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let versions = self.all_versions(id)?;
+        self.connection.execute("DELETE FROM objects WHERE id = ?1", params![id])?;
+        Ok(versions)
+    }
+
+    fn ids(&self) -> Result<Vec<String>, StoreError> {
+        let mut statement = self.connection.prepare("SELECT DISTINCT id FROM objects ORDER BY id")?;
+        let ids = statement.query_map([], |row| row.get(0))?.collect::<Result<_, _>>()?;
+        Ok(ids)
+    }
+
+    fn query(&self, filters: &[Filter]) -> Result<Vec<Box<dyn STIXObject>>, StoreError> {
+        let mut conditions = vec![LATEST.to_string()];
+        let mut parameters = Vec::new();
+        for filter in filters {
+            if let Some(condition) = indexed_condition(filter, &mut parameters) {
+                conditions.push(condition);
+            }
+        }
+        let sql = format!("SELECT o.id, o.object FROM objects o WHERE {}", conditions.join(" AND "));
+        let objects = self.select(&sql, &parameters)?;
+        Ok(objects.into_iter().filter(|object| filter::matches_all(filters, &object.to_value())).collect())
+    }
+}
+
+/// Translates a filter on an indexed property into an SQL condition, whose parameters are appended.
+/// The condition never rejects an object satisfying the filter, but may keep some that do not: the filters are checked again on the objects selected.
+fn indexed_condition(filter: &Filter, parameters: &mut Vec<String>) -> Option<String> {
+    let column = match filter.property.as_str() {
+        "id" | "type" | "created_by_ref" => filter.property.as_str(),
+        "modified" => {
+            let timestamp = filter.value.as_str().and_then(|value| DateTime::parse_from_rfc3339(value).ok())?;
+            let operator = match filter.operator {
+                Operator::Equal => "=",
+                Operator::LessThan => "<",
+                Operator::LessOrEqual => "<=",
+                Operator::GreaterThan => ">",
+                Operator::GreaterOrEqual => ">=",
+                _ => return None,
+            };
+            parameters.push(modified_key(Some(&timestamp.with_timezone(&Utc))));
+            return Some(format!("o.modified != '' AND o.modified {} ?{}", operator, parameters.len()));
+        }
+        property if property.ends_with("_refs") || (property.ends_with("_ref") && filter.operator == Operator::Equal) => {
+            let target = filter.value.as_str()?;
+            if filter.operator != Operator::Equal && filter.operator != Operator::Contains {
+                return None;
+            }
+            parameters.push(property.to_string());
+            parameters.push(target.to_string());
+            return Some(format!(
+                "EXISTS (SELECT 1 FROM refs r WHERE r.id = o.id AND r.modified = o.modified AND r.property = ?{} AND r.target = ?{})",
+                parameters.len() - 1,
+                parameters.len()
+            ));
+        }
+        _ => return None,
+    };
+    let values: Vec<&str> = match (filter.operator, &filter.value) {
+        (Operator::Equal, Value::String(value)) => vec![value],
+        (Operator::In, Value::Array(values)) if values.iter().all(Value::is_string) => values.iter().filter_map(Value::as_str).collect(),
+        _ => return None,
+    };
+    let mut placeholders = Vec::new();
+    for value in values {
+        parameters.push(value.to_string());
+        placeholders.push(format!("?{}", parameters.len()));
+    }
+    Some(format!("o.{} IN ({})", column, placeholders.join(", ")))
+}
+
+/// The `modified` timestamp as stored: written with a fixed precision so that the texts sort as the timestamps do, or empty for the objects without it.
+fn modified_key(modified: Option<&DateTime<Utc>>) -> String {
+    modified.map(|modified| modified.to_rfc3339_opts(SecondsFormat::Nanos, true)).unwrap_or_default()
+}
+
+fn parse(id: &str, text: &str) -> Result<Box<dyn STIXObject>, StoreError> {
+    serde_json::from_str(text).map_err(|_| StoreError::InvalidContent(id.to_string()))
+}
@@ -8,7 +8,7 @@ use validator::{
     // ValidationError
 };
 
-#[derive(Serialize, Deserialize, Validate, Debug)]
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
 pub struct ExternalReference {  
     pub source_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use chrono::{DateTime, Duration, SecondsFormat, SubsecRound, Utc};
+use serde_json::{Map, Value};
+use crate::core::STIXObject;
+use crate::core::stores::{Store, StoreError};
+
+/// The properties that stay the same through the versions of an object, as required by [Section 3.6 of the Stix 2.1](https://docs.oasis-open.org/cti/stix/v2.1/csprd01/stix-v2.1-csprd01.html).
+pub const UNMODIFIABLE_PROPERTIES: [&str; 4] = ["id", "type", "created", "created_by_ref"];
+
+#[derive(Debug, PartialEq)]
+pub enum VersioningError {
+    /// The object with this identifier has no `modified` property (e. g. an SCO), so it cannot be versioned.
+    NotVersionable(String),
+    /// The object with this identifier has been revoked, so no further version can be made.
+    Revoked(String),
+    /// The changes would modify this property, which must stay the same through the versions.
+    UnmodifiableProperty(String),
+    /// The `modified` timestamp given is not later than that of the current version.
+    ModifiedNotNewer,
+    /// The changes are not a JSON object, or the new version they give is not a valid object, with the reason.
+    InvalidChanges(String),
+}
+
+impl fmt::Display for VersioningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VersioningError::NotVersionable(id) => write!(f, "{} has no modified timestamp and cannot be versioned", id),
+            VersioningError::Revoked(id) => write!(f, "{} is revoked and cannot be versioned", id),
+            VersioningError::UnmodifiableProperty(property) => write!(f, "the {} property cannot change between versions", property),
+            VersioningError::ModifiedNotNewer => write!(f, "the modified timestamp of a new version must be later than the current one"),
+            VersioningError::InvalidChanges(reason) => write!(f, "invalid changes: {}", reason),
+        }
+    }
+}
+
+impl Error for VersioningError {}
+
+/// Makes a new version of an object, applying the changes given as a JSON object whose `null` values remove the properties.
+///
+/// The `modified` timestamp is set to now (at millisecond precision, and always later than the current one), unless the changes give a later one.
+/// The identifier, type, creation time and creator cannot change, and revoked objects cannot be versioned.
+pub fn new_version(object: &dyn STIXObject, changes: Value) -> Result<Box<dyn STIXObject>, VersioningError> {
+    let mut value = object.to_value();
+    let id = value.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+    let modified = value.get("modified").and_then(Value::as_str).and_then(|modified| DateTime::parse_from_rfc3339(modified).ok());
+    let modified = modified.ok_or_else(|| VersioningError::NotVersionable(id.clone()))?.with_timezone(&Utc);
+    if value.get("revoked") == Some(&Value::Bool(true)) {
+        return Err(VersioningError::Revoked(id));
+    }
+    let changes = match changes {
+        Value::Object(changes) => changes,
+        _ => return Err(VersioningError::InvalidChanges("the changes must be a JSON object".to_string())),
+    };
+    let properties: &mut Map<String, Value> = value.as_object_mut().expect("STIX objects are JSON objects");
+    for property in UNMODIFIABLE_PROPERTIES.iter() {
+        if changes.get(*property).is_some_and(|change| properties.get(*property) != Some(change)) {
+            return Err(VersioningError::UnmodifiableProperty(property.to_string()));
+        }
+    }
+    let new_modified = match changes.get("modified") {
+        Some(change) => {
+            let change = change.as_str().and_then(|change| DateTime::parse_from_rfc3339(change).ok());
+            let change = change.ok_or_else(|| VersioningError::InvalidChanges("the modified timestamp is not valid".to_string()))?.with_timezone(&Utc);
+            if change <= modified {
+                return Err(VersioningError::ModifiedNotNewer);
+            }
+            change
+        }
+        None => Utc::now().round_subsecs(3).max(modified.round_subsecs(3) + Duration::milliseconds(1)),
+    };
+    for (property, change) in changes {
+        match change {
+            Value::Null => properties.remove(&property),
+            change => properties.insert(property, change),
+        };
+    }
+    properties.insert("modified".to_string(), Value::String(new_modified.to_rfc3339_opts(SecondsFormat::Millis, true)));
+    <dyn STIXObject>::from_value(value).map_err(|error| VersioningError::InvalidChanges(error.to_string()))
+}
+
+/// Makes the last version of an object, marking it as revoked.
+pub fn revoke(object: &dyn STIXObject) -> Result<Box<dyn STIXObject>, VersioningError> {
+    new_version(object, serde_json::json!({"revoked": true}))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// Several copies of this version differ in their content.
+    ConflictingDuplicate,
+    /// The `created` timestamp differs from that of the first version.
+    CreatedChanged,
+    /// The `created_by_ref` differs from that of the first version.
+    CreatorChanged,
+    /// An earlier version was revoked.
+    VersionAfterRevocation,
+    /// The version was modified before the object was created.
+    ModifiedBeforeCreated,
+}
+
+/// A version of an object breaking the versioning rules.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryViolation {
+    pub id: String,
+    pub modified: DateTime<Utc>,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for HistoryViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let problem = match self.kind {
+            ViolationKind::ConflictingDuplicate => "has copies with different contents",
+            ViolationKind::CreatedChanged => "changes the created timestamp",
+            ViolationKind::CreatorChanged => "changes the creator",
+            ViolationKind::VersionAfterRevocation => "follows a revoked version",
+            ViolationKind::ModifiedBeforeCreated => "was modified before being created",
+        };
+        write!(f, "the version of {} modified at {} {}", self.id, self.modified.to_rfc3339_opts(SecondsFormat::AutoSi, true), problem)
+    }
+}
+
+/// Checks the history of the objects of a collection, e. g. a bundle, where several versions of an object can be found.
+/// The objects without `modified` are not versioned, so they are not checked. The violations are sorted by identifier and version.
+pub fn check_history(objects: &[Box<dyn STIXObject>]) -> Vec<HistoryViolation> {
+    let mut histories: BTreeMap<String, Vec<(DateTime<Utc>, Value)>> = BTreeMap::new();
+    for value in objects.iter().map(|object| object.to_value()) {
+        if let (Some(id), Some(modified)) = (value.get("id").and_then(Value::as_str), timestamp(&value, "modified")) {
+            histories.entry(id.to_string()).or_default().push((modified, value));
+        }
+    }
+    histories.into_iter().flat_map(|(id, versions)| check_versions(&id, versions)).collect()
+}
+
+/// Checks the history of every object of a store. Stores keep a single copy of each version, so no conflicting duplicates are found.
+pub fn check_store_history(store: &dyn Store) -> Result<Vec<HistoryViolation>, StoreError> {
+    let mut ids = store.ids()?;
+    ids.sort();
+    let mut violations = Vec::new();
+    for id in ids {
+        let versions = store.all_versions(&id)?.iter().map(|object| object.to_value()).collect::<Vec<_>>();
+        let versions = versions.into_iter().filter_map(|value| Some((timestamp(&value, "modified")?, value))).collect();
+        violations.extend(check_versions(&id, versions));
+    }
+    Ok(violations)
+}
+
+fn check_versions(id: &str, mut versions: Vec<(DateTime<Utc>, Value)>) -> Vec<HistoryViolation> {
+    let violation = |modified: DateTime<Utc>, kind| HistoryViolation { id: id.to_string(), modified, kind };
+    let mut violations = Vec::new();
+    versions.sort_by_key(|(modified, _)| *modified);
+    let mut distinct: Vec<(DateTime<Utc>, Value)> = Vec::new();
+    for (modified, value) in versions {
+        match distinct.last() {
+            Some((last, copy)) if *last == modified => {
+                if *copy != value && violations.last() != Some(&violation(modified, ViolationKind::ConflictingDuplicate)) {
+                    violations.push(violation(modified, ViolationKind::ConflictingDuplicate));
+                }
+            }
+            _ => distinct.push((modified, value)),
+        }
+    }
+    let first = match distinct.first() {
+        Some((_, first)) => first.clone(),
+        None => return violations,
+    };
+    let mut revoked = false;
+    for (modified, value) in &distinct {
+        if timestamp(value, "created").is_some_and(|created| *modified < created) {
+            violations.push(violation(*modified, ViolationKind::ModifiedBeforeCreated));
+        }
+        if timestamp(value, "created") != timestamp(&first, "created") {
+            violations.push(violation(*modified, ViolationKind::CreatedChanged));
+        }
+        if value.get("created_by_ref") != first.get("created_by_ref") {
+            violations.push(violation(*modified, ViolationKind::CreatorChanged));
+        }
+        if revoked {
+            violations.push(violation(*modified, ViolationKind::VersionAfterRevocation));
+        }
+        revoked |= value.get("revoked") == Some(&Value::Bool(true));
+    }
+    violations.sort_by_key(|violation| violation.modified);
+    violations
+}
+
+fn timestamp(value: &Value, property: &str) -> Option<DateTime<Utc>> {
+    let text = value.get(property)?.as_str()?;
+    DateTime::parse_from_rfc3339(text).ok().map(|timestamp| timestamp.with_timezone(&Utc))
+}
@@ -0,0 +1,86 @@
+use serde_json::{json, Value};
+use stix4rust::core::STIXObject;
+use stix4rust::core::bundle::{Bundle, MergeOptions};
+use stix4rust::core::versioning;
+
+mod common;
+use common::object;
+
+const IDENTITY: &str = "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff";
+const GROUPING: &str = "grouping--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3";
+
+fn domain(value: &str) -> Box<dyn STIXObject> {
+    object(json!({"type": "domain-name", "spec_version": "2.1", "id": "domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5", "value": value}))
+}
+
+fn malware(modified: &str, name: &str) -> Box<dyn STIXObject> {
+    object(json!({
+        "type": "malware", "spec_version": "2.1", "id": "malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061",
+        "created": "2016-05-12T08:17:27.000Z", "modified": modified, "name": name, "malware_types": ["ransomware"], "is_family": false
+    }))
+}
+
+fn grouping(modified: &str, creator: &str, object_refs: &[&str]) -> Box<dyn STIXObject> {
+    object(json!({
+        "type": "grouping", "spec_version": "2.1", "id": GROUPING, "created_by_ref": creator,
+        "created": "2016-05-12T08:17:27.000Z", "modified": modified, "context": "suspicious-activity", "object_refs": object_refs
+    }))
+}
+
+fn values(bundle: &Bundle) -> Vec<Value> {
+    bundle.objects.iter().map(|object| object.to_value()).collect()
+}
+
+/// Only the latest version of each object is kept, the copies of an SCO are collapsed, and the order the objects first appear in is kept.
+#[test]
+fn it_merges_latest_versions() {
+    let first = Bundle::new(vec![malware("2016-05-12T08:17:27.000Z", "Cryptolocker"), domain("example.com")]);
+    let second = Bundle::new(vec![domain("example.com"), malware("2017-01-01T00:00:00.000Z", "CryptoLocker")]);
+    let third = Bundle::new(vec![malware("2016-06-01T00:00:00.000Z", "Crypto")]);
+    let merged = Bundle::merge(vec![first, second, third], &MergeOptions::default()).unwrap();
+    let merged = values(&merged);
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0]["name"], "CryptoLocker");
+    assert_eq!(merged[1]["type"], "domain-name");
+}
+
+/// Every version is kept when asked, from the oldest to the latest.
+#[test]
+fn it_merges_all_versions() {
+    let first = Bundle::new(vec![malware("2017-01-01T00:00:00.000Z", "CryptoLocker"), domain("example.com")]);
+    let second = Bundle::new(vec![malware("2016-05-12T08:17:27.000Z", "Cryptolocker"), malware("2017-01-01T00:00:00.000Z", "CryptoLocker")]);
+    let merged = Bundle::merge(vec![first, second], &MergeOptions { all_versions: true }).unwrap();
+    let names: Vec<Value> = values(&merged).iter().map(|value| value["name"].clone()).collect();
+    assert_eq!(names, vec![json!("Cryptolocker"), json!("CryptoLocker"), Value::Null]);
+}
+
+/// The latest version of a grouping also refers to the objects of the earlier versions by the same creator.
+#[test]
+fn it_unions_grouping_refs() {
+    let bundle = Bundle::new(vec![
+        grouping("2016-05-12T08:17:27.000Z", IDENTITY, &["malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061"]),
+        grouping("2017-01-01T00:00:00.000Z", IDENTITY, &["indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2"]),
+        grouping("2016-06-01T00:00:00.000Z", "identity--311b2d2d-f010-4473-83ec-1edf84858f4c", &["campaign--83422c77-904c-4dc1-aff5-5c38f3a2c55c"]),
+    ]);
+    let merged = Bundle::merge(vec![bundle], &MergeOptions::default()).unwrap();
+    let merged = values(&merged);
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0]["object_refs"], json!(["indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2", "malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061"]));
+    assert!(merged[0]["modified"].as_str().unwrap() > "2017-01-01T00:00:00.000Z");
+}
+
+/// The grouping made by the merge is a new version, which the history of the versions merged accepts.
+#[test]
+fn it_versions_unioned_groupings() {
+    let versions = || {
+        vec![
+            grouping("2016-05-12T08:17:27.000Z", IDENTITY, &["malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061"]),
+            grouping("2017-01-01T00:00:00.000Z", IDENTITY, &["indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2"]),
+        ]
+    };
+    let mut merged = Bundle::merge(vec![Bundle::new(versions())], &MergeOptions::default()).unwrap().objects;
+    merged.extend(versions());
+    assert_eq!(versioning::check_history(&merged), vec![]);
+    let unchanged = Bundle::merge(vec![Bundle::new(vec![grouping("2017-01-01T00:00:00.000Z", IDENTITY, &["indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2"])])], &MergeOptions::default()).unwrap();
+    assert_eq!(values(&unchanged)[0]["modified"], "2017-01-01T00:00:00Z");
+}
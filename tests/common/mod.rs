@@ -0,0 +1,7 @@
+use serde_json::Value;
+use stix4rust::core::STIXObject;
+
+/// Deserializes an object whatever its type, for the fixtures built from a few varying properties.
+pub fn object(value: Value) -> Box<dyn STIXObject> {
+    serde_json::from_value(value).unwrap()
+}
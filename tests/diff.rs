@@ -0,0 +1,88 @@
+use serde_json::json;
+use stix4rust::core::STIXObject;
+use stix4rust::core::diff::{self, Change, ChangeKind, DiffError};
+
+fn report() -> Box<dyn STIXObject> {
+    serde_json::from_value(json!({
+        "type": "report",
+        "spec_version": "2.1",
+        "id": "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3",
+        "created": "2015-12-21T19:59:11.000Z",
+        "modified": "2015-12-21T19:59:11.000Z",
+        "name": "The Black Vine Cyberespionage Group",
+        "description": "A simple report with an indicator and campaign",
+        "published": "2016-01-20T17:00:00.000Z",
+        "report_types": ["campaign"],
+        "object_refs": [
+            "indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2",
+            "campaign--83422c77-904c-4dc1-aff5-5c38f3a2c55c"
+        ],
+        "external_references": [
+            {"source_name": "capec", "external_id": "CAPEC-163", "url": "http://capec.mitre.org/data/definitions/163.html"},
+            {"source_name": "veris", "external_id": "0001AA7F-C601-424A-B2B8-BE6C9F5164E7"}
+        ],
+        "granular_markings": [
+            {"marking_ref": "marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da", "selectors": ["description"]}
+        ]
+    }))
+    .unwrap()
+}
+
+/// The properties added, removed and changed are given with their paths, and the lists are compared item by item.
+#[test]
+fn it_diffs_versions() {
+    let old = report();
+    let new = old.new_version(json!({
+        "modified": "2016-01-01T00:00:00.000Z",
+        "description": "A report with an indicator, a campaign and a malware",
+        "published": null,
+        "object_refs": [
+            "campaign--83422c77-904c-4dc1-aff5-5c38f3a2c55c",
+            "malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061"
+        ],
+        "external_references": [
+            {"source_name": "veris", "external_id": "0001AA7F-C601-424A-B2B8-BE6C9F5164E7"},
+            {"source_name": "capec", "external_id": "CAPEC-163", "url": "https://capec.mitre.org/data/definitions/163.html"}
+        ],
+        "granular_markings": [
+            {"marking_ref": "marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da", "selectors": ["description", "name"]}
+        ],
+        "labels": ["espionage"]
+    })).unwrap();
+    let diff = old.diff(new.as_ref()).unwrap();
+    let change = |path: &str| diff.changes.iter().find(|change| change.path == path).map(|change| change.kind.clone());
+    assert_eq!(change("description"), Some(ChangeKind::Changed {
+        old: json!("A simple report with an indicator and campaign"),
+        new: json!("A report with an indicator, a campaign and a malware"),
+    }));
+    assert_eq!(change("published"), Some(ChangeKind::Removed(json!("2016-01-20T17:00:00Z"))));
+    assert_eq!(change("labels"), Some(ChangeKind::Added(json!(["espionage"]))));
+    let object_refs: Vec<&Change> = diff.changes_of("object_refs").collect();
+    assert_eq!(object_refs, vec![
+        &Change { path: "object_refs[1]".to_string(), kind: ChangeKind::Added(json!("malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061")) },
+        &Change { path: "object_refs[0]".to_string(), kind: ChangeKind::Removed(json!("indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2")) },
+    ]);
+    assert_eq!(diff.changes_of("external_references").collect::<Vec<_>>(), vec![&Change {
+        path: "external_references[1].url".to_string(),
+        kind: ChangeKind::Changed { old: json!("http://capec.mitre.org/data/definitions/163.html"), new: json!("https://capec.mitre.org/data/definitions/163.html") },
+    }]);
+    assert_eq!(change("granular_markings[0].selectors[1]"), Some(ChangeKind::Added(json!("name"))));
+    assert_eq!(diff.changes.len(), 8);
+}
+
+/// The rendering gives one line per change, and objects with different identifiers cannot be compared.
+#[test]
+fn it_renders_diffs() {
+    let old = report();
+    let new = old.new_version(json!({"modified": "2016-01-01T00:00:00.000Z", "name": "Black Vine"})).unwrap();
+    let text = diff::diff(old.as_ref(), new.as_ref()).unwrap().to_string();
+    assert_eq!(text.lines().next(), Some("report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3"));
+    assert!(text.contains(r#"  ~ name: "The Black Vine Cyberespionage Group" -> "Black Vine""#));
+    assert!(text.lines().any(|line| line.starts_with("  ~ modified: ")));
+    assert_eq!(diff::diff(old.as_ref(), old.as_ref()).unwrap().to_string(), "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3 (no changes)");
+    let other: Box<dyn STIXObject> = serde_json::from_value(json!({
+        "type": "identity", "spec_version": "2.1", "id": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "created": "2015-12-21T19:59:11.000Z", "modified": "2015-12-21T19:59:11.000Z", "name": "ACME"
+    })).unwrap();
+    assert!(matches!(old.diff(other.as_ref()), Err(DiffError::DifferentIds(_, _))));
+}
@@ -0,0 +1,84 @@
+use stix4rust::core::factory::ObjectFactory;
+use stix4rust::core::sdos::identity::Identity;
+use stix4rust::core::sdos::report::Report;
+use stix4rust::core::sros::relationship::Relationship;
+use stix4rust::core::sros::sighting::Sighting;
+use stix4rust::core::types::ExternalReference;
+
+const ACME: &str = "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff";
+const TLP_AMBER: &str = "marking-definition--f88d31f6-486f-44da-b317-01333bde0b82";
+const TLP_GREEN: &str = "marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da";
+
+fn reference(source_name: &str) -> ExternalReference {
+    ExternalReference { source_name: source_name.to_string(), description: None, url: None, hashes: None, external_id: None }
+}
+
+fn factory() -> ObjectFactory {
+    ObjectFactory {
+        created_by_ref: Some(ACME.to_string()),
+        object_marking_refs: vec![TLP_AMBER.to_string()],
+        lang: Some("en".to_string()),
+        external_references: vec![reference("ACME")],
+        ..ObjectFactory::default()
+    }
+}
+
+/// The defaults are given to every object built, whatever its type.
+#[test]
+fn it_applies_defaults() {
+    let identity = factory().create(Identity::builder().name("ACME")).unwrap();
+    assert_eq!(identity.created_by_ref.as_deref(), Some(ACME));
+    assert_eq!(identity.object_marking_refs, Some(vec![TLP_AMBER.to_string()]));
+    assert_eq!(identity.lang.as_deref(), Some("en"));
+    assert_eq!(identity.external_references.unwrap()[0].source_name, "ACME");
+    let report = factory().create(Report::builder().name("Poison Ivy").report_types(vec!["malware".to_string()])).unwrap();
+    assert_eq!(report.created_by_ref.as_deref(), Some(ACME));
+    assert!(factory().create(Report::builder().name("Poison Ivy")).is_err());
+}
+
+/// The properties given prevail, and the default lists are appended to those given unless they are replaced.
+#[test]
+fn it_keeps_given_properties() {
+    let builder = || {
+        Identity::builder()
+            .name("ACME")
+            .lang("fr")
+            .created_by_ref("identity--311b2d2d-f010-4473-83ec-1edf84858f4c")
+            .object_marking_refs(vec![TLP_GREEN.to_string(), TLP_AMBER.to_string()])
+            .external_references(vec![reference("other")])
+    };
+    let identity = factory().create(builder()).unwrap();
+    assert_eq!(identity.lang.as_deref(), Some("fr"));
+    assert_eq!(identity.created_by_ref.as_deref(), Some("identity--311b2d2d-f010-4473-83ec-1edf84858f4c"));
+    assert_eq!(identity.object_marking_refs, Some(vec![TLP_GREEN.to_string(), TLP_AMBER.to_string()]));
+    let sources: Vec<String> = identity.external_references.unwrap().into_iter().map(|reference| reference.source_name).collect();
+    assert_eq!(sources, vec!["other", "ACME"]);
+    let identity = factory().create(builder().external_references(vec![reference("ACME"), reference("other")])).unwrap();
+    assert_eq!(identity.external_references.unwrap().len(), 2);
+    let replacing = ObjectFactory { replace_lists: true, ..factory() };
+    let identity = replacing.create(builder().object_marking_refs(vec![TLP_GREEN.to_string()])).unwrap();
+    assert_eq!(identity.object_marking_refs, Some(vec![TLP_GREEN.to_string()]));
+    assert_eq!(identity.external_references.unwrap().len(), 1);
+}
+
+/// The relationships and sightings are stamped with the defaults too.
+#[test]
+fn it_applies_defaults_to_sros() {
+    let relationship = factory()
+        .create(
+            Relationship::builder()
+                .relationship_type("uses")
+                .source_ref("threat-actor--9a8a0d25-7636-429b-a99e-b2a73cd0f11f")
+                .target_ref("malware--92ec0cbd-2c30-44a2-b270-73f4ec949841"),
+        )
+        .unwrap();
+    assert!(relationship.id.starts_with("relationship--"));
+    assert_eq!(relationship.created_by_ref.as_deref(), Some(ACME));
+    assert_eq!(relationship.object_marking_refs, Some(vec![TLP_AMBER.to_string()]));
+    let sighting = factory().create(Sighting::builder().sighting_of_ref("indicator--9299f726-ce06-492e-8472-2b52ccb53191").count(3u64)).unwrap();
+    assert!(sighting.id.starts_with("sighting--"));
+    assert_eq!(sighting.created_by_ref.as_deref(), Some(ACME));
+    assert_eq!(sighting.object_marking_refs, Some(vec![TLP_AMBER.to_string()]));
+    assert_eq!(sighting.count, Some(3));
+    assert!(factory().create(Relationship::builder().relationship_type("uses")).is_err());
+}
@@ -0,0 +1,182 @@
+use stix4rust::core::STIXObject;
+use stix4rust::core::bundle::Bundle;
+use stix4rust::core::graph::closure::{self, ClosureOptions};
+use stix4rust::core::graph::navigation;
+use stix4rust::core::stores::Store;
+use stix4rust::core::stores::filesystem::FileSystemStore;
+use stix4rust::core::stores::memory::MemoryStore;
+
+const REPORT: &str = "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3";
+const ACME: &str = "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff";
+const OTHER: &str = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+const MALWARE: &str = "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b";
+const INFRASTRUCTURE: &str = "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d";
+const RELATIONSHIP: &str = "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad";
+
+/// A report on a malware and the infrastructure it uses, next to an unrelated campaign.
+fn store() -> MemoryStore {
+    let text = r#"
+    [
+        {
+            "type": "report",
+            "spec_version": "2.1",
+            "id": "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "object_marking_refs": ["marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da"],
+            "name": "Poison Ivy",
+            "published": "2020-01-01T00:00:00Z",
+            "report_types": ["malware"],
+            "object_refs": [
+                "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+                "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+                "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f"
+            ]
+        },
+        {
+            "type": "identity",
+            "spec_version": "2.1",
+            "id": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "ACME"
+        },
+        {
+            "type": "identity",
+            "spec_version": "2.1",
+            "id": "identity--311b2d2d-f010-4473-83ec-1edf84858f4c",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "Other"
+        },
+        {
+            "type": "malware",
+            "spec_version": "2.1",
+            "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "Poison Ivy",
+            "malware_types": ["remote-access-trojan"],
+            "is_family": true
+        },
+        {
+            "type": "infrastructure",
+            "spec_version": "2.1",
+            "id": "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "created_by_ref": "identity--311b2d2d-f010-4473-83ec-1edf84858f4c",
+            "name": "C2",
+            "infrastructure_types": ["command-and-control"]
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "uses",
+            "source_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "target_ref": "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d"
+        },
+        {
+            "type": "campaign",
+            "spec_version": "2.1",
+            "id": "campaign--83422c77-904c-4dc1-aff5-5c38f3a2c55c",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "Unrelated"
+        }
+    ]
+    "#;
+    let mut store = MemoryStore::new();
+    let objects: Vec<Box<dyn STIXObject>> = serde_json::from_str(text).unwrap();
+    objects.into_iter().for_each(|object| store.add(object).unwrap());
+    store
+}
+
+fn ids(bundle: &Bundle) -> Vec<String> {
+    bundle.objects.iter().map(|object| object.to_value()["id"].as_str().unwrap().to_string()).collect()
+}
+
+/// Everything the seed refers to, directly or not, is gathered, and the missing objects are skipped.
+#[test]
+fn it_gathers_references() {
+    let bundle = closure::closure(&store(), REPORT, &ClosureOptions::default()).unwrap().unwrap();
+    assert_eq!(ids(&bundle), vec![REPORT, ACME, MALWARE, RELATIONSHIP, INFRASTRUCTURE, OTHER]);
+    assert!(closure::closure(&store(), "report--00000000-0000-0000-0000-000000000000", &ClosureOptions::default()).unwrap().is_none());
+}
+
+/// The depth and the types limit what is gathered.
+#[test]
+fn it_limits_closures() {
+    let options = ClosureOptions { max_depth: Some(1), ..ClosureOptions::default() };
+    let bundle = closure::closure(&store(), REPORT, &options).unwrap().unwrap();
+    assert_eq!(ids(&bundle), vec![REPORT, ACME, MALWARE, RELATIONSHIP]);
+    let options = ClosureOptions { types: Some(vec!["identity".to_string(), "malware".to_string()]), ..ClosureOptions::default() };
+    let bundle = closure::closure(&store(), REPORT, &options).unwrap().unwrap();
+    assert_eq!(ids(&bundle), vec![REPORT, ACME, MALWARE]);
+}
+
+/// The references between the objects embedded in observed data are not taken as identifiers, which a filesystem store would reject.
+#[test]
+fn it_skips_embedded_object_references() {
+    let root = std::env::temp_dir().join(format!("stix4rust-closure-{}", uuid::Uuid::new_v4()));
+    let mut store = FileSystemStore::new(&root);
+    let observed_data = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 1,
+        "objects": {
+            "0": {
+                "type": "ipv4-addr",
+                "value": "198.51.100.3"
+            },
+            "1": {
+                "type": "network-traffic",
+                "src_ref": "0",
+                "protocols": ["tcp"]
+            }
+        }
+    }
+    "#;
+    let identity = r#"
+    {
+        "type": "identity",
+        "spec_version": "2.1",
+        "id": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "name": "ACME"
+    }
+    "#;
+    store.add(serde_json::from_str(observed_data).unwrap()).unwrap();
+    store.add(serde_json::from_str(identity).unwrap()).unwrap();
+    let bundle = closure::closure(&store, "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf", &ClosureOptions::default()).unwrap().unwrap();
+    assert_eq!(ids(&bundle), vec!["observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf", "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff"]);
+    let visits: Vec<usize> = navigation::traverse(&store, "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf", None).map(|visit| visit.unwrap().depth).collect();
+    assert_eq!(visits, vec![0, 1]);
+    let malformed = r#"
+    {
+        "type": "identity",
+        "spec_version": "2.1",
+        "id": "identity--311b2d2d-f010-4473-83ec-1edf84858f4c",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "created_by_ref": "identity--../acme",
+        "name": "Other"
+    }
+    "#;
+    store.add(serde_json::from_str(malformed).unwrap()).unwrap();
+    let bundle = closure::closure(&store, "identity--311b2d2d-f010-4473-83ec-1edf84858f4c", &ClosureOptions::default()).unwrap().unwrap();
+    assert_eq!(bundle.objects.len(), 1);
+    std::fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,132 @@
+use stix4rust::core::STIXObject;
+use stix4rust::core::graph::{dot, graphml};
+use stix4rust::core::graph::export::{ExportOptions, Subgraph};
+use stix4rust::core::stores::memory::MemoryStore;
+
+/// A threat actor using a malware that communicates with a domain, the malware being created by an identity.
+fn objects() -> Vec<Box<dyn STIXObject>> {
+    let text = r#"
+    [
+        {
+            "type": "identity",
+            "spec_version": "2.1",
+            "id": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "ACME \"Labs\""
+        },
+        {
+            "type": "malware",
+            "spec_version": "2.1",
+            "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "name": "Evil",
+            "malware_types": ["bot"],
+            "is_family": true
+        },
+        {
+            "type": "threat-actor",
+            "spec_version": "2.1",
+            "id": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "APT <1>",
+            "threat_actor_types": ["crime-syndicate"]
+        },
+        {
+            "type": "domain-name",
+            "id": "domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5",
+            "value": "evil.com"
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "uses",
+            "source_ref": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+            "target_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b"
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--4f9d4a0e-7b6a-4c5e-9a1c-2d3e4f5a6b7c",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "communicates-with",
+            "source_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "target_ref": "domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5"
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--7e1c2f3a-9b8d-4c6e-a5f4-3d2c1b0a9e8f",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "targets",
+            "source_ref": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+            "target_ref": "identity--0a3b8c1e-2d4f-4e6a-8b7c-9d0e1f2a3b4c"
+        }
+    ]
+    "#;
+    serde_json::from_str(text).unwrap()
+}
+
+fn node_ids(subgraph: &Subgraph) -> Vec<&str> {
+    subgraph.nodes.iter().map(|node| node.id.as_str()).collect()
+}
+
+/// The whole collection is drawn, leaving out the edges to missing objects.
+#[test]
+fn it_renders_dot() {
+    let subgraph = Subgraph::from_objects(&objects(), &ExportOptions::default());
+    assert_eq!(
+        dot::render(&subgraph),
+        concat!(
+            "digraph stix {\n",
+            "    node [shape=box];\n",
+            "    \"domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5\" [label=\"evil.com\", tooltip=\"domain-name\"];\n",
+            "    \"identity--f431f809-377b-45e0-aa1c-6a4751cae5ff\" [label=\"ACME \\\"Labs\\\"\", tooltip=\"identity\"];\n",
+            "    \"malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b\" [label=\"Evil\", tooltip=\"malware\"];\n",
+            "    \"threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500\" [label=\"APT <1>\", tooltip=\"threat-actor\"];\n",
+            "    \"malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b\" -> \"domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5\" [label=\"communicates-with\", style=solid];\n",
+            "    \"malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b\" -> \"identity--f431f809-377b-45e0-aa1c-6a4751cae5ff\" [label=\"created_by_ref\", style=dashed];\n",
+            "    \"threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500\" -> \"malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b\" [label=\"uses\", style=solid];\n",
+            "}\n"
+        )
+    );
+}
+
+/// GraphML documents carry the labels and types as data, escaped.
+#[test]
+fn it_renders_graphml() {
+    let options = ExportOptions { types: Some(vec!["threat-actor".to_string(), "malware".to_string()]), ..ExportOptions::default() };
+    let xml = graphml::render(&Subgraph::from_objects(&objects(), &options));
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"));
+    assert!(xml.contains("    <node id=\"threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500\">\n      <data key=\"label\">APT &lt;1&gt;</data>\n      <data key=\"type\">threat-actor</data>\n    </node>\n"));
+    assert!(xml.contains("    <edge source=\"threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500\" target=\"malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b\">\n      <data key=\"edge_label\">uses</data>\n      <data key=\"kind\">relationship</data>\n    </edge>\n"));
+    assert_eq!(xml.matches("<node ").count(), 2);
+    assert_eq!(xml.matches("<edge ").count(), 1);
+    assert!(xml.ends_with("  </graph>\n</graphml>\n"));
+}
+
+/// From a seed, only the objects within the depth given are kept, whether they come from a bundle or a store.
+#[test]
+fn it_exports_around_a_seed() {
+    let options = ExportOptions { seed: Some("threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500".to_string()), max_depth: Some(1), ..ExportOptions::default() };
+    let subgraph = Subgraph::from_objects(&objects(), &options);
+    assert_eq!(node_ids(&subgraph), ["malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b", "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500"]);
+    assert_eq!(subgraph.edges.len(), 1);
+    let mut store = MemoryStore::new();
+    for object in objects() {
+        store.add(object).unwrap();
+    }
+    assert_eq!(Subgraph::from_store(&store, &options).unwrap(), subgraph);
+    let options = ExportOptions { seed: Some("threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500".to_string()), types: Some(vec!["threat-actor".to_string(), "domain-name".to_string()]), max_depth: None };
+    assert_eq!(node_ids(&Subgraph::from_store(&store, &options).unwrap()), ["threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500"]);
+    let whole = Subgraph::from_store(&store, &ExportOptions::default()).unwrap();
+    assert_eq!(whole, Subgraph::from_objects(&objects(), &ExportOptions::default()));
+}
@@ -0,0 +1,184 @@
+use stix4rust::core::STIXObject;
+use stix4rust::core::graph::navigation::{self, Direction, EdgeKind};
+use stix4rust::core::stores::memory::MemoryStore;
+
+const ACME: &str = "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff";
+const VICTIM: &str = "identity--fa8aa9a4-5f4e-4a2b-9d2e-3c1b0a9f8e7d";
+const MISSING: &str = "identity--0a3b8c1e-2d4f-4e6a-8b7c-9d0e1f2a3b4c";
+const MALWARE: &str = "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b";
+const INFRASTRUCTURE: &str = "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d";
+const THREAT_ACTOR: &str = "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500";
+const USES_INFRASTRUCTURE: &str = "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad";
+const USES_MALWARE: &str = "relationship--4f9d4a0e-7b6a-4c5e-9a1c-2d3e4f5a6b7c";
+const SIGHTING: &str = "sighting--ee254c13-6c9a-4ed6-9b1b-2c0d2f8a2f4e";
+const SIGHTING_WITHOUT_WHERE: &str = "sighting--f1d0a6c3-2b8e-4d7f-8c3a-5e6b7a8c9d0e";
+
+/// A malware used by a threat actor, running on an infrastructure and sighted by an identity.
+fn store() -> MemoryStore {
+    let text = r#"
+    [
+        {
+            "type": "identity",
+            "spec_version": "2.1",
+            "id": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "ACME"
+        },
+        {
+            "type": "identity",
+            "spec_version": "2.1",
+            "id": "identity--fa8aa9a4-5f4e-4a2b-9d2e-3c1b0a9f8e7d",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "Victim"
+        },
+        {
+            "type": "malware",
+            "spec_version": "2.1",
+            "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "name": "Evil",
+            "malware_types": ["ransomware"],
+            "is_family": true
+        },
+        {
+            "type": "infrastructure",
+            "spec_version": "2.1",
+            "id": "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "C2"
+        },
+        {
+            "type": "threat-actor",
+            "spec_version": "2.1",
+            "id": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "name": "APT",
+            "threat_actor_types": ["nation-state"]
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "uses",
+            "source_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "target_ref": "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d"
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--4f9d4a0e-7b6a-4c5e-9a1c-2d3e4f5a6b7c",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "uses",
+            "source_ref": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+            "target_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b"
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--7e1c2f3a-9b8d-4c6e-a5f4-3d2c1b0a9e8f",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "relationship_type": "attributed-to",
+            "source_ref": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+            "target_ref": "identity--0a3b8c1e-2d4f-4e6a-8b7c-9d0e1f2a3b4c"
+        },
+        {
+            "type": "sighting",
+            "spec_version": "2.1",
+            "id": "sighting--ee254c13-6c9a-4ed6-9b1b-2c0d2f8a2f4e",
+            "created": "2020-01-01T00:00:00Z",
+            "modified": "2020-01-01T00:00:00Z",
+            "sighting_of_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "where_sighted_refs": ["identity--fa8aa9a4-5f4e-4a2b-9d2e-3c1b0a9f8e7d"]
+        }
+    ]
+    "#;
+    let mut store = MemoryStore::new();
+    let objects: Vec<Box<dyn STIXObject>> = serde_json::from_str(text).unwrap();
+    objects.into_iter().for_each(|object| store.add(object).unwrap());
+    store
+}
+
+fn ids(objects: Vec<Box<dyn STIXObject>>) -> Vec<String> {
+    let mut ids: Vec<String> = objects.iter().map(|object| object.to_value()["id"].as_str().unwrap().to_string()).collect();
+    ids.sort();
+    ids
+}
+
+/// Relationships and sightings are found from either of their ends.
+#[test]
+fn it_finds_relationships() {
+    let store = store();
+    assert_eq!(ids(navigation::relationships(&store, MALWARE, None, Direction::Both).unwrap()), [USES_INFRASTRUCTURE, USES_MALWARE, SIGHTING]);
+    assert_eq!(ids(navigation::relationships(&store, MALWARE, Some("uses"), Direction::Source).unwrap()), [USES_INFRASTRUCTURE]);
+    assert_eq!(ids(navigation::relationships(&store, MALWARE, None, Direction::Target).unwrap()), [USES_MALWARE, SIGHTING]);
+    assert_eq!(ids(navigation::relationships(&store, VICTIM, Some("sighted"), Direction::Source).unwrap()), [SIGHTING]);
+    assert!(navigation::relationships(&store, MALWARE, Some("indicates"), Direction::Both).unwrap().is_empty());
+}
+
+/// Pivoting from the malware reaches its infrastructure, its threat actor and the identities that sighted it.
+#[test]
+fn it_finds_related_objects() {
+    let store = store();
+    assert_eq!(ids(navigation::related_to(&store, MALWARE, None, Direction::Both).unwrap()), [VICTIM, INFRASTRUCTURE, THREAT_ACTOR]);
+    assert_eq!(ids(navigation::related_to(&store, MALWARE, Some("uses"), Direction::Target).unwrap()), [THREAT_ACTOR]);
+    assert_eq!(ids(navigation::related_to(&store, THREAT_ACTOR, None, Direction::Source).unwrap()), [MALWARE]);
+    let malware = store.get(MALWARE).unwrap();
+    assert_eq!(ids(navigation::creator_of(&store, malware).unwrap().into_iter().collect()), [ACME]);
+    let infrastructure = store.get(INFRASTRUCTURE).unwrap();
+    assert!(navigation::creator_of(&store, infrastructure).unwrap().is_none());
+}
+
+/// The traversal walks the relationships and the embedded references breadth first, up to the depth given.
+#[test]
+fn it_traverses_the_graph() {
+    let store = store();
+    let visits: Vec<_> = navigation::traverse(&store, INFRASTRUCTURE, None).collect::<Result<_, _>>().unwrap();
+    let reached: Vec<(String, usize)> = visits.iter().map(|visit| (visit.object.to_value()["id"].as_str().unwrap().to_string(), visit.depth)).collect();
+    assert_eq!(reached[0], (INFRASTRUCTURE.to_string(), 0));
+    assert_eq!(reached[1], (MALWARE.to_string(), 1));
+    let mut further = reached[2..].to_vec();
+    further.sort();
+    assert_eq!(further, [(ACME.to_string(), 2), (VICTIM.to_string(), 2), (THREAT_ACTOR.to_string(), 2)]);
+    let acme = visits.iter().find(|visit| visit.object.to_value()["id"] == ACME).unwrap();
+    assert_eq!(acme.edge.as_ref().unwrap().kind, EdgeKind::Reference("created_by_ref".to_string()));
+    let malware = &visits[1];
+    assert_eq!(
+        malware.edge.as_ref().unwrap().kind,
+        EdgeKind::Relationship { relationship_type: "uses".to_string(), id: USES_INFRASTRUCTURE.to_string() }
+    );
+    assert_eq!(navigation::traverse(&store, INFRASTRUCTURE, Some(1)).count(), 2);
+    assert_eq!(navigation::traverse(&store, MISSING, None).count(), 0);
+}
+
+/// A sighting that does not tell where the object was sighted goes from the sighting itself to the object.
+#[test]
+fn it_finds_sightings_without_where_sighted() {
+    let mut store = store();
+    let text = r#"
+    {
+        "type": "sighting",
+        "spec_version": "2.1",
+        "id": "sighting--f1d0a6c3-2b8e-4d7f-8c3a-5e6b7a8c9d0e",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "sighting_of_ref": "infrastructure--38c47d93-d984-4fd9-b87b-d69d0841628d"
+    }
+    "#;
+    store.add(serde_json::from_str(text).unwrap()).unwrap();
+    assert_eq!(ids(navigation::relationships(&store, INFRASTRUCTURE, Some("sighted"), Direction::Target).unwrap()), [SIGHTING_WITHOUT_WHERE]);
+    assert_eq!(ids(navigation::relationships(&store, SIGHTING_WITHOUT_WHERE, Some("sighted"), Direction::Source).unwrap()), [SIGHTING_WITHOUT_WHERE]);
+    assert_eq!(ids(navigation::related_to(&store, INFRASTRUCTURE, Some("sighted"), Direction::Target).unwrap()), [SIGHTING_WITHOUT_WHERE]);
+    let edges = navigation::relationship_edges(&store.get(SIGHTING_WITHOUT_WHERE).unwrap().to_value());
+    assert_eq!(edges.len(), 1);
+    assert_eq!((edges[0].source.as_str(), edges[0].target.as_str()), (SIGHTING_WITHOUT_WHERE, INFRASTRUCTURE));
+}
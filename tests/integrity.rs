@@ -0,0 +1,109 @@
+use stix4rust::core::STIXObject;
+use stix4rust::core::integrity::{self, Violation, ViolationKind};
+use stix4rust::core::stores::memory::MemoryStore;
+
+const MALWARE: &str = "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b";
+const INDICATOR: &str = "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f";
+
+/// A malware created by an identity, a report credited to that malware, and a relationship between two missing objects.
+fn objects() -> Vec<Box<dyn STIXObject>> {
+    let text = r#"
+    [
+        {
+            "type": "identity",
+            "spec_version": "2.1",
+            "id": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "created": "2016-04-06T20:03:00.000Z",
+            "modified": "2016-04-06T20:03:00.000Z",
+            "name": "ACME"
+        },
+        {
+            "type": "malware",
+            "spec_version": "2.1",
+            "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "created": "2016-04-06T20:03:00.000Z",
+            "modified": "2016-04-06T20:03:00.000Z",
+            "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+            "object_marking_refs": ["marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da"],
+            "name": "Poison Ivy",
+            "malware_types": ["remote-access-trojan"],
+            "is_family": true,
+            "sample_refs": ["url--c1477287-23ac-5971-a010-5c287877fa60"]
+        },
+        {
+            "type": "report",
+            "spec_version": "2.1",
+            "id": "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3",
+            "created": "2016-04-06T20:03:00.000Z",
+            "modified": "2016-04-06T20:03:00.000Z",
+            "created_by_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "granular_markings": [
+                {
+                    "marking_ref": "marking-definition--089a6ecb-cc15-43cc-9494-767639779123",
+                    "selectors": ["name"]
+                }
+            ],
+            "name": "Poison Ivy",
+            "published": "2016-04-06T20:03:00.000Z",
+            "report_types": ["malware"],
+            "object_refs": ["malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b", "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f"]
+        },
+        {
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+            "created": "2016-04-06T20:06:37.000Z",
+            "modified": "2016-04-06T20:06:37.000Z",
+            "relationship_type": "indicates",
+            "source_ref": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+            "target_ref": "relationship--57b56a43-b8b0-4cba-9deb-34e3e1faed9e"
+        }
+    ]
+    "#;
+    serde_json::from_str(text).unwrap()
+}
+
+fn violation(source: &str, property: &str, target: &str, kind: ViolationKind) -> Violation {
+    Violation { source: source.to_string(), property: property.to_string(), target: target.to_string(), kind }
+}
+
+/// The references missing from the objects, and those to types their property does not allow, are reported.
+#[test]
+fn it_checks_references() {
+    let violations = integrity::check_references(&objects());
+    let report = "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3";
+    let relationship = "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad";
+    assert_eq!(violations, vec![
+        violation(MALWARE, "sample_refs", "url--c1477287-23ac-5971-a010-5c287877fa60", ViolationKind::DisallowedType),
+        violation(report, "created_by_ref", MALWARE, ViolationKind::DisallowedType),
+        violation(report, "granular_markings.marking_ref", "marking-definition--089a6ecb-cc15-43cc-9494-767639779123", ViolationKind::Unresolved),
+        violation(report, "object_refs", INDICATOR, ViolationKind::Unresolved),
+        violation(relationship, "source_ref", INDICATOR, ViolationKind::Unresolved),
+        violation(relationship, "target_ref", "relationship--57b56a43-b8b0-4cba-9deb-34e3e1faed9e", ViolationKind::DisallowedType),
+    ]);
+    assert_eq!(violations[3].to_string(), format!("the object_refs of {} refers to {}, which is not found", report, INDICATOR));
+}
+
+/// Stores are checked the same way, against the objects stored.
+#[test]
+fn it_checks_store_references() {
+    let mut store = MemoryStore::new();
+    objects().into_iter().for_each(|object| store.add(object).unwrap());
+    assert_eq!(integrity::check_store_references(&store).unwrap().len(), 6);
+    let indicator = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern": "[ file:name = 'foo.exe' ]",
+        "pattern_type": "stix",
+        "valid_from": "2016-01-01T00:00:00Z"
+    }
+    "#;
+    store.add(serde_json::from_str(indicator).unwrap()).unwrap();
+    let violations = integrity::check_store_references(&store).unwrap();
+    assert_eq!(violations.len(), 4);
+    assert!(violations.iter().all(|violation| violation.target != INDICATOR));
+}
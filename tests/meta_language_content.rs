@@ -0,0 +1,109 @@
+use validator::Validate;
+use stix4rust::core::STIXObject;
+use stix4rust::core::metas::language_content::{LanguageContent, translate};
+
+const CAMPAIGN: &str = r#"
+{
+    "type": "campaign",
+    "spec_version": "2.1",
+    "id": "campaign--12a111f0-b824-4baf-a224-83b80237a094",
+    "created": "2017-02-08T21:31:22.007Z",
+    "modified": "2017-02-08T21:31:22.007Z",
+    "name": "Bank Attack 1",
+    "description": "More information about bank attack",
+    "aliases": ["Robbery", "Heist"]
+}
+"#;
+
+const LANGUAGE_CONTENT: &str = r#"
+{
+    "type": "language-content",
+    "id": "language-content--b86bd89f-98bb-4fa9-8cb2-9ad421da981d",
+    "spec_version": "2.1",
+    "created": "2017-02-08T21:31:22.007Z",
+    "modified": "2017-02-08T21:31:22.007Z",
+    "object_ref": "campaign--12a111f0-b824-4baf-a224-83b80237a094",
+    "object_modified": "2017-02-08T21:31:22.007Z",
+    "contents": {
+        "es-ES": {
+            "name": "Ataque al Banco 1",
+            "aliases": ["", "Atraco"]
+        },
+        "fr": {
+            "name": "Attaque Bank 1",
+            "description": "Plus d'informations sur la crise bancaire"
+        }
+    }
+}
+"#;
+
+/// A complete deserialization of the object grabbing values for each and every element.
+#[test]
+fn it_stix_object_complete_deserialization() {
+    let object: LanguageContent = serde_json::from_str(LANGUAGE_CONTENT).unwrap();
+    assert!(object.validate().is_ok());
+}
+
+/// Since there are some fields which are required, this test verifies that the deserialization method effectively detects that a panics.
+#[test]
+#[should_panic]
+fn it_stix_object_deserialization_with_missing_required_field() {
+    let text = r#"
+    {
+        "type": "language-content",
+        "id": "language-content--b86bd89f-98bb-4fa9-8cb2-9ad421da981d",
+        "spec_version": "2.1",
+        "created": "2017-02-08T21:31:22.007Z",
+        "modified": "2017-02-08T21:31:22.007Z",
+        "contents": {}
+    }
+    "#;
+    let _object: LanguageContent = serde_json::from_str(text).unwrap();
+}
+
+/// The keys of the contents MUST be valid RFC 5646 language tags.
+#[test]
+fn it_stix_object_validation_with_wrong_language_tag() {
+    let text = r#"
+    {
+        "type": "language-content",
+        "id": "language-content--b86bd89f-98bb-4fa9-8cb2-9ad421da981d",
+        "spec_version": "2.1",
+        "created": "2017-02-08T21:31:22.007Z",
+        "modified": "2017-02-08T21:31:22.007Z",
+        "object_ref": "campaign--12a111f0-b824-4baf-a224-83b80237a094",
+        "contents": {
+            "not a language!": {
+                "name": "Whatever"
+            }
+        }
+    }
+    "#;
+    let object: LanguageContent = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+}
+
+/// The translation falls back property by property to the next preferred language.
+#[test]
+fn it_translates_with_fallback_languages() {
+    let campaign: Box<dyn STIXObject> = serde_json::from_str(CAMPAIGN).unwrap();
+    let content: LanguageContent = serde_json::from_str(LANGUAGE_CONTENT).unwrap();
+    let translated = translate(campaign.as_ref(), &[content], &["es", "fr"]).unwrap().to_value();
+    assert_eq!(translated["name"], "Ataque al Banco 1");
+    assert_eq!(translated["description"], "Plus d'informations sur la crise bancaire");
+    assert_eq!(translated["aliases"], serde_json::json!(["Robbery", "Atraco"]));
+    assert_eq!(translated["lang"], "es-ES");
+}
+
+/// Contents targeting another version of the object are ignored.
+#[test]
+fn it_ignores_contents_for_other_versions() {
+    let campaign: Box<dyn STIXObject> = serde_json::from_str(&CAMPAIGN.replace(
+        "\"modified\": \"2017-02-08T21:31:22.007Z\"",
+        "\"modified\": \"2018-02-08T21:31:22.007Z\"",
+    )).unwrap();
+    let content: LanguageContent = serde_json::from_str(LANGUAGE_CONTENT).unwrap();
+    let translated = translate(campaign.as_ref(), &[content], &["fr"]).unwrap().to_value();
+    assert_eq!(translated["name"], "Bank Attack 1");
+    assert!(translated.get("lang").is_none());
+}
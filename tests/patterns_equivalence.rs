@@ -0,0 +1,108 @@
+use stix4rust::core::patterns::{self, equivalence};
+
+fn pattern(text: &str) -> patterns::ast::Pattern {
+    patterns::parse(text).unwrap()
+}
+
+/// Patterns written differently but matching the same observations are equivalent.
+#[test]
+fn it_finds_equivalent_patterns() {
+    let pairs = [
+        ("[ipv4-addr:value = '10.0.0.1' OR ipv4-addr:value = '10.0.0.2']", "[ipv4-addr:value = '10.0.0.2'] OR [ipv4-addr:value = '10.0.0.1']"),
+        ("[file:name = 'a' AND (file:size = 1 OR file:size = 2)]", "[file:name = 'a' AND file:size = 1] OR [file:size = 2 AND file:name = 'a']"),
+        ("[file:hashes.sha256 = 'AEC070']", "[file:hashes.'SHA-256' = 'aec070']"),
+        ("[ipv4-addr:value = '10.0.0.1/32']", "[ipv4-addr:value IN ('10.0.0.1')]"),
+        ("[ipv4-addr:value ISSUBSET '10.1.2.3/8']", "[ipv4-addr:value ISSUBSET '10.0.0.0/8']"),
+        ("[domain-name:value = 'a.com'] OR ([domain-name:value = 'a.com'] AND [url:value = 'http://a.com'])", "[domain-name:value = 'a.com']"),
+        ("[file:size > 10 AND file:size >= 5]", "[file:size > 10]"),
+    ];
+    for (first, second) in pairs.iter() {
+        assert!(equivalence::equivalent(&pattern(first), &pattern(second)), "{} <=> {}", first, second);
+    }
+}
+
+/// Patterns that differ in what they match are not equivalent, even when they look alike.
+#[test]
+fn it_distinguishes_different_patterns() {
+    let pairs = [
+        ("[file:name = 'a'] AND [file:name = 'a']", "[file:name = 'a']"),
+        ("[file:name = 'a'] FOLLOWEDBY [file:name = 'b']", "[file:name = 'b'] FOLLOWEDBY [file:name = 'a']"),
+        ("([file:name = 'a'] OR [file:name = 'b']) REPEATS 2 TIMES", "[file:name = 'a'] REPEATS 2 TIMES OR [file:name = 'b'] REPEATS 2 TIMES"),
+        ("[windows-registry-key:key = 'HKEY_LOCAL_MACHINE\\\\Run']", "[windows-registry-key:key = 'hkey_local_machine\\\\run' AND windows-registry-key:values[*].name = 'x']"),
+        ("[file:hashes.MD5 MATCHES '^\\\\D+$']", "[file:hashes.MD5 MATCHES '^\\\\d+$']"),
+        ("[windows-registry-key:key LIKE 'HKEY_LOCAL_MACHINE%']", "[windows-registry-key:key LIKE 'hkey_local_machine%']"),
+    ];
+    for (first, second) in pairs.iter() {
+        assert!(!equivalence::equivalent(&pattern(first), &pattern(second)), "{} <=> {}", first, second);
+    }
+}
+
+/// A pattern subsumes those that only match a part of its observations.
+#[test]
+fn it_finds_subsumed_patterns() {
+    let pairs = [
+        ("[ipv4-addr:value ISSUBSET '10.0.0.0/8']", "[ipv4-addr:value = '10.1.2.3']"),
+        ("[ipv4-addr:value ISSUBSET '10.0.0.0/8']", "[ipv4-addr:value ISSUBSET '10.1.0.0/16']"),
+        ("[domain-name:value LIKE '%.example.com']", "[domain-name:value IN ('www.example.com', 'mail.example.com')]"),
+        ("[file:size > 10]", "[file:size > 100 AND file:name = 'a']"),
+        ("[file:name = 'a']", "[file:name = 'a'] FOLLOWEDBY [file:name = 'b']"),
+        ("[file:name = 'a'] AND [file:name = 'b']", "[file:name = 'b'] FOLLOWEDBY [file:name = 'a']"),
+        ("[file:name = 'a'] WITHIN 300 SECONDS", "([file:name = 'a'] WITHIN 60 SECONDS) REPEATS 3 TIMES"),
+        ("[file:name = 'a'] REPEATS 2 TIMES", "[file:name = 'a'] REPEATS 5 TIMES"),
+        ("[file:name MATCHES '^mal.*\\\\.exe$']", "[file:name = 'malware.exe']"),
+        ("[file:name != 'a']", "[file:name = 'b']"),
+        ("[EXISTS file:hashes]", "[file:hashes.MD5 = '0123']"),
+        ("[network-traffic:protocols[*] IN ('tcp', 'udp')]", "[network-traffic:protocols[*] = 'tcp']"),
+        ("[network-traffic:protocols[0] NOT = 'udp']", "[network-traffic:protocols[0] = 'tcp']"),
+    ];
+    for (general, specific) in pairs.iter() {
+        assert!(equivalence::subsumes(&pattern(general), &pattern(specific)), "{} => {}", general, specific);
+        assert!(!equivalence::subsumes(&pattern(specific), &pattern(general)), "{} => {}", specific, general);
+    }
+}
+
+/// Patterns that match observations the other one does not are not subsumed.
+#[test]
+fn it_rejects_wider_patterns() {
+    let pairs = [
+        ("[ipv4-addr:value ISSUBSET '10.0.0.0/16']", "[ipv4-addr:value = '10.1.2.3']"),
+        ("[file:size >= 10]", "[file:size >= 9]"),
+        ("[file:name = 'a'] FOLLOWEDBY [file:name = 'b']", "[file:name = 'a'] AND [file:name = 'b']"),
+        ("[file:name = 'a'] WITHIN 60 SECONDS", "[file:name = 'a'] WITHIN 300 SECONDS"),
+        ("[file:name NOT = 'a']", "[file:size = 1]"),
+        ("[file:hashes.MD5 MATCHES '^[a-f0-9]+$']", "[file:hashes.MD5 = 'ABC123']"),
+        ("[network-traffic:protocols[*] NOT = 'udp']", "[network-traffic:protocols[*] = 'tcp']"),
+        ("[network-traffic:protocols[*] NOT IN ('udp', 'icmp')]", "[network-traffic:protocols[*] = 'tcp']"),
+    ];
+    for (general, specific) in pairs.iter() {
+        assert!(!equivalence::subsumes(&pattern(general), &pattern(specific)), "{} => {}", general, specific);
+    }
+}
+
+/// Redundant patterns of a merged feed are reported, keeping the first of the equivalent ones.
+#[test]
+fn it_reports_redundant_patterns() {
+    let feed = [
+        pattern("[ipv4-addr:value = '10.1.2.3']"),
+        pattern("[ipv4-addr:value ISSUBSET '10.0.0.0/8']"),
+        pattern("[domain-name:value = 'a.com']"),
+        pattern("[domain-name:value IN ('a.com')]"),
+        pattern("[url:value = 'http://a.com']"),
+    ];
+    assert_eq!(equivalence::redundant(&feed), vec![0, 3]);
+    let feed = [pattern("[network-traffic:protocols[*] NOT = 'udp']"), pattern("[network-traffic:protocols[*] = 'tcp']")];
+    assert_eq!(equivalence::redundant(&feed), Vec::<usize>::new());
+}
+
+/// Patterns whose disjunctive normal form would be too large are not compared, rather than expanded.
+#[test]
+fn it_limits_the_normal_form() {
+    let clauses: Vec<String> = (0..12).map(|i| format!("(file:size = {} OR file:name = '{}')", i, i)).collect();
+    let large = pattern(&format!("[{}]", clauses.join(" AND ")));
+    assert!(!equivalence::equivalent(&large, &large));
+    assert!(!equivalence::subsumes(&large, &large));
+    assert_eq!(equivalence::canonicalize(&large), patterns::normalizer::normalize(&large));
+    assert!(equivalence::redundant(&[large.clone(), large]).is_empty());
+    let small = pattern(&format!("[{}]", clauses[..8].join(" AND ")));
+    assert!(equivalence::equivalent(&small, &small));
+}
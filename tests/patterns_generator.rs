@@ -0,0 +1,77 @@
+use validator::Validate;
+use stix4rust::core::STIXObject;
+use stix4rust::core::patterns::{self, generator::{self, GenerateError, Join}, matcher, pattern_type::PatternType};
+use stix4rust::core::sdos::observed_data::ObservedData;
+
+fn objects(texts: &[&str]) -> Vec<Box<dyn STIXObject>> {
+    texts.iter().map(|text| serde_json::from_str(text).unwrap()).collect()
+}
+
+/// Every observed property is compared, with the quotes and backslashes of the strings escaped.
+#[test]
+fn it_generates_patterns_from_objects() {
+    let objects = objects(&[
+        r#"{"type": "file", "id": "file--1", "spec_version": "2.1", "name": "it's C:\\evil.exe", "size": 2048,
+            "hashes": {"SHA-256": "aec070645fe53ee3b3763059376134f058cc337247c978add178b6ccdfb0019f"}, "mtime": "2020-01-01T10:00:00.5Z"}"#,
+    ]);
+    let pattern = generator::generate(&objects, Join::Any).unwrap();
+    let text = pattern.to_string();
+    assert_eq!(
+        text,
+        "[file:hashes.'SHA-256' = 'aec070645fe53ee3b3763059376134f058cc337247c978add178b6ccdfb0019f' AND file:mtime = t'2020-01-01T10:00:00.500Z' \
+         AND file:name = 'it\\'s C:\\\\evil.exe' AND file:size = 2048]"
+    );
+    assert_eq!(patterns::parse(&text).unwrap(), pattern);
+}
+
+/// References to other objects of the list are followed instead of getting observations of their own.
+#[test]
+fn it_follows_references() {
+    let objects = objects(&[
+        r#"{"type": "ipv4-addr", "id": "ipv4-addr--2", "value": "198.51.100.3"}"#,
+        r#"{"type": "network-traffic", "id": "network-traffic--1", "src_ref": "ipv4-addr--9", "dst_ref": "ipv4-addr--2", "dst_port": 443, "protocols": ["ipv4", "tcp"]}"#,
+        r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#,
+    ]);
+    let pattern = generator::generate(&objects, Join::All).unwrap();
+    assert_eq!(
+        pattern.to_string(),
+        "[network-traffic:dst_port = 443 AND network-traffic:dst_ref.value = '198.51.100.3' AND network-traffic:protocols[*] = 'ipv4' \
+         AND network-traffic:protocols[*] = 'tcp'] AND [domain-name:value = 'evil.com']"
+    );
+    let observed_data: ObservedData = serde_json::from_value(serde_json::json!({
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--1",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "first_observed": "2020-01-01T00:00:00Z",
+        "last_observed": "2020-01-01T00:00:00Z",
+        "number_observed": 2,
+        "object_refs": ["network-traffic--1", "ipv4-addr--2", "domain-name--1"]
+    }))
+    .unwrap();
+    assert!(matcher::is_match(&pattern, &[observed_data], &objects));
+}
+
+/// The generated pattern is wrapped into a new valid indicator.
+#[test]
+fn it_wraps_patterns_into_indicators() {
+    let objects = objects(&[r#"{"type": "url", "id": "url--1", "value": "http://evil.com/a'b"}"#]);
+    let indicator = generator::indicator(&objects, Join::Any).unwrap();
+    assert!(indicator.id.starts_with("indicator--"));
+    assert_eq!(indicator.pattern, "[url:value = 'http://evil.com/a\\'b']");
+    assert_eq!(indicator.pattern_type, PatternType::Stix);
+    assert_eq!(indicator.valid_from, indicator.created);
+    assert!(indicator.validate().is_ok());
+}
+
+/// Only SCOs with some property to compare can be turned into patterns.
+#[test]
+fn it_rejects_other_objects() {
+    let identity = objects(&[r#"{"type": "identity", "spec_version": "2.1", "id": "identity--1", "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z", "name": "ACME"}"#]);
+    assert_eq!(generator::generate(&identity, Join::Any), Err(GenerateError::NotObservable("identity--1".to_string())));
+    assert_eq!(generator::generate(&[], Join::Any), Err(GenerateError::NoObjects));
+    let empty = objects(&[r#"{"type": "file", "id": "file--1", "spec_version": "2.1"}"#]);
+    assert_eq!(generator::generate(&empty, Join::Any), Err(GenerateError::NoObjects));
+}
@@ -0,0 +1,94 @@
+use stix4rust::core::STIXObject;
+use stix4rust::core::patterns::{self, matcher};
+use stix4rust::core::sdos::observed_data::ObservedData;
+
+fn observed_data(id: &str, first_observed: &str, last_observed: &str, number_observed: u64, object_refs: &[&str]) -> ObservedData {
+    serde_json::from_value(serde_json::json!({
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": id,
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "first_observed": first_observed,
+        "last_observed": last_observed,
+        "number_observed": number_observed,
+        "object_refs": object_refs
+    })).unwrap()
+}
+
+fn objects() -> Vec<Box<dyn STIXObject>> {
+    let texts = [
+        r#"{"type": "file", "id": "file--1", "name": "evil.exe", "size": 2048, "hashes": {"MD5": "79054025255FB1A26E4BC422AEF54EB4"}}"#,
+        r#"{"type": "file", "id": "file--2", "name": "good.txt", "size": 10}"#,
+        r#"{"type": "ipv4-addr", "id": "ipv4-addr--1", "value": "10.1.2.3"}"#,
+        r#"{"type": "ipv4-addr", "id": "ipv4-addr--2", "value": "198.51.100.3"}"#,
+        r#"{"type": "network-traffic", "id": "network-traffic--1", "dst_ref": "ipv4-addr--2", "dst_port": 443, "protocols": ["ipv4", "tcp"]}"#,
+    ];
+    texts.iter().map(|text| serde_json::from_str(text).unwrap()).collect()
+}
+
+fn observations() -> Vec<ObservedData> {
+    vec![
+        observed_data("observed-data--1", "2020-01-01T00:00:00Z", "2020-01-01T00:00:00Z", 1, &["file--1", "ipv4-addr--1"]),
+        observed_data("observed-data--2", "2020-01-01T00:01:00Z", "2020-01-01T00:01:00Z", 1, &["network-traffic--1", "ipv4-addr--2"]),
+        observed_data("observed-data--3", "2020-01-02T00:00:00Z", "2020-01-02T00:00:00Z", 3, &["file--2"]),
+    ]
+}
+
+fn matches(text: &str) -> bool {
+    matcher::is_match(&patterns::parse(text).unwrap(), &observations(), &objects())
+}
+
+/// Comparisons over properties, nested properties and references are evaluated.
+#[test]
+fn it_matches_comparisons() {
+    assert!(matches("[file:name = 'evil.exe']"));
+    assert!(matches("[file:hashes.MD5 = h'79054025255fb1a26e4bc422aef54eb4']"));
+    assert!(matches("[file:size > 1024 AND file:name LIKE 'ev_l.%']"));
+    assert!(matches("[file:name MATCHES '^e.*\\\\.exe$']"));
+    assert!(matches("[network-traffic:dst_ref.value = '198.51.100.3' AND network-traffic:protocols[*] = 'tcp']"));
+    assert!(matches("[ipv4-addr:value ISSUBSET '10.0.0.0/8']"));
+    assert!(matches("[file:name IN ('a.exe', 'evil.exe')]"));
+    assert!(matches("[file:name NOT = 'good.txt']"));
+    assert!(!matches("[file:name = 'missing.exe']"));
+    assert!(!matches("[file:missing NOT = 'x']"));
+}
+
+/// Comparisons of the same object type joined by AND must be satisfied by the same object.
+#[test]
+fn it_binds_same_object_type_to_the_same_object() {
+    assert!(!matches("[file:name = 'evil.exe' AND file:size = 10]"));
+    assert!(matches("[file:name = 'evil.exe' AND ipv4-addr:value = '10.1.2.3']"));
+    assert!(!matches("[file:name = 'evil.exe' AND ipv4-addr:value = '198.51.100.3']"));
+}
+
+/// FOLLOWEDBY honors the order of the observations and WITHIN and START/STOP their time span.
+#[test]
+fn it_honors_timestamps() {
+    assert!(matches("[file:name = 'evil.exe'] FOLLOWEDBY [network-traffic:dst_port = 443]"));
+    assert!(!matches("[network-traffic:dst_port = 443] FOLLOWEDBY [file:name = 'evil.exe']"));
+    assert!(matches("([file:name = 'evil.exe'] AND [network-traffic:dst_port = 443]) WITHIN 60 SECONDS"));
+    assert!(!matches("([file:name = 'evil.exe'] AND [network-traffic:dst_port = 443]) WITHIN 59 SECONDS"));
+    assert!(matches("[file:name = 'good.txt'] START t'2020-01-02T00:00:00Z' STOP t'2020-01-03T00:00:00Z'"));
+    assert!(!matches("[file:name = 'evil.exe'] START t'2020-01-02T00:00:00Z' STOP t'2020-01-03T00:00:00Z'"));
+}
+
+/// REPEATS counts distinct observations, including those summarized by number_observed.
+#[test]
+fn it_honors_repeats() {
+    assert!(matches("[file:name = 'good.txt'] REPEATS 3 TIMES"));
+    assert!(!matches("[file:name = 'good.txt'] REPEATS 4 TIMES"));
+    assert!(!matches("[file:name = 'evil.exe'] REPEATS 2 TIMES"));
+    assert!(!matches("[file:name = 'evil.exe'] AND [file:name = 'evil.exe']"));
+}
+
+/// The matches report the observed data and the objects that satisfied the pattern.
+#[test]
+fn it_reports_how_the_pattern_matches() {
+    let pattern = patterns::parse("[file:name = 'evil.exe'] AND [network-traffic:dst_port = 443]").unwrap();
+    let found = matcher::find_matches(&pattern, &observations(), &objects());
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].observations[0].observed_data_ref, "observed-data--1");
+    assert_eq!(found[0].observations[0].object_refs, vec!["file--1".to_string()]);
+    assert_eq!(found[0].observations[1].observed_data_ref, "observed-data--2");
+}
@@ -0,0 +1,57 @@
+use stix4rust::core::patterns;
+
+/// Printing a parsed pattern keeps the parentheses written in the original text.
+#[test]
+fn it_prints_parsed_patterns() {
+    let text = "([file:name = 'a\\'b' AND (file:size > 10 OR file:size < 2)] FOLLOWEDBY [ipv4-addr:value = '10.0.0.1']) WITHIN 300 SECONDS";
+    let pattern = patterns::parse(text).unwrap();
+    assert_eq!(pattern.to_string(), text);
+}
+
+/// The printed text of any pattern can be parsed again into the same tree.
+#[test]
+fn it_round_trips_printed_patterns() {
+    let texts = [
+        "[file:extensions.'windows-pebinary-ext'.sections[*].entropy > 7.0]",
+        "[file:hashes.'SHA-256' = h'ABCD' AND artifact:payload_bin = b'VGhpcyBpcyBhIHRlc3Q=']",
+        "[file:created = t'2016-01-01T00:00:00.123Z'] REPEATS 5 TIMES START t'2016-01-01T00:00:00Z' STOP t'2017-01-01T00:00:00Z'",
+        "[file:name IN ('a', 'b') AND file:x_flag = true AND NOT_A_KEYWORD:'AND' = 1]",
+        "[file:size = 1000000000000000.0 AND file:x_ratio = -123456789012345678901234567890.0 AND file:x_tiny = 0.000000000000000000001]",
+    ];
+    for text in texts.iter() {
+        let pattern = patterns::parse(text).unwrap();
+        assert_eq!(patterns::parse(&pattern.to_string()).unwrap(), pattern);
+    }
+}
+
+/// Commutative operands are sorted, nested groups flattened and redundant parentheses removed.
+#[test]
+fn it_normalizes_equivalent_patterns() {
+    let first = patterns::parse("([ipv4-addr:value = '10.0.0.1'] OR ([domain-name:value = 'a.com'] OR [url:value = 'http://a.com']))").unwrap();
+    let second = patterns::parse("[url:value = 'http://a.com'] OR [domain-name:value = 'a.com'] OR [ipv4-addr:value = '10.0.0.1']").unwrap();
+    assert_eq!(first.canonical(), second.canonical());
+    assert_eq!(
+        first.canonical(),
+        "[domain-name:value = 'a.com'] OR [ipv4-addr:value = '10.0.0.1'] OR [url:value = 'http://a.com']"
+    );
+}
+
+/// Parentheses required by the operator precedence are kept by the normalization.
+#[test]
+fn it_keeps_required_parentheses() {
+    let pattern = patterns::parse("[file:size = 1 AND ((file:name = 'b' OR file:name = 'a'))] AND ([x:y = 1] OR [x:y = 2])").unwrap();
+    assert_eq!(
+        pattern.canonical(),
+        "[(file:name = 'a' OR file:name = 'b') AND file:size = 1] AND ([x:y = 1] OR [x:y = 2])"
+    );
+}
+
+/// Duplicated comparisons and set items are removed, but not duplicated observations nor FOLLOWEDBY operands reordered.
+#[test]
+fn it_deduplicates_comparisons_only() {
+    let pattern = patterns::parse("[file:name = 'a' AND file:name = 'a' AND file:name IN ('z', 'a', 'z')] FOLLOWEDBY [x:y = 2] FOLLOWEDBY [x:y = 1] AND [x:y = 1]").unwrap();
+    assert_eq!(
+        pattern.canonical(),
+        "[file:name = 'a' AND file:name IN ('a', 'z')] FOLLOWEDBY [x:y = 2] FOLLOWEDBY [x:y = 1] AND [x:y = 1]"
+    );
+}
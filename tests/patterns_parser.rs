@@ -0,0 +1,124 @@
+use stix4rust::core::patterns::{self, PatternErrorKind};
+use stix4rust::core::patterns::ast::{
+    ComparisonExpression,
+    ComparisonOperator,
+    ListIndex,
+    Literal,
+    ObservationExpression,
+    PathComponent,
+    Qualifier,
+};
+
+/// A simple observation with a single comparison.
+#[test]
+fn it_parses_single_comparison() {
+    let pattern = patterns::parse("[file:hashes.'SHA-256' = 'aec070645fe53ee3b3763059376134f058cc337247c978add178b6ccdfb0019f']").unwrap();
+    match pattern.expression {
+        ObservationExpression::Observation(ComparisonExpression::Comparison(comparison)) => {
+            assert_eq!(comparison.path.object_type, "file");
+            assert_eq!(comparison.path.components, vec![
+                PathComponent::Property("hashes".to_string()),
+                PathComponent::Property("SHA-256".to_string()),
+            ]);
+            assert_eq!(comparison.operator, ComparisonOperator::Equal);
+            assert!(!comparison.negated);
+        }
+        other => panic!("unexpected expression {:?}", other),
+    }
+}
+
+/// Observation operators keep their precedence: AND binds tighter than OR, which binds tighter than FOLLOWEDBY.
+#[test]
+fn it_parses_observation_operators_precedence() {
+    let pattern = patterns::parse("[a:x = 1] AND [b:y = 2] OR [c:z = 3] FOLLOWEDBY [d:w = 4]").unwrap();
+    match pattern.expression {
+        ObservationExpression::FollowedBy(items) => {
+            assert_eq!(items.len(), 2);
+            match &items[0] {
+                ObservationExpression::Or(operands) => assert!(matches!(operands[0], ObservationExpression::And(_))),
+                other => panic!("unexpected expression {:?}", other),
+            }
+        }
+        other => panic!("unexpected expression {:?}", other),
+    }
+}
+
+/// Every comparison operator, negation and literal type is accepted.
+#[test]
+fn it_parses_every_operator_and_literal() {
+    let text = "[file:size > 10 AND file:size <= 20.5 AND file:name NOT LIKE 'a%' AND file:name MATCHES '^a\\\\.exe$' \
+        AND ipv4-addr:value ISSUBSET '10.0.0.0/8' AND ipv4-addr:value ISSUPERSET '10.0.0.1' AND file:name IN ('a', 'b') \
+        AND file:mime_type != 'text/plain' AND artifact:payload_bin = b'VGhpcyBpcyBhIHRlc3Q=' AND file:hashes.MD5 = h'AbCd' \
+        AND file:created = t'2016-01-01T00:00:00Z' AND file:x_flag = true AND EXISTS file:parent_directory_ref]";
+    let pattern = patterns::parse(text).unwrap();
+    match pattern.expression {
+        ObservationExpression::Observation(ComparisonExpression::And(items)) => {
+            assert_eq!(items.len(), 13);
+            match &items[2] {
+                ComparisonExpression::Comparison(comparison) => assert!(comparison.negated),
+                other => panic!("unexpected expression {:?}", other),
+            }
+            match &items[9] {
+                ComparisonExpression::Comparison(comparison) => assert_eq!(comparison.value, Literal::Hex("abcd".to_string())),
+                other => panic!("unexpected expression {:?}", other),
+            }
+            assert!(matches!(items[12], ComparisonExpression::Exists(_)));
+        }
+        other => panic!("unexpected expression {:?}", other),
+    }
+}
+
+/// List indices, the any-index wildcard and extensions can be used in object paths.
+#[test]
+fn it_parses_object_paths_with_indices() {
+    let pattern = patterns::parse("[file:extensions.'windows-pebinary-ext'.sections[*].entropy > 7.0 AND email-message:to_refs[0].value = 'a@b.c']").unwrap();
+    let paths = pattern.object_paths();
+    assert_eq!(paths[0].components[3], PathComponent::Index(ListIndex::Any));
+    assert_eq!(paths[1].object_type, "email-message");
+    assert_eq!(paths[1].components[1], PathComponent::Index(ListIndex::Position(0)));
+}
+
+/// Qualifiers can be chained after observation expressions.
+#[test]
+fn it_parses_qualifiers() {
+    let pattern = patterns::parse("([a:x = 1] FOLLOWEDBY [b:y = 2]) WITHIN 300 SECONDS REPEATS 2 TIMES START t'2016-01-01T00:00:00Z' STOP t'2016-01-02T00:00:00Z'").unwrap();
+    match pattern.expression {
+        ObservationExpression::Qualified(inner, Qualifier::StartStop(start, stop)) => {
+            assert!(start < stop);
+            match *inner {
+                ObservationExpression::Qualified(inner, Qualifier::Repeats(2)) => {
+                    assert!(matches!(*inner, ObservationExpression::Qualified(_, Qualifier::Within(seconds)) if seconds == 300.0));
+                }
+                other => panic!("unexpected expression {:?}", other),
+            }
+        }
+        other => panic!("unexpected expression {:?}", other),
+    }
+}
+
+/// Errors report the position where the problem was found.
+#[test]
+fn it_reports_error_positions() {
+    let error = patterns::parse("[file:name = 'foo' AND ]").unwrap_err();
+    assert_eq!(error.position, 23);
+    let error = patterns::parse("[file:name = 'foo'").unwrap_err();
+    assert_eq!(error.position, 18);
+    assert!(matches!(error.kind, PatternErrorKind::UnexpectedEnd { .. }));
+    let error = patterns::parse("[file:name = 'foo]").unwrap_err();
+    assert_eq!(error.kind, PatternErrorKind::UnterminatedString);
+    assert_eq!(error.position, 13);
+    let error = patterns::parse("[file:name LIKE 10]").unwrap_err();
+    assert_eq!(error.position, 16);
+}
+
+/// Invalid literals and qualifiers are rejected.
+#[test]
+fn it_rejects_invalid_literals_and_qualifiers() {
+    assert!(patterns::parse("[file:hashes.MD5 = h'abc']").is_err());
+    assert!(patterns::parse("[file:created = t'2016-01-01']").is_err());
+    assert!(patterns::parse("[file:name = 'a'] REPEATS 0 TIMES").is_err());
+    assert!(patterns::parse("[file:name = 'a'] START t'2016-01-02T00:00:00Z' STOP t'2016-01-01T00:00:00Z'").is_err());
+    assert!(patterns::parse("[file:name > true]").is_err());
+    assert!(patterns::parse("[file:name = 'it\\'s'] ").is_ok());
+    assert!(patterns::parse("[file:name = 'bad \\n escape']").is_err());
+}
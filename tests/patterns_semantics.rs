@@ -0,0 +1,67 @@
+use stix4rust::core::patterns::{self, semantics::{self, Severity}};
+
+fn diagnostics(text: &str) -> Vec<(Severity, String)> {
+    semantics::check(&patterns::parse(text).unwrap())
+        .into_iter()
+        .map(|diagnostic| (diagnostic.severity, diagnostic.message))
+        .collect()
+}
+
+/// Patterns following the SCO definitions do not raise any diagnostic.
+#[test]
+fn it_accepts_valid_paths() {
+    let texts = [
+        "[file:hashes.'SHA-256' = 'aec070645fe53ee3b3763059376134f058cc337247c978add178b6ccdfb0019f']",
+        "[file:extensions.'windows-pebinary-ext'.sections[*].entropy > 7]",
+        "[network-traffic:dst_ref.value ISSUBSET '10.0.0.0/8' AND network-traffic:protocols[*] = 'tcp']",
+        "[email-message:from_ref.value MATCHES '.+@example\\\\.com$' AND email-message:body_multipart[0].body_raw_ref.name = 'a.exe']",
+        "[artifact:payload_bin = b'VGhpcyBpcyBhIHRlc3Q=' AND file:magic_number_hex = h'ffd8']",
+        "[windows-registry-key:values[*].data = 'foo' AND process:extensions.'windows-process-ext'.aslr_enabled = true]",
+        "[file:created = 'x'] FOLLOWEDBY [ipv4-addr:value = '10.0.0.1']",
+    ];
+    for text in texts.iter().take(6) {
+        assert_eq!(diagnostics(text), vec![], "{}", text);
+    }
+    assert!(!semantics::is_valid(&patterns::parse(texts[6]).unwrap()));
+}
+
+/// The common properties of the SCOs can be compared like their specific ones.
+#[test]
+fn it_accepts_common_properties() {
+    assert_eq!(diagnostics("[file:type = 'file']"), vec![]);
+    assert_eq!(diagnostics("[file:granular_markings[*].marking_ref = 'marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da']"), vec![]);
+    assert_eq!(diagnostics("[ipv4-addr:defanged = true AND ipv4-addr:spec_version = '2.1']"), vec![]);
+    assert_eq!(diagnostics("[file:granular_markings[*].unknown = 'x']")[0].0, Severity::Error);
+}
+
+/// Unknown properties, types and extensions are errors.
+#[test]
+fn it_rejects_unknown_paths() {
+    assert_eq!(diagnostics("[file:nonexistent = 'x']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[fiel:name = 'x']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[file:extensions.'unknown-ext'.foo = 'x']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[network-traffic:dst_ref.nonexistent = 'x']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[file:name[0] = 'x']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[network-traffic:protocols = 'tcp']")[0].0, Severity::Error);
+}
+
+/// Literals MUST suit the type of the property compared.
+#[test]
+fn it_rejects_wrong_value_types() {
+    assert_eq!(diagnostics("[file:size = '10']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[file:name = 10]")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[file:size IN (1, 'two')]").len(), 1);
+    assert_eq!(diagnostics("[user-account:is_privileged > 1]")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[file:size LIKE '1%']")[0].0, Severity::Error);
+    assert_eq!(diagnostics("[file:ctime = '2016-01-01T00:00:00Z']")[0].0, Severity::Warning);
+    assert_eq!(diagnostics("[file:ctime = t'2016-01-01T00:00:00Z']"), vec![]);
+}
+
+/// Custom object types, properties and extensions only raise warnings.
+#[test]
+fn it_warns_on_custom_names() {
+    assert_eq!(diagnostics("[x-acme-widget:serial = 'x']")[0].0, Severity::Warning);
+    assert_eq!(diagnostics("[file:x_acme_score > 5]")[0].0, Severity::Warning);
+    assert_eq!(diagnostics("[file:extensions.'x-acme-ext'.foo = 1]")[0].0, Severity::Warning);
+    assert!(semantics::is_valid(&patterns::parse("[x-acme-widget:serial = 'x']").unwrap()));
+}
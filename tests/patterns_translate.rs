@@ -0,0 +1,193 @@
+use stix4rust::core::patterns;
+use stix4rust::core::patterns::pattern_type::PatternType;
+use stix4rust::core::patterns::translate::{network, sigma, yara, RuleMetadata, TranslationError};
+use stix4rust::core::patterns::translate::network::{Dialect, NetworkRuleOptions};
+use stix4rust::core::sdos::indicator::Indicator;
+
+fn metadata() -> RuleMetadata {
+    RuleMetadata {
+        id: "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f".to_string(),
+        title: "Poison Ivy".to_string(),
+        description: Some("Poison Ivy \"dropper\"".to_string()),
+    }
+}
+
+fn indicator(pattern: &str, pattern_type: &str) -> Indicator {
+    serde_json::from_value(serde_json::json!({
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "name": "Poison Ivy",
+        "pattern": pattern,
+        "pattern_type": pattern_type,
+        "valid_from": "2016-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+/// Process and registry comparisons are written as Sigma detections, one rule per log source.
+#[test]
+fn it_translates_to_sigma() {
+    let pattern = patterns::parse(
+        "[process:name = 'cmd.exe' AND process:command_line LIKE '%/c whoami%' AND process:parent_ref.name != 'explorer.exe'] \
+         OR [windows-registry-key:key = 'HKEY_LOCAL_MACHINE\\\\Software\\\\Run']",
+    )
+    .unwrap();
+    let translation = sigma::translate_pattern(&pattern, &metadata());
+    assert!(translation.is_complete(), "{:?}", translation.untranslated);
+    let documents: Vec<serde_yaml::Value> = translation.rules.split("---\n").map(|document| serde_yaml::from_str(document).unwrap()).collect();
+    assert_eq!(documents.len(), 2);
+    let process = &documents[0];
+    assert_eq!(process["id"], "8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f");
+    assert_eq!(process["logsource"]["category"], "process_creation");
+    assert_eq!(process["detection"]["selection1"]["Image|endswith"], "\\cmd.exe");
+    assert_eq!(process["detection"]["selection1"]["CommandLine"], "*/c whoami*");
+    assert_eq!(process["detection"]["filter1"]["ParentImage|endswith"], "\\explorer.exe");
+    assert_eq!(process["detection"]["condition"], "selection1 and not filter1");
+    let registry = &documents[1];
+    assert_eq!(registry["logsource"]["category"], "registry_event");
+    assert_eq!(registry["detection"]["selection1"]["TargetObject|startswith"], "HKLM\\software\\run");
+}
+
+/// Network comparisons and correlations that a Sigma rule cannot express are reported.
+#[test]
+fn it_reports_untranslated_fragments() {
+    let pattern = patterns::parse("([process:name = 'a.exe'] FOLLOWEDBY [ipv4-addr:value = '10.0.0.1']) WITHIN 60 SECONDS").unwrap();
+    let translation = sigma::translate_pattern(&pattern, &metadata());
+    let fragments: Vec<&str> = translation.untranslated.iter().map(|untranslated| untranslated.fragment.as_str()).collect();
+    assert_eq!(
+        fragments,
+        vec![
+            "([process:name = 'a.exe'] FOLLOWEDBY [ipv4-addr:value = '10.0.0.1']) WITHIN 60 SECONDS",
+            "[process:name = 'a.exe'] FOLLOWEDBY [ipv4-addr:value = '10.0.0.1']",
+            "ipv4-addr:value = '10.0.0.1'",
+        ]
+    );
+    assert!(translation.rules.contains("Image|endswith"));
+}
+
+/// Addresses, ports and protocols go to the rule header, domains and URLs to content matches.
+#[test]
+fn it_translates_to_suricata() {
+    let pattern = patterns::parse(
+        "[network-traffic:dst_ref.value ISSUBSET '198.51.100.0/24' AND network-traffic:dst_port > 1024 AND network-traffic:protocols[*] = 'tcp'] \
+         OR [domain-name:value IN ('evil.com', 'bad.org')] OR [url:value = 'http://evil.com/a;b']",
+    )
+    .unwrap();
+    let translation = network::translate_pattern(&pattern, &metadata(), NetworkRuleOptions::default());
+    assert!(translation.is_complete(), "{:?}", translation.untranslated);
+    let rules: Vec<&str> = translation.rules.lines().collect();
+    assert_eq!(rules.len(), 4);
+    assert!(rules.contains(&"alert dns any any -> any any (msg:\"Poison Ivy\"; dns.query; content:\"bad.org\"; nocase; bsize:7; metadata:stix_id indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f; sid:1000000; rev:1;)"));
+    assert!(rules.iter().any(|rule| rule.starts_with("alert tcp any any -> 198.51.100.0/24 1025: (")));
+    assert!(rules.iter().any(|rule| rule.contains("http.host; content:\"evil.com\"; nocase; bsize:8; http.uri; content:\"/a|3B|b\"; bsize:4;")));
+    let sids: Vec<&str> = rules.iter().map(|rule| rule.split("sid:").nth(1).unwrap()).collect();
+    assert_eq!(sids, vec!["1000000; rev:1;)", "1000001; rev:1;)", "1000002; rev:1;)", "1000003; rev:1;)"]);
+}
+
+/// Snort rules use their own buffer names, and have no buffer for the DNS queries.
+#[test]
+fn it_translates_to_snort() {
+    let pattern = patterns::parse("[network-traffic:extensions.'http-request-ext'.request_method = 'POST'] OR [domain-name:value = 'evil.com']").unwrap();
+    let options = NetworkRuleOptions { dialect: Dialect::Snort, first_sid: 5 };
+    let translation = network::translate_pattern(&pattern, &metadata(), options);
+    assert_eq!(
+        translation.rules,
+        "alert http any any -> any any (msg:\"Poison Ivy\"; http_method; content:\"POST\"; bufferlen:4; metadata:stix_id indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f; sid:5; rev:1;)\n"
+    );
+    assert_eq!(translation.untranslated.len(), 1);
+    assert_eq!(translation.untranslated[0].fragment, "domain-name:value = 'evil.com'");
+}
+
+/// File hashes, names and sizes are written as a YARA condition.
+#[test]
+fn it_translates_to_yara() {
+    let pattern = patterns::parse(
+        "[file:hashes.'SHA-256' = 'AEC070645FE53EE3B3763059376134F058CC337247C978ADD178B6CCDFB0019F' AND file:size < 1000] \
+         OR [file:name LIKE '%.scr' AND file:magic_number_hex = h'4D5A'] OR [file:hashes.'SSDEEP' = '3:a:b']",
+    )
+    .unwrap();
+    let translation = yara::translate_pattern(&pattern, &metadata());
+    assert_eq!(
+        translation.rules,
+        "import \"hash\"\n\
+         \n\
+         // The filename external variable must be defined when compiling this rule.\n\
+         rule Poison_Ivy {\n    meta:\n        description = \"Poison Ivy \\\"dropper\\\"\"\n        stix_id = \"indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f\"\n\
+         \x20   strings:\n        $magic1 = { 4D 5A }\n\
+         \x20   condition:\n        (hash.sha256(0, filesize) == \"aec070645fe53ee3b3763059376134f058cc337247c978add178b6ccdfb0019f\" and filesize < 1000) or\n        \
+         ($magic1 at 0 and filename matches /^.*\\.scr$/)\n}\n"
+    );
+    assert_eq!(translation.untranslated.len(), 1);
+    assert_eq!(translation.untranslated[0].reason, "the SSDEEP hash cannot be computed by YARA");
+}
+
+/// Only the indicators whose patterns are written in STIX can be translated.
+#[test]
+fn it_translates_stix_indicators_only() {
+    let translation = yara::translate(&indicator("[file:name = 'a.exe']", "stix")).unwrap();
+    assert!(translation.rules.contains("rule Poison_Ivy"));
+    assert_eq!(
+        sigma::translate(&indicator("rule a { condition: true }", "yara")),
+        Err(TranslationError::UnsupportedPatternType("yara".to_string()))
+    );
+    assert!(matches!(network::translate(&indicator("[file:name = ", "stix"), NetworkRuleOptions::default()), Err(TranslationError::InvalidPattern(_))));
+}
+
+/// The rules written are accepted by the checkers of their pattern type.
+#[test]
+fn it_writes_well_formed_rules() {
+    let pattern = patterns::parse(
+        "[process:name = 'cmd.exe' AND process:pid > 4] OR [domain-name:value = 'evil.com'] OR [file:hashes.MD5 = '79054025255fb1a26e4bc422aef54eb4']",
+    )
+    .unwrap();
+    assert_eq!(PatternType::Sigma.check(&sigma::translate_pattern(&pattern, &metadata()).rules), Ok(()));
+    assert_eq!(PatternType::Suricata.check(&network::translate_pattern(&pattern, &metadata(), NetworkRuleOptions::default()).rules), Ok(()));
+    assert_eq!(PatternType::Yara.check(&yara::translate_pattern(&pattern, &metadata()).rules), Ok(()));
+}
+
+/// The regular expressions reach the rules as they are written, even on the properties compared regardless of their case.
+#[test]
+fn it_keeps_case_sensitive_regular_expressions() {
+    let pattern = patterns::parse("[file:hashes.MD5 MATCHES '^D41D[0-9A-F]+$' AND file:hashes.'SHA-256' = 'AEC070']").unwrap();
+    let translation = yara::translate_pattern(&pattern, &metadata());
+    assert!(translation.rules.contains("hash.md5(0, filesize) matches /^D41D[0-9A-F]+$/"), "{}", translation.rules);
+    assert!(translation.rules.contains("hash.sha256(0, filesize) == \"aec070\""), "{}", translation.rules);
+    let pattern = patterns::parse("[windows-registry-key:key LIKE 'HKEY_LOCAL_MACHINE\\\\Software\\\\%Run']").unwrap();
+    let translation = sigma::translate_pattern(&pattern, &metadata());
+    let document: serde_yaml::Value = serde_yaml::from_str(&translation.rules).unwrap();
+    assert_eq!(document["detection"]["selection1"]["TargetObject"], "HKEY?LOCAL?MACHINE\\Software\\*Run");
+}
+
+/// Sets are written as one rule per value only as long as the number of rules stays reasonable.
+#[test]
+fn it_limits_the_rules_written_for_sets() {
+    let set = |name: &str| format!("({})", (0..10).map(|i| format!("'{}{}.com'", name, i)).collect::<Vec<_>>().join(", "));
+    let text = format!(
+        "[domain-name:value IN {} AND url:value IN {} AND network-traffic:extensions.'http-request-ext'.request_method IN {} AND ipv4-addr:value = '10.0.0.1']",
+        set("a"),
+        set("b"),
+        set("c"),
+    );
+    let translation = network::translate_pattern(&patterns::parse(&text).unwrap(), &metadata(), NetworkRuleOptions::default());
+    assert_eq!(translation.rules.lines().count(), 1, "{}", translation.rules);
+    assert_eq!(translation.untranslated.len(), 3);
+    assert!(translation.untranslated.iter().all(|fragment| fragment.reason.starts_with("the sets compared would be written as more than")));
+    let pattern = patterns::parse("[domain-name:value IN ('a.com', 'b.com') AND network-traffic:dst_port IN (80, 8080)]").unwrap();
+    assert_eq!(network::translate_pattern(&pattern, &metadata(), NetworkRuleOptions::default()).rules.lines().count(), 2);
+}
+
+/// The port ranges that no port falls in are not written, as rules cannot express them.
+#[test]
+fn it_rejects_empty_port_ranges() {
+    let pattern = patterns::parse("[network-traffic:src_port < 0 AND network-traffic:dst_port > 65535 AND network-traffic:dst_ref.value = '10.0.0.1']").unwrap();
+    let translation = network::translate_pattern(&pattern, &metadata(), NetworkRuleOptions::default());
+    assert!(translation.rules.starts_with("alert ip any any -> 10.0.0.1 any ("), "{}", translation.rules);
+    let fragments: Vec<&str> = translation.untranslated.iter().map(|untranslated| untranslated.fragment.as_str()).collect();
+    assert_eq!(fragments, vec!["network-traffic:dst_port > 65535", "network-traffic:src_port < 0"]);
+    let pattern = patterns::parse("[network-traffic:src_port < 1 AND network-traffic:dst_port > 65534]").unwrap();
+    let translation = network::translate_pattern(&pattern, &metadata(), NetworkRuleOptions::default());
+    assert!(translation.rules.starts_with("alert ip any :0 -> any 65535: ("), "{}", translation.rules);
+}
@@ -0,0 +1,146 @@
+use serde::de::{self, value, Deserialize, Deserializer, Visitor};
+use stix4rust::core::STIXObject;
+use stix4rust::core::scos::*;
+use stix4rust::core::scos::file::File;
+use stix4rust::core::scos::network_traffic::NetworkTraffic;
+use stix4rust::core::scos::schema::{self, PropertyType};
+
+/// A deserializer which only records the names of the fields of the struct deserialized.
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de, 'a> Deserializer<'de> for FieldNames<'a> {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], _visitor: V) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+fn field_names<T: for<'de> Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Every SCO type can be deserialized as a generic STIX object from a minimal example of the standard.
+#[test]
+fn it_stix_objects_deserialization_for_every_type() {
+    let texts = [
+        r#"{"type": "artifact", "spec_version": "2.1", "id": "artifact--6f437177-6e48-5cf8-9d9e-872a2bddd641", "mime_type": "application/zip", "payload_bin": "ZX7HIBWPpsaZmiC7z2LZ0WSYhW4="}"#,
+        r#"{"type": "autonomous-system", "spec_version": "2.1", "id": "autonomous-system--f720c34b-98ae-597f-ade5-27dc241e8c74", "number": 15139, "name": "Slime Industries", "rir": "ARIN"}"#,
+        r#"{"type": "directory", "spec_version": "2.1", "id": "directory--93c0a9b0-520d-545d-9094-1a08ddf46b05", "path": "C:\\Windows\\System32"}"#,
+        r#"{"type": "domain-name", "spec_version": "2.1", "id": "domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5", "value": "example.com"}"#,
+        r#"{"type": "email-addr", "spec_version": "2.1", "id": "email-addr--2d77a846-6264-5d51-b586-e43822ea1ea3", "value": "john@example.com", "display_name": "John Doe"}"#,
+        r#"{"type": "email-message", "spec_version": "2.1", "id": "email-message--72b7698f-10c2-565a-a2a6-b4996a2f2265", "is_multipart": true, "body_multipart": [{"content_type": "text/plain; charset=utf-8", "body": "Cats are funny!"}]}"#,
+        r#"{"type": "file", "spec_version": "2.1", "id": "file--e277603e-1060-5ad4-9937-c26c97f1ca68", "hashes": {"SHA-256": "ceafbfd424be2ca4a5f0402cae090dda2fb0526cf521b60b60077c0f622b285a"}, "name": "qwerty.dll"}"#,
+        r#"{"type": "ipv4-addr", "spec_version": "2.1", "id": "ipv4-addr--ff26c055-6336-5bc5-b98d-13d6226742dd", "value": "198.51.100.3"}"#,
+        r#"{"type": "ipv6-addr", "spec_version": "2.1", "id": "ipv6-addr--1e61d36c-a16c-53b7-a80f-2a00161c96b1", "value": "2001:0db8:85a3:0000:0000:8a2e:0370:7334"}"#,
+        r#"{"type": "mac-addr", "spec_version": "2.1", "id": "mac-addr--65cfcf98-8a6e-5a1b-8f61-379ac4f92d00", "value": "d2:fb:49:24:37:18"}"#,
+        r#"{"type": "mutex", "spec_version": "2.1", "id": "mutex--eba44954-d4e4-5d3b-814c-2b17dd8de300", "name": "__CLEANSWEEP__"}"#,
+        r#"{"type": "network-traffic", "spec_version": "2.1", "id": "network-traffic--2568d22a-8998-58eb-99ec-3c8ca74f527d", "src_ref": "ipv4-addr--4d22aae0-2bf9-5427-8819-e4f6abf20a53", "dst_ref": "ipv4-addr--ff26c055-6336-5bc5-b98d-13d6226742dd", "protocols": ["tcp"]}"#,
+        r#"{"type": "process", "spec_version": "2.1", "id": "process--f52a906a-0dfc-40bd-92f1-e7778ead38a9", "pid": 1221, "command_line": "./gedit-bin --new-window"}"#,
+        r#"{"type": "software", "spec_version": "2.1", "id": "software--a1827f6d-ca53-5605-9e93-4316cd22a00a", "name": "Word", "cpe": "cpe:2.3:a:microsoft:word:2000:*:*:*:*:*:*:*", "version": "2002", "vendor": "Microsoft"}"#,
+        r#"{"type": "url", "spec_version": "2.1", "id": "url--c1477287-23ac-5971-a010-5c287877fa60", "value": "https://example.com/research/index.html"}"#,
+        r#"{"type": "user-account", "spec_version": "2.1", "id": "user-account--0d5b424b-93b8-5cd8-ac36-306e1789d63c", "user_id": "1001", "account_login": "jdoe", "is_privileged": false}"#,
+        r#"{"type": "windows-registry-key", "spec_version": "2.1", "id": "windows-registry-key--2ba37ae7-2745-5082-9dfd-9486dad41016", "key": "hkey_local_machine\\system\\bar\\foo", "values": [{"name": "Foo", "data": "qwerty", "data_type": "REG_SZ"}]}"#,
+        r#"{"type": "x509-certificate", "spec_version": "2.1", "id": "x509-certificate--463d7b2a-8516-5a50-a3d7-6f801465d5de", "issuer": "C=ZA, ST=Western Cape, L=Cape Town, O=Thawte Consulting cc", "validity_not_before": "2016-03-12T12:00:00Z", "validity_not_after": "2016-08-21T12:00:00Z", "subject": "C=US, ST=Maryland, L=Pasadena, O=Brent Baccala"}"#,
+    ];
+    for text in texts.iter() {
+        let object: Box<dyn STIXObject> = serde_json::from_str(text).unwrap();
+        let value = object.to_value();
+        assert_eq!(value["id"], serde_json::from_str::<serde_json::Value>(text).unwrap()["id"]);
+    }
+}
+
+/// Since there are some fields which are required, this test verifies that the deserialization method effectively detects that a panics.
+#[test]
+#[should_panic]
+fn it_stix_object_deserialization_with_missing_required_field() {
+    let text = r#"
+    {
+        "type": "network-traffic",
+        "spec_version": "2.1",
+        "id": "network-traffic--2568d22a-8998-58eb-99ec-3c8ca74f527d",
+        "src_ref": "ipv4-addr--4d22aae0-2bf9-5427-8819-e4f6abf20a53"
+    }
+    "#;
+    let _object: NetworkTraffic = serde_json::from_str(text).unwrap();
+}
+
+/// Extensions are kept as they are when (de)serializing the objects.
+#[test]
+fn it_stix_object_keeps_extensions() {
+    let text = r#"
+    {
+        "type": "file",
+        "spec_version": "2.1",
+        "id": "file--e277603e-1060-5ad4-9937-c26c97f1ca68",
+        "name": "foo.exe",
+        "extensions": {
+            "windows-pebinary-ext": {
+                "pe_type": "exe",
+                "sections": [{"name": ".text", "entropy": 7.5}]
+            }
+        }
+    }
+    "#;
+    let object: File = serde_json::from_str(text).unwrap();
+    let value = serde_json::to_value(&object).unwrap();
+    assert_eq!(value["extensions"]["windows-pebinary-ext"]["sections"][0]["entropy"], 7.5);
+    assert!(value.get("size").is_none());
+}
+
+/// Every property of the SCO structs, and of the objects they embed, is described by the schema checking the patterns.
+#[test]
+fn it_schema_describes_every_property() {
+    let types = [
+        ("artifact", field_names::<artifact::Artifact>()),
+        ("autonomous-system", field_names::<autonomous_system::AutonomousSystem>()),
+        ("directory", field_names::<directory::Directory>()),
+        ("domain-name", field_names::<domain_name::DomainName>()),
+        ("email-addr", field_names::<email_addr::EmailAddr>()),
+        ("email-message", field_names::<email_message::EmailMessage>()),
+        ("file", field_names::<File>()),
+        ("ipv4-addr", field_names::<ipv4_addr::Ipv4Addr>()),
+        ("ipv6-addr", field_names::<ipv6_addr::Ipv6Addr>()),
+        ("mac-addr", field_names::<mac_addr::MacAddr>()),
+        ("mutex", field_names::<mutex::Mutex>()),
+        ("network-traffic", field_names::<NetworkTraffic>()),
+        ("process", field_names::<process::Process>()),
+        ("software", field_names::<software::Software>()),
+        ("url", field_names::<url::Url>()),
+        ("user-account", field_names::<user_account::UserAccount>()),
+        ("windows-registry-key", field_names::<windows_registry_key::WindowsRegistryKey>()),
+        ("x509-certificate", field_names::<x509_certificate::X509Certificate>()),
+    ];
+    assert_eq!(types.len(), schema::OBJECT_TYPES.len());
+    for (object_type, fields) in types.iter() {
+        assert!(!fields.is_empty(), "{}", object_type);
+        for field in fields.iter() {
+            assert!(schema::property_type(object_type, field).is_some(), "{}:{}", object_type, field);
+        }
+    }
+    let embedded = [
+        ("email-message", "body_multipart", field_names::<email_message::EmailMimeComponent>()),
+        ("windows-registry-key", "values", field_names::<windows_registry_key::WindowsRegistryValue>()),
+    ];
+    for (object_type, property, fields) in embedded.iter() {
+        let properties = match schema::property_type(object_type, property) {
+            Some(PropertyType::List(PropertyType::Object(properties))) => properties,
+            other => panic!("{}:{} is {:?}", object_type, property, other),
+        };
+        for field in fields.iter() {
+            assert!(properties.iter().any(|(name, _)| name == field), "{}:{}.{}", object_type, property, field);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{Duration, Timelike, Utc};
+use stix4rust::core::patterns::pattern_type::PatternType;
+use stix4rust::core::sdos::builder::BuildError;
+use stix4rust::core::sdos::indicator::Indicator;
+use stix4rust::core::sdos::location::Location;
+use stix4rust::core::sdos::malware::Malware;
+use stix4rust::core::sdos::report::Report;
+use stix4rust::core::sros::relationship::Relationship;
+use stix4rust::core::sros::sighting::Sighting;
+
+/// The builder generates the identifier, the spec version and the timestamps, at millisecond precision.
+#[test]
+fn it_builds_objects_with_defaults() {
+    let before = Utc::now() - Duration::milliseconds(1);
+    let malware = Malware::builder()
+        .name("Cryptolocker")
+        .malware_types(vec!["ransomware".to_string()])
+        .is_family(true)
+        .created_by_ref("identity--f431f809-377b-45e0-aa1c-6a4751cae5ff")
+        .build()
+        .unwrap();
+    assert!(malware.id.starts_with("malware--"));
+    assert_eq!(malware.spec_version, "2.1");
+    assert_eq!(malware.created, malware.modified);
+    assert!(malware.created >= before && malware.created <= Utc::now());
+    assert_eq!(malware.created.nanosecond() % 1_000_000, 0);
+    assert_eq!(malware.name.as_deref(), Some("Cryptolocker"));
+    assert_eq!(malware.created_by_ref.as_deref(), Some("identity--f431f809-377b-45e0-aa1c-6a4751cae5ff"));
+    assert!(malware.description.is_none());
+    let other = Malware::builder().malware_types(vec!["ransomware".to_string()]).is_family(true).build().unwrap();
+    assert_ne!(malware.id, other.id);
+}
+
+/// The identifier and timestamps given are kept, as long as the identifier is one of the type built.
+#[test]
+fn it_builds_objects_with_given_properties() {
+    let created = Utc::now() - Duration::days(1);
+    let report = Report::builder()
+        .id("report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3")
+        .created(created)
+        .name("Poison Ivy")
+        .report_types(vec!["malware".to_string()])
+        .build()
+        .unwrap();
+    assert_eq!(report.id, "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3");
+    assert_eq!((report.created, report.modified), (created, created));
+    let error = Report::builder().id("malware--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3").name("Poison Ivy").report_types(vec![]).build().unwrap_err();
+    assert_eq!(error, BuildError::InvalidId("malware--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3".to_string()));
+}
+
+/// Building fails when a required property is missing or when the object is not valid.
+#[test]
+fn it_rejects_incomplete_or_invalid_objects() {
+    let error = Malware::builder().name("Cryptolocker").is_family(true).build().unwrap_err();
+    assert_eq!(error, BuildError::MissingProperty("malware_types".to_string()));
+    assert!(matches!(Location::builder().latitude(200.0).longitude(0.0).build(), Err(BuildError::Invalid(_))));
+    let indicator = Indicator::builder().pattern("[ file:name = 'foo.exe' ").pattern_type(PatternType::Stix).valid_from(Utc::now()).build();
+    assert!(matches!(indicator, Err(BuildError::Invalid(_))));
+    let indicator = Indicator::builder().pattern("[ file:name = 'foo.exe' ]").pattern_type(PatternType::Stix).valid_from(Utc::now()).build();
+    assert!(indicator.is_ok());
+}
+
+/// The SROs are built the same way, and validated as well.
+#[test]
+fn it_builds_sros() {
+    let now = Utc::now();
+    let relationship = Relationship::builder()
+        .relationship_type("indicates")
+        .source_ref("indicator--9299f726-ce06-492e-8472-2b52ccb53191")
+        .target_ref("malware--92ec0cbd-2c30-44a2-b270-73f4ec949841")
+        .start_time(now)
+        .build()
+        .unwrap();
+    assert!(relationship.id.starts_with("relationship--"));
+    assert_eq!(relationship.spec_version, "2.1");
+    assert_eq!(relationship.start_time, Some(now));
+    let error = Relationship::builder().relationship_type("indicates").source_ref("indicator--9299f726-ce06-492e-8472-2b52ccb53191").build().unwrap_err();
+    assert_eq!(error, BuildError::MissingProperty("target_ref".to_string()));
+    let sighting = Sighting::builder().sighting_of_ref("indicator--9299f726-ce06-492e-8472-2b52ccb53191").first_seen(now).last_seen(now - Duration::days(1)).build();
+    assert!(matches!(sighting, Err(BuildError::Invalid(_))));
+    let sighting = Sighting::builder().id("relationship--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3").sighting_of_ref("indicator--9299f726-ce06-492e-8472-2b52ccb53191").build();
+    assert_eq!(sighting.unwrap_err(), BuildError::InvalidId("relationship--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3".to_string()));
+}
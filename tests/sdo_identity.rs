@@ -1,9 +1,5 @@
 use chrono::{DateTime, Utc};
 use stix4rust::core::sdos::identity::Identity;
-use stix4rust::core::types::{
-    ExternalReference,
-    GranularMarking
-};
 
 /// A complete deserialization of the object grabbing values for each and every element.
 #[test]
@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use validator::Validate;
+use stix4rust::core::patterns::pattern_type::PatternType;
+use stix4rust::core::sdos::indicator::Indicator;
+
+/// A complete deserialization of the object grabbing values for each and every element.
+#[test]
+fn it_stix_object_complete_deserialization() {
+    let text = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "revoked": false,
+        "labels": ["hack"],
+        "confidence": 100,
+        "lang": "en",
+        "external_references": [
+            {
+                "source_name": "NASA",
+                "external_id": "NASA-2021"
+            }
+        ],
+        "granular_markings": [
+            {
+                "lang": "es",
+                "selectors": ["sel1", "sel2"]
+            }
+        ],
+        "object_marking_refs": [],
+        "indicator_types": ["malicious-activity"],
+        "name": "Poison Ivy Malware",
+        "description": "This file is part of Poison Ivy",
+        "pattern": "[ file:hashes.'SHA-256' = '4bac27393bdd9777ce02453256c5577cd02275510b2227f473d03f533924f877' ]",
+        "pattern_type": "stix",
+        "pattern_version": "2.1",
+        "valid_from": "2016-01-01T00:00:00Z",
+        "valid_until": "2017-01-01T00:00:00Z",
+        "kill_chain_phases": [
+            {
+                "kill_chain_name": "mandiant-attack-lifecycle-model",
+                "phase_name": "establish-foothold"
+            }
+        ]
+    }
+    "#;
+    let object: Indicator = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_ok());
+}
+
+/// A complete deserialization of a JSON object that has duplicate keys.
+#[test]
+#[should_panic]
+fn it_stix_object_complete_deserialization_with_duplicate_key() {
+    let text = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern": "[ file:name = 'foo.exe' ]",
+        "pattern": "[ file:name = 'bar.exe' ]",
+        "pattern_type": "stix",
+        "valid_from": "2016-01-01T00:00:00Z"
+    }
+    "#;
+    let _object: Indicator = serde_json::from_str(text).unwrap();
+}
+
+/// Deserialization of an indicator with only the required fields.
+#[test]
+fn it_stix_object_deserialization_with_required_fields_only() {
+    let text = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern": "[ file:name = 'foo.exe' ]",
+        "pattern_type": "stix",
+        "valid_from": "2016-01-01T00:00:00Z"
+    }
+    "#;
+    let _object: Indicator = serde_json::from_str(text).unwrap();
+}
+
+/// Since there are some fields which are required, this test verifies that the deserialization method effectively detects that a panics.
+#[test]
+#[should_panic]
+fn it_stix_object_deserialization_with_missing_required_field() {
+    let text = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern_type": "stix",
+        "valid_from": "2016-01-01T00:00:00Z"
+    }
+    "#;
+    let _object: Indicator = serde_json::from_str(text).unwrap();
+}
+
+/// Indicators whose STIX pattern cannot be parsed MUST be rejected by the validation.
+#[test]
+fn it_stix_object_validation_with_wrong_pattern() {
+    let text = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern": "[ file:name = 'foo.exe' ",
+        "pattern_type": "stix",
+        "valid_from": "2016-01-01T00:00:00Z"
+    }
+    "#;
+    let object: Indicator = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+}
+
+/// Indicators whose STIX pattern refers to properties not defined for the object type MUST be rejected by the validation.
+#[test]
+fn it_stix_object_validation_with_wrong_object_path() {
+    let text = r#"
+    {
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern": "[ file:nonexistent = 'x' ]",
+        "pattern_type": "stix",
+        "valid_from": "2016-01-01T00:00:00Z"
+    }
+    "#;
+    let object: Indicator = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+}
+
+/// Serialization test of the object with certain values for the object.
+/// Note that STIX 2.1 requires null values not appear in the object. This test verifies that optional values are not shown.
+#[test]
+fn it_stix_object_serialization() {
+    let object = Indicator {
+        id: "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f".to_string(),
+        spec_version: "2.1".to_string(),
+        created: DateTime::parse_from_rfc3339("2016-04-06T20:03:48Z").unwrap().with_timezone(&Utc),
+        modified: DateTime::parse_from_rfc3339("2016-04-06T20:03:48Z").unwrap().with_timezone(&Utc),
+        created_by_ref: None,
+        revoked: None,
+        labels: None,
+        confidence: None,
+        lang: None,
+        external_references: None,
+        object_marking_refs: None,
+        granular_markings: None,
+        name: Some("Poison Ivy Malware".to_string()),
+        description: None,
+        indicator_types: None,
+        pattern: "[ file:name = 'foo.exe' ]".to_string(),
+        pattern_type: PatternType::Stix,
+        pattern_version: None,
+        valid_from: DateTime::parse_from_rfc3339("2016-01-01T00:00:00Z").unwrap().with_timezone(&Utc),
+        valid_until: None,
+        kill_chain_phases: None,
+    };
+    let text = serde_json::to_string_pretty(&object).unwrap();
+    assert!(!text.contains("null"));
+}
+
+fn indicator_with_pattern(pattern: &str, pattern_type: &str) -> Indicator {
+    serde_json::from_value(serde_json::json!({
+        "type": "indicator",
+        "spec_version": "2.1",
+        "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2016-04-06T20:03:48.000Z",
+        "modified": "2016-04-06T20:03:48.000Z",
+        "pattern": pattern,
+        "pattern_type": pattern_type,
+        "valid_from": "2016-01-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+/// The pattern types of the vocabulary are typed, while the others are kept as they are.
+#[test]
+fn it_stix_object_pattern_type_round_trip() {
+    let object = indicator_with_pattern("rule a { condition: true }", "yara");
+    assert_eq!(object.pattern_type, PatternType::Yara);
+    let object = indicator_with_pattern("anything", "x-custom");
+    assert_eq!(object.pattern_type, PatternType::Other("x-custom".to_string()));
+    assert!(object.validate().is_ok());
+    assert!(serde_json::to_string(&object).unwrap().contains(r#""pattern_type":"x-custom""#));
+}
+
+/// Indicators written in the other languages of the vocabulary are validated according to their language.
+#[test]
+fn it_stix_object_validation_of_other_pattern_types() {
+    let valid = [
+        (r"^evil\d+\.exe$", "pcre"),
+        (r"/EVIL\.exe$/i", "pcre"),
+        ("title: Whoami\nlogsource:\n  category: process_creation\ndetection:\n  selection:\n    Image|endswith: '\\whoami.exe'\n  condition: selection", "sigma"),
+        (r#"alert tcp any any -> 10.0.0.0/8 445 (msg:"SMB; probe"; content:"|FF|SMB"; sid:1000001; rev:1;)"#, "suricata"),
+        (r#"alert http (msg:"post"; http_method; content:"POST"; sid:5;)"#, "snort"),
+        ("import \"pe\"\nrule a : tag {\n meta:\n  score = -1\n strings:\n  $a = \"x\\\"y\" wide\n  $b = { 4D 5A ?? [2-4] (00 | 01) }\n  $c = /ab+c/i\n condition:\n  pe.is_pe and ($a or #b > 2) and $c and not @b[1] == 0\n}", "yara"),
+    ];
+    for (pattern, pattern_type) in valid.iter() {
+        assert!(indicator_with_pattern(pattern, pattern_type).validate().is_ok(), "{}", pattern);
+    }
+    let invalid = [
+        (r"^evil(\d+\.exe$", "pcre"),
+        ("title: Whoami\ndetection: [", "sigma"),
+        ("title: Whoami\nlogsource: {}\ndetection:\n  selection:\n    Image: a\n  condition: selection and filter", "sigma"),
+        (r#"alert tcp any any -> any any (msg:"no sid";)"#, "suricata"),
+        (r#"alert tcp any any => any any (sid:1;)"#, "snort"),
+        ("rule a { strings: $a = \"x\" condition: $b }", "yara"),
+        ("rule a { condition: true } rule a { condition: false }", "yara"),
+        ("rule a { condition: (true }", "yara"),
+    ];
+    for (pattern, pattern_type) in invalid.iter() {
+        assert!(indicator_with_pattern(pattern, pattern_type).validate().is_err(), "{}", pattern);
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use validator::Validate;
+use stix4rust::core::sdos::observed_data::ObservedData;
+
+/// A complete deserialization of the object grabbing values for each and every element.
+#[test]
+fn it_stix_object_complete_deserialization() {
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "revoked": false,
+        "labels": ["hack"],
+        "confidence": 100,
+        "lang": "en",
+        "external_references": [
+            {
+                "source_name": "NASA",
+                "external_id": "NASA-2021"
+            }
+        ],
+        "granular_markings": [
+            {
+                "lang": "es",
+                "selectors": ["sel1", "sel2"]
+            }
+        ],
+        "object_marking_refs": [],
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 50,
+        "object_refs": [
+            "ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8",
+            "domain-name--ecb120bf-2694-4902-a737-62b74539a41b"
+        ]
+    }
+    "#;
+    let object: ObservedData = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_ok());
+}
+
+/// A complete deserialization of a JSON object that has duplicate keys.
+#[test]
+#[should_panic]
+fn it_stix_object_complete_deserialization_with_duplicate_key() {
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 50,
+        "number_observed": 50,
+        "object_refs": ["ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8"]
+    }
+    "#;
+    let _object: ObservedData = serde_json::from_str(text).unwrap();
+}
+
+/// Deserialization of observed data embedding its objects, as allowed (but deprecated) in STIX 2.1.
+#[test]
+fn it_stix_object_deserialization_with_embedded_objects() {
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 1,
+        "objects": {
+            "0": {
+                "type": "file",
+                "name": "foo.exe"
+            }
+        }
+    }
+    "#;
+    let _object: ObservedData = serde_json::from_str(text).unwrap();
+}
+
+/// Since there are some fields which are required, this test verifies that the deserialization method effectively detects that a panics.
+#[test]
+#[should_panic]
+fn it_stix_object_deserialization_with_missing_required_field() {
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 50
+    }
+    "#;
+    let _object: ObservedData = serde_json::from_str(text).unwrap();
+}
+
+/// The number of observations MUST be between 1 and 999,999,999.
+#[test]
+fn it_stix_object_validation_with_wrong_number_observed() {
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 0,
+        "object_refs": ["ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8"]
+    }
+    "#;
+    let object: ObservedData = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+}
+
+/// The observations MUST NOT end before they start, and exactly one of `objects` and `object_refs` MUST be given.
+#[test]
+fn it_stix_object_validation_of_observations() {
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-20T19:00:00Z",
+        "number_observed": 1,
+        "object_refs": ["ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8"]
+    }
+    "#;
+    let object: ObservedData = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+    let text = r#"
+    {
+        "type": "observed-data",
+        "spec_version": "2.1",
+        "id": "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf",
+        "created": "2016-04-06T19:58:16.000Z",
+        "modified": "2016-04-06T19:58:16.000Z",
+        "first_observed": "2015-12-21T19:00:00Z",
+        "last_observed": "2015-12-21T19:00:00Z",
+        "number_observed": 1,
+        "objects": {
+            "0": {
+                "type": "file",
+                "name": "foo.exe"
+            }
+        },
+        "object_refs": ["ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8"]
+    }
+    "#;
+    let object: ObservedData = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+    let now = Utc::now();
+    let built = ObservedData::builder().first_observed(now).last_observed(now).number_observed(1u64).build();
+    assert!(built.is_err());
+    let built = ObservedData::builder().first_observed(now).last_observed(now).number_observed(1u64).object_refs(vec!["ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8".to_string()]).build();
+    assert!(built.is_ok());
+}
+
+/// Serialization test of the object with certain values for the object.
+/// Note that STIX 2.1 requires null values not appear in the object. This test verifies that optional values are not shown.
+#[test]
+fn it_stix_object_serialization() {
+    let object = ObservedData {
+        id: "observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf".to_string(),
+        spec_version: "2.1".to_string(),
+        created: DateTime::parse_from_rfc3339("2016-04-06T19:58:16Z").unwrap().with_timezone(&Utc),
+        modified: DateTime::parse_from_rfc3339("2016-04-06T19:58:16Z").unwrap().with_timezone(&Utc),
+        created_by_ref: None,
+        revoked: None,
+        labels: None,
+        confidence: None,
+        lang: None,
+        external_references: None,
+        object_marking_refs: None,
+        granular_markings: None,
+        first_observed: DateTime::parse_from_rfc3339("2015-12-21T19:00:00Z").unwrap().with_timezone(&Utc),
+        last_observed: DateTime::parse_from_rfc3339("2015-12-21T19:00:00Z").unwrap().with_timezone(&Utc),
+        number_observed: 50,
+        objects: None,
+        object_refs: Some(vec!["ipv4-addr--efcd5e80-570d-4131-b213-62cb18eaa6a8".to_string()]),
+    };
+    let text = serde_json::to_string_pretty(&object).unwrap();
+    assert!(!text.contains("null"));
+}
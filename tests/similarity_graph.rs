@@ -0,0 +1,238 @@
+use stix4rust::core::STIXObject;
+use stix4rust::core::similarity::graph;
+use stix4rust::core::similarity::object::Weights;
+use stix4rust::core::stores::memory::MemoryStore;
+
+/// A report on a threat actor and its malware, as told by a partner.
+const FIRST: &str = r#"
+[
+    {
+        "type": "report",
+        "spec_version": "2.1",
+        "id": "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Operation X",
+        "report_types": ["campaign"],
+        "published": "2020-01-02T00:00:00Z",
+        "object_refs": ["threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500", "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b"]
+    },
+    {
+        "type": "threat-actor",
+        "spec_version": "2.1",
+        "id": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Evil Org",
+        "threat_actor_types": ["crime-syndicate"]
+    },
+    {
+        "type": "malware",
+        "spec_version": "2.1",
+        "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Zeus",
+        "is_family": true,
+        "malware_types": ["bot"]
+    }
+]
+"#;
+
+/// The same report as told by another partner, who names the malware differently.
+const SECOND: &str = r#"
+[
+    {
+        "type": "report",
+        "spec_version": "2.1",
+        "id": "report--f2b63e80-b523-4747-a069-35c002c690db",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Operation X",
+        "report_types": ["campaign"],
+        "published": "2020-01-02T00:00:00Z",
+        "object_refs": ["threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f", "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841"]
+    },
+    {
+        "type": "threat-actor",
+        "spec_version": "2.1",
+        "id": "threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Evil Org",
+        "threat_actor_types": ["crime-syndicate"]
+    },
+    {
+        "type": "malware",
+        "spec_version": "2.1",
+        "id": "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Zbot",
+        "is_family": true,
+        "malware_types": ["bot"]
+    }
+]
+"#;
+
+/// The same report as told by another partner, who names the malware the same way.
+const SECOND_SAME_NAME: &str = r#"
+[
+    {
+        "type": "report",
+        "spec_version": "2.1",
+        "id": "report--f2b63e80-b523-4747-a069-35c002c690db",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Operation X",
+        "report_types": ["campaign"],
+        "published": "2020-01-02T00:00:00Z",
+        "object_refs": ["threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f", "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841"]
+    },
+    {
+        "type": "threat-actor",
+        "spec_version": "2.1",
+        "id": "threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Evil Org",
+        "threat_actor_types": ["crime-syndicate"]
+    },
+    {
+        "type": "malware",
+        "spec_version": "2.1",
+        "id": "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841",
+        "created": "2020-01-01T00:00:00Z",
+        "modified": "2020-01-01T00:00:00Z",
+        "name": "Zeus",
+        "is_family": true,
+        "malware_types": ["bot"]
+    }
+]
+"#;
+
+fn objects(text: &str) -> Vec<Box<dyn STIXObject>> {
+    serde_json::from_str(text).unwrap()
+}
+
+fn close(value: f64, expected: f64) -> bool {
+    (value - expected).abs() < 1e-9
+}
+
+/// Identical graphs score 100, and each object is matched with its counterpart.
+#[test]
+fn it_matches_identical_graphs() {
+    let similarity = graph::graph_similarity(&objects(FIRST), &objects(SECOND_SAME_NAME), &Weights::default()).unwrap();
+    assert!(close(similarity.score, 100.0));
+    assert_eq!(similarity.len_pairs, 6);
+    let pairs: Vec<(&str, &str)> = similarity.summary.iter().map(|pair| (pair.lhs.as_str(), pair.rhs.as_str())).collect();
+    assert_eq!(pairs[0], ("malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b", "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841"));
+    assert_eq!(pairs[4], ("threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500", "threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f"));
+}
+
+/// The references of the reports are compared through the objects they refer to, unless the depth forbids it.
+#[test]
+fn it_follows_references() {
+    let similarity = graph::graph_similarity(&objects(FIRST), &objects(SECOND), &Weights::default()).unwrap();
+    let report_score = similarity.summary.iter().find(|pair| pair.lhs == "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3").unwrap().similarity.score;
+    assert!(close(report_score, 82.0));
+    assert!(close(similarity.score, (2.0 * 82.0 + 2.0 * 100.0 + 2.0 * 40.0) / 6.0));
+    let mut shallow = Weights::default();
+    shallow.max_depth = 0;
+    let similarity = graph::graph_similarity(&objects(FIRST), &objects(SECOND), &shallow).unwrap();
+    assert!(close(similarity.score, 80.0));
+}
+
+/// Stores are compared the same way.
+#[test]
+fn it_compares_stores() {
+    let (mut first, mut second) = (MemoryStore::new(), MemoryStore::new());
+    objects(FIRST).into_iter().for_each(|object| first.add(object).unwrap());
+    objects(SECOND).into_iter().for_each(|object| second.add(object).unwrap());
+    let stores = graph::store_similarity(&first, &second, &Weights::default()).unwrap();
+    let bundles = graph::graph_similarity(&objects(FIRST), &objects(SECOND), &Weights::default()).unwrap();
+    assert_eq!(stores, bundles);
+    assert_eq!(graph::graph_similarity(&objects(FIRST), &[], &Weights::default()).unwrap().score, 0.0);
+}
+
+/// As in python-stix2, the references missing from a graph, and those to objects of a type without weights, are not paired.
+#[test]
+fn it_skips_unresolved_references() {
+    let first = r#"
+    [
+        {
+            "type": "report",
+            "spec_version": "2.1",
+            "id": "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3",
+            "created": "2020-01-01T00:00:00.000Z",
+            "modified": "2020-01-01T00:00:00.000Z",
+            "name": "Operation X",
+            "report_types": ["campaign"],
+            "published": "2020-01-02T00:00:00Z",
+            "object_refs": [
+                "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+                "malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061",
+                "note--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061"
+            ]
+        },
+        {
+            "type": "malware",
+            "spec_version": "2.1",
+            "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+            "created": "2020-01-01T00:00:00.000Z",
+            "modified": "2020-01-01T00:00:00.000Z",
+            "name": "Zeus",
+            "is_family": true,
+            "malware_types": ["bot"]
+        },
+        {
+            "type": "note",
+            "spec_version": "2.1",
+            "id": "note--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061",
+            "created": "2020-01-01T00:00:00.000Z",
+            "modified": "2020-01-01T00:00:00.000Z",
+            "content": "Seen in the wild",
+            "object_refs": ["malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b"]
+        }
+    ]
+    "#;
+    let second = r#"
+    [
+        {
+            "type": "report",
+            "spec_version": "2.1",
+            "id": "report--f2b63e80-b523-4747-a069-35c002c690db",
+            "created": "2020-01-01T00:00:00.000Z",
+            "modified": "2020-01-01T00:00:00.000Z",
+            "name": "Operation X",
+            "report_types": ["campaign"],
+            "published": "2020-01-02T00:00:00Z",
+            "object_refs": ["malware--92ec0cbd-2c30-44a2-b270-73f4ec949841", "note--b34a8e0a-4f2f-4b8b-b4f4-7f3b1f6cd3a0"]
+        },
+        {
+            "type": "malware",
+            "spec_version": "2.1",
+            "id": "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841",
+            "created": "2020-01-01T00:00:00.000Z",
+            "modified": "2020-01-01T00:00:00.000Z",
+            "name": "Zeus",
+            "is_family": true,
+            "malware_types": ["bot"]
+        },
+        {
+            "type": "note",
+            "spec_version": "2.1",
+            "id": "note--b34a8e0a-4f2f-4b8b-b4f4-7f3b1f6cd3a0",
+            "created": "2020-01-01T00:00:00.000Z",
+            "modified": "2020-01-01T00:00:00.000Z",
+            "content": "Seen in the wild",
+            "object_refs": ["malware--92ec0cbd-2c30-44a2-b270-73f4ec949841"]
+        }
+    ]
+    "#;
+    let first: Vec<Box<dyn STIXObject>> = serde_json::from_str(first).unwrap();
+    let second: Vec<Box<dyn STIXObject>> = serde_json::from_str(second).unwrap();
+    let similarity = graph::graph_similarity(&first, &second, &Weights::default()).unwrap();
+    let report = similarity.summary.iter().find(|pair| pair.lhs == "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3").unwrap();
+    assert!(close(report.similarity.score, 100.0), "{}", report.similarity.score);
+}
@@ -0,0 +1,106 @@
+use serde_json::json;
+use stix4rust::core::similarity::object::{self, Comparison, PropertyWeight, SimilarityError, Weights};
+
+mod common;
+use common::object;
+
+/// Only the properties present in both objects are compared, each contributing its weight times its score.
+#[test]
+fn it_scores_threat_actors() {
+    let first = object(json!({
+        "type": "threat-actor", "spec_version": "2.1", "id": "threat-actor--56f3f0db-b5a5-431c-ae56-c18f02caf500", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "Evil Org", "threat_actor_types": ["crime-syndicate"], "aliases": ["EO"]
+    }));
+    let second = object(json!({
+        "type": "threat-actor", "spec_version": "2.1", "id": "threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "Org Evil", "threat_actor_types": ["crime-syndicate", "hacker"]
+    }));
+    let similarity = object::object_similarity(first.as_ref(), second.as_ref(), &Weights::default()).unwrap();
+    assert_eq!(similarity.score, 87.5);
+    assert_eq!(similarity.sum_weights, 80.0);
+    let breakdown: Vec<(&str, f64)> = similarity.properties.iter().map(|property| (property.property.as_str(), property.contributing_score)).collect();
+    assert_eq!(breakdown, [("name", 60.0), ("threat_actor_types", 10.0)]);
+    assert!(object::object_equivalence(first.as_ref(), second.as_ref(), 85.0, &Weights::default()).unwrap());
+    assert!(!object::object_equivalence(first.as_ref(), second.as_ref(), 90.0, &Weights::default()).unwrap());
+}
+
+/// Strings are compared through the ratio of their sorted tokens.
+#[test]
+fn it_compares_strings() {
+    assert_eq!(object::token_sort_ratio("Evil Org", "Org  Evil"), 1.0);
+    assert!((object::token_sort_ratio("abc", "abd") - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(object::token_sort_ratio("", ""), 1.0);
+    assert_eq!(object::token_sort_ratio("abc", ""), 0.0);
+}
+
+/// Locations are compared through the haversine distance between them, as well as their region and country.
+#[test]
+fn it_scores_locations() {
+    let paris = object(json!({
+        "type": "location", "spec_version": "2.1", "id": "location--a6e9345f-5a15-4c29-8bb3-7dcc5d168d64", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "latitude": 48.8566, "longitude": 2.3522, "region": "western-europe", "country": "FR"
+    }));
+    let london = object(json!({
+        "type": "location", "spec_version": "2.1", "id": "location--c7e1a3d2-4b5f-4e6a-9d8c-1b2a3c4d5e6f", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "latitude": 51.5074, "longitude": -0.1278, "region": "western-europe", "country": "GB"
+    }));
+    let similarity = object::object_similarity(paris.as_ref(), london.as_ref(), &Weights::default()).unwrap();
+    let distance = similarity.properties.iter().find(|property| property.property == object::LONGITUDE_LATITUDE).unwrap();
+    assert!((distance.contributing_score - 34.0 * (1.0 - 343.5 / 1000.0)).abs() < 0.1);
+    assert!((similarity.score - (distance.contributing_score + 33.0)).abs() < 1e-9);
+    let tokyo = object(json!({
+        "type": "location", "spec_version": "2.1", "id": "location--0f5e2a1b-3c4d-4e5f-8a9b-7c6d5e4f3a2b", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "latitude": 35.6762, "longitude": 139.6503
+    }));
+    assert_eq!(object::object_similarity(paris.as_ref(), tokyo.as_ref(), &Weights::default()).unwrap().score, 0.0);
+}
+
+/// Indicators with equivalent patterns match, and the external references of the sources defined by STIX are decisive.
+#[test]
+fn it_scores_indicators_and_references() {
+    let first = object(json!({
+        "type": "indicator", "spec_version": "2.1", "id": "indicator--26ffb872-1dd9-446e-b6f5-d58527e5b5d2", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "pattern": "[file:name = 'a' OR file:size = 1]", "pattern_type": "stix", "valid_from": "2020-01-01T00:00:00Z"
+    }));
+    let second = object(json!({
+        "type": "indicator", "spec_version": "2.1", "id": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "pattern": "[file:size = 1 OR file:name = 'a']", "pattern_type": "stix", "valid_from": "2020-01-01T12:00:00Z"
+    }));
+    assert_eq!(object::object_similarity(first.as_ref(), second.as_ref(), &Weights::default()).unwrap().score, (80.0 + 2.5) / 85.0 * 100.0);
+    let first = object(json!({
+        "type": "vulnerability", "spec_version": "2.1", "id": "vulnerability--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "Heartbleed", "external_references": [{"source_name": "cve", "external_id": "CVE-2014-0160"}]
+    }));
+    let second = object(json!({
+        "type": "vulnerability", "spec_version": "2.1", "id": "vulnerability--9d0e1f2a-3b4c-4d5e-8f6a-7b8c9d0e1f2a", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "OpenSSL bug", "external_references": [{"source_name": "cve", "external_id": "CVE-2014-0160"}]
+    }));
+    let similarity = object::object_similarity(first.as_ref(), second.as_ref(), &Weights::default()).unwrap();
+    assert_eq!(similarity.properties[1].contributing_score, 70.0);
+}
+
+/// Weights can be changed, types without weights score 0 and objects of different types cannot be compared.
+#[test]
+fn it_uses_custom_weights() {
+    let first = object(json!({
+        "type": "campaign", "spec_version": "2.1", "id": "campaign--83422c77-904c-4dc1-aff5-5c38f3a2c55c", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "Operation X", "objective": "money"
+    }));
+    let second = object(json!({
+        "type": "campaign", "spec_version": "2.1", "id": "campaign--e5f6a7b8-c9d0-4e1f-a2b3-c4d5e6f7a8b9", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "Operation Y", "objective": "money"
+    }));
+    let mut weights = Weights::default();
+    weights.set("campaign", vec![PropertyWeight::new("objective", 1.0, Comparison::Exact)]);
+    assert_eq!(object::object_similarity(first.as_ref(), second.as_ref(), &weights).unwrap().score, 100.0);
+    let empty = Weights::empty();
+    assert_eq!(object::object_similarity(first.as_ref(), second.as_ref(), &empty).unwrap().score, 0.0);
+    let malware = object(json!({
+        "type": "malware", "spec_version": "2.1", "id": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b", "created": "2020-01-01T00:00:00Z", "modified": "2020-01-01T00:00:00Z",
+        "name": "X", "is_family": true, "malware_types": ["bot"]
+    }));
+    assert_eq!(
+        object::object_similarity(first.as_ref(), malware.as_ref(), &weights),
+        Err(SimilarityError::DifferentTypes("campaign".to_string(), "malware".to_string()))
+    );
+}
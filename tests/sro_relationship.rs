@@ -0,0 +1,64 @@
+use validator::Validate;
+use stix4rust::core::sros::relationship::Relationship;
+
+/// A complete deserialization of the object grabbing values for each and every element.
+#[test]
+fn it_stix_object_complete_deserialization() {
+    let text = r#"
+    {
+        "type": "relationship",
+        "spec_version": "2.1",
+        "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+        "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "created": "2016-04-06T20:06:37.000Z",
+        "modified": "2016-04-06T20:06:37.000Z",
+        "relationship_type": "indicates",
+        "description": "This indicator detects the malware.",
+        "source_ref": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "target_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+        "start_time": "2016-04-06T20:06:37.000Z",
+        "stop_time": "2016-05-06T20:06:37.000Z"
+    }
+    "#;
+    let object: Relationship = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_ok());
+}
+
+/// Since there are some fields which are required, this test verifies that the deserialization method effectively detects that a panics.
+#[test]
+#[should_panic]
+fn it_stix_object_deserialization_with_missing_required_field() {
+    let text = r#"
+    {
+        "type": "relationship",
+        "spec_version": "2.1",
+        "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+        "created": "2016-04-06T20:06:37.000Z",
+        "modified": "2016-04-06T20:06:37.000Z",
+        "relationship_type": "indicates",
+        "source_ref": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f"
+    }
+    "#;
+    let _object: Relationship = serde_json::from_str(text).unwrap();
+}
+
+/// A relationship cannot stop before it starts.
+#[test]
+fn it_stix_object_validation_of_times() {
+    let text = r#"
+    {
+        "type": "relationship",
+        "spec_version": "2.1",
+        "id": "relationship--44298a74-ba52-4f0c-87a3-1824e67d7fad",
+        "created": "2016-04-06T20:06:37.000Z",
+        "modified": "2016-04-06T20:06:37.000Z",
+        "relationship_type": "uses",
+        "source_ref": "threat-actor--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "target_ref": "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b",
+        "start_time": "2016-05-06T20:06:37.000Z",
+        "stop_time": "2016-04-06T20:06:37.000Z"
+    }
+    "#;
+    let object: Relationship = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_err());
+}
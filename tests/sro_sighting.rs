@@ -0,0 +1,65 @@
+use validator::Validate;
+use stix4rust::core::sros::sighting::Sighting;
+
+/// A complete deserialization of the object grabbing values for each and every element.
+#[test]
+fn it_stix_object_complete_deserialization() {
+    let text = r#"
+    {
+        "type": "sighting",
+        "spec_version": "2.1",
+        "id": "sighting--ee20065d-2555-424f-ad9e-0f8428623c75",
+        "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "created": "2016-04-06T20:08:31.000Z",
+        "modified": "2016-04-06T20:08:31.000Z",
+        "first_seen": "2015-12-21T19:00:00Z",
+        "last_seen": "2015-12-21T19:00:00Z",
+        "count": 50,
+        "sighting_of_ref": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f",
+        "observed_data_refs": ["observed-data--b67d30ff-02ac-498a-92f9-32f845f448cf"],
+        "where_sighted_refs": ["identity--b67d30ff-02ac-498a-92f9-32f845f448ff"],
+        "summary": false
+    }
+    "#;
+    let object: Sighting = serde_json::from_str(text).unwrap();
+    assert!(object.validate().is_ok());
+}
+
+/// Since there are some fields which are required, this test verifies that the deserialization method effectively detects that a panics.
+#[test]
+#[should_panic]
+fn it_stix_object_deserialization_with_missing_required_field() {
+    let text = r#"
+    {
+        "type": "sighting",
+        "spec_version": "2.1",
+        "id": "sighting--ee20065d-2555-424f-ad9e-0f8428623c75",
+        "created": "2016-04-06T20:08:31.000Z",
+        "modified": "2016-04-06T20:08:31.000Z",
+        "count": 50
+    }
+    "#;
+    let _object: Sighting = serde_json::from_str(text).unwrap();
+}
+
+/// A sighting cannot be last seen before it was first seen, and only refers to observed data through `observed_data_refs`.
+#[test]
+fn it_stix_object_validation() {
+    let mut object: Sighting = serde_json::from_str(r#"
+    {
+        "type": "sighting",
+        "spec_version": "2.1",
+        "id": "sighting--ee20065d-2555-424f-ad9e-0f8428623c75",
+        "created": "2016-04-06T20:08:31.000Z",
+        "modified": "2016-04-06T20:08:31.000Z",
+        "first_seen": "2015-12-21T19:00:00Z",
+        "last_seen": "2015-12-20T19:00:00Z",
+        "sighting_of_ref": "indicator--8e2e2d2b-17d4-4cbf-938f-98ee46b3cd3f"
+    }
+    "#).unwrap();
+    assert!(object.validate().is_err());
+    object.last_seen = None;
+    assert!(object.validate().is_ok());
+    object.observed_data_refs = Some(vec!["identity--b67d30ff-02ac-498a-92f9-32f845f448ff".to_string()]);
+    assert!(object.validate().is_err());
+}
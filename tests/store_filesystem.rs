@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use stix4rust::core::STIXObject;
+use stix4rust::core::bundle::Bundle;
+use stix4rust::core::stores::{Store, StoreError};
+use stix4rust::core::stores::filesystem::FileSystemStore;
+use stix4rust::core::stores::filter::{Filter, Operator};
+
+const ID: &str = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+
+/// A new empty directory for a test, under the temporary directory of the system.
+fn root(test: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("stix4rust-{}-{}", test, uuid::Uuid::new_v4()));
+    fs::create_dir_all(&root).unwrap();
+    root
+}
+
+fn identity(modified: &str, name: &str) -> Box<dyn STIXObject> {
+    serde_json::from_value(serde_json::json!({
+        "type": "identity",
+        "spec_version": "2.1",
+        "id": ID,
+        "created": "2015-12-21T19:59:11.000Z",
+        "modified": modified,
+        "name": name
+    }))
+    .unwrap()
+}
+
+fn name(object: &dyn STIXObject) -> String {
+    object.to_value()["name"].as_str().unwrap().to_string()
+}
+
+/// Objects are written in the layout of python-stix2, one file per version.
+#[test]
+fn it_writes_the_python_layout() {
+    let root = root("layout");
+    let mut store = FileSystemStore::new(&root);
+    store.add(identity("2016-05-01T10:20:30.123Z", "first")).unwrap();
+    store.add(serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#).unwrap()).unwrap();
+    assert!(root.join("identity").join(ID).join("20160501102030123000.json").is_file());
+    assert!(root.join("domain-name").join("domain-name--1.json").is_file());
+    let mut ids = store.ids().unwrap();
+    ids.sort();
+    assert_eq!(ids, ["domain-name--1", ID]);
+    fs::remove_dir_all(root).unwrap();
+}
+
+/// Versions are read back in order, and the files holding a bundle are understood too.
+#[test]
+fn it_reads_versions() {
+    let root = root("versions");
+    let mut store = FileSystemStore::new(&root);
+    store.add_bundle(Bundle::new(vec![identity("2017-01-01T00:00:00Z", "second"), identity("2016-01-01T00:00:00Z", "first")])).unwrap();
+    let bundle = serde_json::json!({
+        "type": "bundle",
+        "id": "bundle--1",
+        "objects": [{"type": "identity", "spec_version": "2.1", "id": ID, "created": "2015-12-21T19:59:11Z", "modified": "2018-01-01T00:00:00Z", "name": "third"}]
+    });
+    fs::write(root.join("identity").join(ID).join("20180101000000000000.json"), bundle.to_string()).unwrap();
+    assert_eq!(name(store.get(ID).unwrap().unwrap().as_ref()), "third");
+    let names: Vec<String> = store.all_versions(ID).unwrap().iter().map(|object| name(object.as_ref())).collect();
+    assert_eq!(names, ["first", "second", "third"]);
+    let modified = DateTime::parse_from_rfc3339("2017-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+    assert_eq!(name(store.get_version(ID, &modified).unwrap().unwrap().as_ref()), "second");
+    assert!(store.get("identity--unknown").unwrap().is_none());
+    fs::write(root.join("identity").join(ID).join("20190101000000000000.json"), "{}").unwrap();
+    assert!(matches!(store.get(ID), Err(StoreError::InvalidContent(_))));
+    fs::remove_dir_all(root).unwrap();
+}
+
+/// Queries and removals work as with the other stores.
+#[test]
+fn it_queries_and_removes() {
+    let root = root("query");
+    let mut store = FileSystemStore::new(&root);
+    store.add(identity("2016-01-01T00:00:00Z", "ACME")).unwrap();
+    store.add(identity("2017-01-01T00:00:00Z", "ACME Corp")).unwrap();
+    store.add(serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "acme.com"}"#).unwrap()).unwrap();
+    let found = store.query(&[Filter::new("type", Operator::Equal, "identity"), Filter::new("name", Operator::Contains, "Corp")]).unwrap();
+    assert_eq!(found.len(), 1);
+    assert!(store.query(&[Filter::new("name", Operator::Equal, "ACME")]).unwrap().is_empty());
+    assert_eq!(store.query(&[Filter::new("value", Operator::Contains, "acme")]).unwrap().len(), 1);
+    let modified = DateTime::parse_from_rfc3339("2017-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+    assert_eq!(name(store.remove_version(ID, &modified).unwrap().unwrap().as_ref()), "ACME Corp");
+    assert_eq!(name(store.get(ID).unwrap().unwrap().as_ref()), "ACME");
+    assert_eq!(store.remove(ID).unwrap().len(), 1);
+    assert_eq!(store.remove("domain-name--1").unwrap().len(), 1);
+    assert!(store.ids().unwrap().is_empty());
+    fs::remove_dir_all(root).unwrap();
+}
+
+/// Identifiers that would lead out of the root are rejected before any file is read, written or removed.
+#[test]
+fn it_rejects_escaping_ids() {
+    let root = root("escape");
+    let store_root = root.join("store");
+    let victim = root.join("victim");
+    fs::create_dir_all(&victim).unwrap();
+    fs::write(victim.join("keep.json"), "{}").unwrap();
+    let mut store = FileSystemStore::new(&store_root);
+    let escaping = r#"{"type": "domain-name", "id": "domain-name--../../../outside", "value": "evil.com"}"#;
+    assert!(matches!(store.add(serde_json::from_str(escaping).unwrap()), Err(StoreError::InvalidId(_))));
+    assert!(!root.join("outside.json").exists());
+    let modified = DateTime::parse_from_rfc3339("2017-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+    let victim_id = victim.display().to_string();
+    for id in [victim_id.as_str(), "identity--../../victim", "identity--..\\victim", "../victim", "identity--"].iter() {
+        assert_eq!(store.get(id).unwrap_err(), StoreError::InvalidId(id.to_string()));
+        assert_eq!(store.get_version(id, &modified).unwrap_err(), StoreError::InvalidId(id.to_string()));
+        assert_eq!(store.remove(id).unwrap_err(), StoreError::InvalidId(id.to_string()));
+        assert_eq!(store.remove_version(id, &modified).unwrap_err(), StoreError::InvalidId(id.to_string()));
+    }
+    assert!(victim.join("keep.json").is_file());
+    assert!(store.query(&[Filter::new("type", Operator::Equal, "../victim")]).unwrap().is_empty());
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,79 @@
+use serde_json::json;
+use stix4rust::core::STIXObject;
+use stix4rust::core::stores::filter::{Filter, Operator};
+use stix4rust::core::stores::memory::MemoryStore;
+
+fn malware(id: &str, created: &str, created_by_ref: &str, is_family: bool) -> Box<dyn STIXObject> {
+    serde_json::from_value(json!({
+        "type": "malware",
+        "spec_version": "2.1",
+        "id": id,
+        "created": created,
+        "modified": created,
+        "created_by_ref": created_by_ref,
+        "name": id,
+        "malware_types": ["ransomware", "worm"],
+        "is_family": is_family,
+        "kill_chain_phases": [{"kill_chain_name": "lockheed-martin-cyber-kill-chain", "phase_name": "installation"}]
+    }))
+    .unwrap()
+}
+
+fn store() -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.add(malware("malware--1", "2016-05-12T08:17:27.000Z", "identity--x", true)).unwrap();
+    store.add(malware("malware--2", "2018-01-01T00:00:00Z", "identity--x", true)).unwrap();
+    store.add(malware("malware--3", "2019-01-01T00:00:00.5Z", "identity--x", false)).unwrap();
+    store.add(malware("malware--4", "2019-01-01T00:00:00Z", "identity--y", true)).unwrap();
+    store.add(serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#).unwrap()).unwrap();
+    store
+}
+
+fn ids(objects: Vec<&dyn STIXObject>) -> Vec<String> {
+    let mut ids: Vec<String> = objects.into_iter().map(|object| object.to_value()["id"].as_str().unwrap().to_string()).collect();
+    ids.sort();
+    ids
+}
+
+/// Filters are combined, comparing the timestamps as such.
+#[test]
+fn it_queries_stores() {
+    let store = store();
+    let found = store.query(&[
+        Filter::new("type", Operator::Equal, "malware"),
+        Filter::new("is_family", Operator::Equal, true),
+        Filter::new("created_by_ref", Operator::Equal, "identity--x"),
+        Filter::new("created", Operator::GreaterThan, "2017-01-01T00:00:00Z"),
+    ]);
+    assert_eq!(ids(found), ["malware--2"]);
+    let found = store.query(&[Filter::new("created", Operator::Equal, "2018-01-01T00:00:00.000Z")]);
+    assert_eq!(ids(found), ["malware--2"]);
+    let found = store.query(&[Filter::new("created", Operator::LessOrEqual, "2018-01-01T00:00:00Z")]);
+    assert_eq!(ids(found), ["malware--1", "malware--2"]);
+    assert_eq!(store.query(&[]).len(), 5);
+}
+
+/// The operators look through lists and nested properties.
+#[test]
+fn it_filters_nested_properties() {
+    let store = store();
+    let query = |filter: Filter| ids(store.query(&[filter]));
+    assert_eq!(query(Filter::new("malware_types", Operator::Contains, "worm")).len(), 4);
+    assert_eq!(query(Filter::new("malware_types", Operator::Equal, "worm")).len(), 4);
+    assert_eq!(query(Filter::new("malware_types", Operator::NotEqual, "worm")).len(), 0);
+    assert_eq!(query(Filter::new("kill_chain_phases.phase_name", Operator::Equal, "installation")).len(), 4);
+    assert_eq!(query(Filter::new("kill_chain_phases.0.phase_name", Operator::In, json!(["delivery", "installation"]))).len(), 4);
+    assert_eq!(query(Filter::new("value", Operator::Contains, "evil")), ["domain-name--1"]);
+    assert_eq!(query(Filter::new("created_by_ref", Operator::NotEqual, "identity--x")), ["malware--4"]);
+    assert_eq!(query(Filter::new("id", Operator::In, json!(["malware--3", "malware--4"]))), ["malware--3", "malware--4"]);
+    assert!(query(Filter::new("kill_chain_phases.1.phase_name", Operator::Equal, "installation")).is_empty());
+}
+
+/// Only the latest version of the objects is queried.
+#[test]
+fn it_queries_latest_versions() {
+    let mut store = store();
+    store.add(malware("malware--1", "2020-01-01T00:00:00Z", "identity--z", true)).unwrap();
+    assert!(store.query(&[Filter::new("created_by_ref", Operator::Equal, "identity--x"), Filter::new("id", Operator::Equal, "malware--1")]).is_empty());
+    assert_eq!(store.query(&[Filter::new("modified", Operator::GreaterThan, "2019-06-01T00:00:00Z")]).len(), 1);
+}
@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use stix4rust::core::STIXObject;
+use stix4rust::core::bundle::Bundle;
+use stix4rust::core::stores::Store;
+use stix4rust::core::stores::memory::MemoryStore;
+
+fn identity(modified: &str, name: &str) -> Box<dyn STIXObject> {
+    serde_json::from_value(serde_json::json!({
+        "type": "identity",
+        "spec_version": "2.1",
+        "id": "identity--311b2d2d-f010-4473-83ec-1edf84858f4c",
+        "created": "2015-12-21T19:59:11.000Z",
+        "modified": modified,
+        "name": name
+    }))
+    .unwrap()
+}
+
+fn timestamp(text: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+}
+
+fn name(object: &dyn STIXObject) -> String {
+    object.to_value()["name"].as_str().unwrap().to_string()
+}
+
+/// Every version is kept, ordered by `modified` whatever the precision of the timestamps, and the latest one is returned by default.
+#[test]
+fn it_keeps_every_version() {
+    let id = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+    let mut store = MemoryStore::new();
+    store.add(identity("2016-05-01T00:00:00.5Z", "second")).unwrap();
+    store.add(identity("2015-12-21T19:59:11Z", "first")).unwrap();
+    store.add(identity("2017-01-01T00:00:00Z", "third")).unwrap();
+    assert_eq!(store.len(), 1);
+    assert_eq!(name(store.get(id).unwrap()), "third");
+    assert_eq!(name(store.get_version(id, &timestamp("2016-05-01T00:00:00.500Z")).unwrap()), "second");
+    assert!(store.get_version(id, &timestamp("2016-05-01T00:00:00Z")).is_none());
+    let names: Vec<String> = store.all_versions(id).into_iter().map(name).collect();
+    assert_eq!(names, ["first", "second", "third"]);
+    assert!(store.all_versions("identity--unknown").is_empty());
+}
+
+/// Adding a version again replaces it, and objects without `modified` only have a single version.
+#[test]
+fn it_replaces_versions() {
+    let id = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+    let mut store = MemoryStore::new();
+    store.add(identity("2016-05-01T00:00:00Z", "old")).unwrap();
+    store.add(identity("2016-05-01T00:00:00.000Z", "new")).unwrap();
+    assert_eq!(store.all_versions(id).len(), 1);
+    assert_eq!(name(store.get(id).unwrap()), "new");
+    store.add(serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#).unwrap()).unwrap();
+    store.add(serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.org"}"#).unwrap()).unwrap();
+    assert_eq!(store.all_versions("domain-name--1").len(), 1);
+    assert_eq!(store.get("domain-name--1").unwrap().to_value()["value"], "evil.org");
+}
+
+/// The objects of the bundles, either parsed or built, are added at once.
+#[test]
+fn it_ingests_bundles() {
+    let bundle: Bundle = serde_json::from_value(serde_json::json!({
+        "type": "bundle",
+        "id": "bundle--5d0092c5-5f74-4287-9642-33f4c354e56d",
+        "objects": [
+            {"type": "domain-name", "id": "domain-name--1", "value": "evil.com"},
+            {"type": "ipv4-addr", "id": "ipv4-addr--1", "value": "198.51.100.3"}
+        ]
+    }))
+    .unwrap();
+    let mut store = MemoryStore::new();
+    store.add_bundle(bundle).unwrap();
+    let mut ids: Vec<&str> = store.ids().collect();
+    ids.sort_unstable();
+    assert_eq!(ids, ["domain-name--1", "ipv4-addr--1"]);
+    let bundle = Bundle::new(vec![identity("2016-05-01T00:00:00Z", "ACME")]);
+    assert!(bundle.id.starts_with("bundle--"));
+    store.add_bundle(bundle).unwrap();
+    assert_eq!(store.len(), 3);
+}
+
+/// Objects are removed either with all their versions or one version at a time.
+#[test]
+fn it_removes_objects() {
+    let id = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+    let mut store = MemoryStore::new();
+    store.add(identity("2016-05-01T00:00:00Z", "first")).unwrap();
+    store.add(identity("2017-05-01T00:00:00Z", "second")).unwrap();
+    let removed = store.remove_version(id, &timestamp("2017-05-01T00:00:00Z")).unwrap();
+    assert_eq!(name(removed.as_ref()), "second");
+    assert_eq!(name(store.get(id).unwrap()), "first");
+    store.add(identity("2018-05-01T00:00:00Z", "third")).unwrap();
+    assert_eq!(store.remove(id).len(), 2);
+    assert!(store.get(id).is_none());
+    assert!(store.is_empty());
+    assert!(store.remove(id).is_empty());
+}
+
+/// Through the `Store` trait, the objects are returned as copies.
+#[test]
+fn it_implements_the_store_trait() {
+    let id = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+    let mut store = MemoryStore::new();
+    let shared: &mut dyn Store = &mut store;
+    shared.add(identity("2016-05-01T00:00:00Z", "first")).unwrap();
+    shared.add(identity("2017-05-01T00:00:00Z", "second")).unwrap();
+    assert_eq!(name(shared.get(id).unwrap().unwrap().as_ref()), "second");
+    assert_eq!(shared.all_versions(id).unwrap().len(), 2);
+    assert_eq!(shared.ids().unwrap(), [id]);
+    assert_eq!(shared.remove(id).unwrap().len(), 2);
+    assert!(store.is_empty());
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "sqlite")]
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use stix4rust::core::STIXObject;
+use stix4rust::core::bundle::Bundle;
+use stix4rust::core::stores::Store;
+use stix4rust::core::stores::filter::{Filter, Operator};
+use stix4rust::core::stores::sqlite::SqliteStore;
+
+mod common;
+use common::object;
+
+const REPORT: &str = "report--84e4d88f-44ea-4bcd-bbf3-b2c1c320bcb3";
+const ACME: &str = "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff";
+const OTHER: &str = "identity--311b2d2d-f010-4473-83ec-1edf84858f4c";
+const FIRST_MALWARE: &str = "malware--31b940d4-6f7f-459a-80ea-9c1f17b5891b";
+const SECOND_MALWARE: &str = "malware--92ec0cbd-2c30-44a2-b270-73f4ec949841";
+const DOMAIN: &str = "domain-name--3c10e93f-798e-5a26-a0c1-08156efab7f5";
+const TLP_GREEN: &str = "marking-definition--34098fce-860f-48ae-8e50-ebd3cc5e41da";
+
+fn report(modified: &str, object_refs: &[&str]) -> Box<dyn STIXObject> {
+    object(json!({
+        "type": "report",
+        "spec_version": "2.1",
+        "id": REPORT,
+        "created": "2016-01-01T00:00:00Z",
+        "modified": modified,
+        "created_by_ref": ACME,
+        "name": "Campaign",
+        "report_types": ["campaign"],
+        "published": "2016-01-01T00:00:00Z",
+        "object_refs": object_refs,
+        "granular_markings": [{"marking_ref": TLP_GREEN, "selectors": ["name"]}]
+    }))
+}
+
+fn malware(id: &str, created_by_ref: &str) -> Box<dyn STIXObject> {
+    object(json!({
+        "type": "malware",
+        "spec_version": "2.1",
+        "id": id,
+        "created": "2016-01-01T00:00:00Z",
+        "modified": "2016-01-01T00:00:00Z",
+        "created_by_ref": created_by_ref,
+        "name": id,
+        "malware_types": ["ransomware"],
+        "is_family": true
+    }))
+}
+
+fn ids(objects: Vec<Box<dyn STIXObject>>) -> Vec<String> {
+    let mut ids: Vec<String> = objects.iter().map(|object| object.to_value()["id"].as_str().unwrap().to_string()).collect();
+    ids.sort();
+    ids
+}
+
+/// Versions are kept and ordered whatever the precision of their timestamps, and persist once the database is closed.
+#[test]
+fn it_keeps_versions_on_disk() {
+    let path = std::env::temp_dir().join(format!("stix4rust-{}.sqlite", uuid::Uuid::new_v4()));
+    {
+        let mut store = SqliteStore::open(&path).unwrap();
+        store.add(report("2016-05-01T00:00:00.5Z", &[FIRST_MALWARE])).unwrap();
+        store.add(report("2016-05-01T00:00:00Z", &[FIRST_MALWARE])).unwrap();
+        store.add(report("2016-05-01T00:00:00.500Z", &[FIRST_MALWARE, SECOND_MALWARE])).unwrap();
+        store.add(object(json!({"type": "domain-name", "id": DOMAIN, "value": "evil.com"}))).unwrap();
+    }
+    let mut store = SqliteStore::open(&path).unwrap();
+    assert_eq!(store.ids().unwrap(), [DOMAIN, REPORT]);
+    assert_eq!(store.all_versions(REPORT).unwrap().len(), 2);
+    assert_eq!(store.get(REPORT).unwrap().unwrap().to_value()["object_refs"], json!([FIRST_MALWARE, SECOND_MALWARE]));
+    let modified = DateTime::parse_from_rfc3339("2016-05-01T00:00:00Z").unwrap().with_timezone(&Utc);
+    assert_eq!(store.get_version(REPORT, &modified).unwrap().unwrap().to_value()["object_refs"], json!([FIRST_MALWARE]));
+    assert!(store.remove_version(REPORT, &modified).unwrap().is_some());
+    assert_eq!(store.remove(REPORT).unwrap().len(), 1);
+    assert!(store.get(REPORT).unwrap().is_none());
+    assert!(store.get(DOMAIN).unwrap().is_some());
+    std::fs::remove_file(path).unwrap();
+}
+
+/// Queries on indexed properties, references included, give the same results as the filters themselves.
+#[test]
+fn it_queries_indexed_properties() {
+    let mut store = SqliteStore::open_in_memory().unwrap();
+    store
+        .add_bundle(Bundle::new(vec![
+            report("2016-01-01T00:00:00Z", &[FIRST_MALWARE]),
+            report("2017-01-01T00:00:00Z", &[SECOND_MALWARE]),
+            malware(FIRST_MALWARE, ACME),
+            malware(SECOND_MALWARE, OTHER),
+        ]))
+        .unwrap();
+    let query = |filters: &[Filter]| ids(store.query(filters).unwrap());
+    assert_eq!(query(&[Filter::new("type", Operator::Equal, "malware")]), [FIRST_MALWARE, SECOND_MALWARE]);
+    assert_eq!(query(&[Filter::new("type", Operator::In, json!(["malware", "report"])), Filter::new("created_by_ref", Operator::Equal, ACME)]), [FIRST_MALWARE, REPORT]);
+    assert_eq!(query(&[Filter::new("object_refs", Operator::Contains, SECOND_MALWARE)]), [REPORT]);
+    assert!(query(&[Filter::new("object_refs", Operator::Contains, FIRST_MALWARE)]).is_empty());
+    assert_eq!(query(&[Filter::new("granular_markings.marking_ref", Operator::Equal, TLP_GREEN)]), [REPORT]);
+    assert_eq!(query(&[Filter::new("modified", Operator::GreaterThan, "2016-06-01T00:00:00.000Z")]), [REPORT]);
+    assert_eq!(query(&[Filter::new("created_by_ref", Operator::Contains, "311b2d2d")]), [SECOND_MALWARE]);
+    assert_eq!(query(&[Filter::new("name", Operator::Equal, FIRST_MALWARE)]), [FIRST_MALWARE]);
+    assert_eq!(ids(store.referencing(SECOND_MALWARE).unwrap()), [REPORT]);
+    assert_eq!(ids(store.referencing(ACME).unwrap()), [FIRST_MALWARE, REPORT]);
+}
+
+/// A version failing to be added does not remove the one stored before.
+#[test]
+fn it_adds_objects_atomically() {
+    let path = std::env::temp_dir().join(format!("stix4rust-{}.sqlite", uuid::Uuid::new_v4()));
+    let mut store = SqliteStore::open(&path).unwrap();
+    store.add(report("2016-05-01T00:00:00Z", &[FIRST_MALWARE])).unwrap();
+    let connection = rusqlite::Connection::open(&path).unwrap();
+    connection.execute_batch("CREATE TRIGGER refs_failing BEFORE INSERT ON refs BEGIN SELECT RAISE(ABORT, 'refs failing'); END;").unwrap();
+    assert!(store.add(report("2016-05-01T00:00:00Z", &[SECOND_MALWARE])).is_err());
+    assert_eq!(store.get(REPORT).unwrap().unwrap().to_value()["object_refs"], json!([FIRST_MALWARE]));
+    drop(connection);
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use stix4rust::core::STIXObject;
+use stix4rust::core::stores::memory::MemoryStore;
+use stix4rust::core::versioning::{self, ViolationKind, VersioningError};
+
+fn malware() -> Box<dyn STIXObject> {
+    serde_json::from_value(json!({
+        "type": "malware",
+        "spec_version": "2.1",
+        "id": "malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061",
+        "created": "2016-05-12T08:17:27.000Z",
+        "modified": "2016-05-12T08:17:27.000Z",
+        "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff",
+        "name": "Cryptolocker",
+        "description": "A variant of the cryptolocker family",
+        "malware_types": ["ransomware"],
+        "is_family": false
+    }))
+    .unwrap()
+}
+
+fn modified(object: &dyn STIXObject) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(object.to_value()["modified"].as_str().unwrap()).unwrap().with_timezone(&Utc)
+}
+
+/// A new version applies the changes, removes the properties set to null and gets a later `modified` timestamp.
+#[test]
+fn it_makes_new_versions() {
+    let original = malware();
+    let version = original.new_version(json!({"name": "Cryptolocker 2", "description": null, "is_family": true})).unwrap();
+    let value = version.to_value();
+    assert_eq!(value["name"], "Cryptolocker 2");
+    assert_eq!(value["is_family"], true);
+    assert!(value.get("description").is_none());
+    assert_eq!(value["id"], original.to_value()["id"]);
+    assert_eq!(value["created"], original.to_value()["created"]);
+    assert!(modified(version.as_ref()) > modified(original.as_ref()));
+    assert_eq!(modified(version.as_ref()).timestamp_subsec_nanos() % 1_000_000, 0);
+    let version = original.new_version(json!({"modified": "2017-01-01T00:00:00.000Z", "created_by_ref": "identity--f431f809-377b-45e0-aa1c-6a4751cae5ff"})).unwrap();
+    assert_eq!(modified(version.as_ref()), DateTime::parse_from_rfc3339("2017-01-01T00:00:00Z").unwrap());
+}
+
+/// The properties identifying the object cannot change, nor can `modified` go back in time.
+#[test]
+fn it_enforces_the_versioning_rules() {
+    let original = malware();
+    assert_eq!(original.new_version(json!({"id": "malware--1"})).err(), Some(VersioningError::UnmodifiableProperty("id".to_string())));
+    assert_eq!(original.new_version(json!({"created": "2017-01-01T00:00:00Z"})).err(), Some(VersioningError::UnmodifiableProperty("created".to_string())));
+    assert_eq!(original.new_version(json!({"created_by_ref": null})).err(), Some(VersioningError::UnmodifiableProperty("created_by_ref".to_string())));
+    assert_eq!(original.new_version(json!({"modified": "2016-05-12T08:17:27Z"})).err(), Some(VersioningError::ModifiedNotNewer));
+    assert!(matches!(original.new_version(json!({"is_family": "yes"})), Err(VersioningError::InvalidChanges(_))));
+    assert!(matches!(original.new_version(json!(["name"])), Err(VersioningError::InvalidChanges(_))));
+    let domain: Box<dyn STIXObject> = serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#).unwrap();
+    assert_eq!(domain.new_version(json!({"value": "evil.org"})).err(), Some(VersioningError::NotVersionable("domain-name--1".to_string())));
+}
+
+/// Revoking makes the last version of an object.
+#[test]
+fn it_revokes_objects() {
+    let revoked = malware().revoke().unwrap();
+    assert_eq!(revoked.to_value()["revoked"], true);
+    let id = "malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061".to_string();
+    assert_eq!(revoked.new_version(json!({"revoked": false})).err(), Some(VersioningError::Revoked(id.clone())));
+    assert_eq!(revoked.revoke().err(), Some(VersioningError::Revoked(id)));
+}
+
+fn version(modified: &str, properties: serde_json::Value) -> Box<dyn STIXObject> {
+    let mut value = malware().to_value();
+    value["modified"] = json!(modified);
+    value.as_object_mut().unwrap().extend(properties.as_object().unwrap().clone());
+    serde_json::from_value(value).unwrap()
+}
+
+/// Conflicting copies, changes of the identifying properties and versions after revocation are reported, in order.
+#[test]
+fn it_checks_version_histories() {
+    let objects = vec![
+        version("2016-06-01T00:00:00Z", json!({"name": "A"})),
+        version("2016-06-01T00:00:00.000Z", json!({"name": "A"})),
+        version("2016-07-01T00:00:00Z", json!({"name": "B"})),
+        version("2016-07-01T00:00:00Z", json!({"name": "C"})),
+        version("2016-08-01T00:00:00Z", json!({"created": "2016-05-13T00:00:00Z"})),
+        version("2016-09-01T00:00:00Z", json!({"created_by_ref": "identity--other", "revoked": true})),
+        version("2016-10-01T00:00:00Z", json!({})),
+        version("2016-01-01T00:00:00Z", json!({})),
+        serde_json::from_str(r#"{"type": "domain-name", "id": "domain-name--1", "value": "evil.com"}"#).unwrap(),
+    ];
+    let violations: Vec<(String, ViolationKind)> = versioning::check_history(&objects)
+        .into_iter()
+        .map(|violation| (violation.modified.to_rfc3339(), violation.kind))
+        .collect();
+    assert_eq!(
+        violations,
+        [
+            ("2016-01-01T00:00:00+00:00".to_string(), ViolationKind::ModifiedBeforeCreated),
+            ("2016-07-01T00:00:00+00:00".to_string(), ViolationKind::ConflictingDuplicate),
+            ("2016-08-01T00:00:00+00:00".to_string(), ViolationKind::CreatedChanged),
+            ("2016-09-01T00:00:00+00:00".to_string(), ViolationKind::CreatorChanged),
+            ("2016-10-01T00:00:00+00:00".to_string(), ViolationKind::VersionAfterRevocation),
+        ]
+    );
+    let history = vec![version("2016-06-01T00:00:00Z", json!({})), version("2016-07-01T00:00:00Z", json!({"name": "B"}))];
+    assert!(versioning::check_history(&history).is_empty());
+}
+
+/// Stores are checked the same way, and the violations explained.
+#[test]
+fn it_checks_store_histories() {
+    let mut store = MemoryStore::new();
+    store.add(version("2016-06-01T00:00:00Z", json!({"revoked": true}))).unwrap();
+    store.add(version("2016-07-01T00:00:00Z", json!({}))).unwrap();
+    let violations = versioning::check_store_history(&store).unwrap();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].to_string(),
+        "the version of malware--0c7b5b88-8ff7-4a4d-aa9d-feb398cd0061 modified at 2016-07-01T00:00:00Z follows a revoked version"
+    );
+}